@@ -0,0 +1,95 @@
+//! A status panel pinned to the bottom rows of the screen, with no
+//! alternate screen.
+//!
+//! `Viewport::BottomPanel(rows)` scrolls what is already on screen up just
+//! enough to free the bottom rows and draws there, so the shell session
+//! above stays in view. The panel follows the bottom edge when the terminal
+//! resizes. `Viewport::Fixed(rect)` is the same idea for any region you pick
+//! — a corner of a multiplexer pane, say.
+//!
+//! Run with: `cargo run --example bottom_panel`
+
+use anyhow::Result;
+use std::time::Duration;
+use tui_base_framework::layout::{Constraint, Layout};
+use tui_base_framework::style::{Color, Style};
+use tui_base_framework::text::Line;
+use tui_base_framework::widgets::{Block, Paragraph, Spinner};
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig,
+    Viewport, run_with_config,
+};
+
+struct StatusPanel {
+    spinner: Spinner,
+    uptime: Duration,
+    last_key: Option<KeyCode>,
+}
+
+impl Component for StatusPanel {
+    type Message = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" watch ");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [status, keys] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+        self.spinner.set_label(format!(
+            "watching for changes — up {}s",
+            self.uptime.as_secs()
+        ));
+        self.spinner.render(frame, status);
+
+        let last_key = match self.last_key {
+            Some(key) => format!("last key: {key}"),
+            None => "press any key".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("{last_key} | q: quit"),
+                Style::default().fg(Color::DarkGray),
+            )),
+            keys,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if let Event::Tick(elapsed) = event {
+            self.uptime += elapsed;
+            return self.spinner.handle_event(&event);
+        }
+        let Some(key) = event.key() else {
+            return EventResult::Propagate;
+        };
+        if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+            context.quit();
+        }
+        self.last_key = Some(key.code);
+        EventResult::Consumed
+    }
+}
+
+fn main() -> Result<()> {
+    println!("$ watch --panel");
+    println!("Everything above the panel stays where it was.");
+
+    // Four rows: a border, two lines of status, and a border.
+    let config = AppConfig {
+        terminal: TerminalConfig {
+            viewport: Viewport::BottomPanel(4),
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    run_with_config(
+        StatusPanel {
+            spinner: Spinner::braille(),
+            uptime: Duration::ZERO,
+            last_key: None,
+        },
+        config,
+    )
+}
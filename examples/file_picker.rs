@@ -0,0 +1,90 @@
+//! Choosing a file with `FilePicker`, embedded in a parent component.
+//!
+//! The picker handles browsing, filtering, completion and the preview on
+//! its own; choosing a file sends `Message::Picked(path)`, which arrives in
+//! `update` like any other message. Esc is left to the parent, which quits.
+//!
+//! Run with: `cargo run --example file_picker [dir] [glob]`
+//!
+//! For example, `cargo run --example file_picker . '*.rs'` lists only Rust
+//! files (directories stay visible so you can navigate).
+
+use anyhow::Result;
+use std::path::PathBuf;
+use tui_base_framework::layout::{Constraint, Layout};
+use tui_base_framework::style::{Color, Style};
+use tui_base_framework::text::Line;
+use tui_base_framework::widgets::FilePicker;
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig,
+    run_with_config,
+};
+
+enum Message {
+    Picked(PathBuf),
+}
+
+struct Chooser {
+    picker: FilePicker<Message>,
+    chosen: Option<PathBuf>,
+}
+
+impl Component for Chooser {
+    type Message = Message;
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let [status, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+
+        let text = match &self.chosen {
+            Some(path) => format!(" Chosen: {}", path.display()),
+            None => " Nothing chosen yet — Enter picks a file, Esc quits".to_string(),
+        };
+        frame.render_widget(
+            Line::styled(text, Style::default().fg(Color::Green)),
+            status,
+        );
+        self.picker.render(frame, body);
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if event.is_key(KeyCode::Esc) {
+            context.quit();
+            return EventResult::Consumed;
+        }
+        self.picker.handle_event(event, context)
+    }
+
+    fn update(&mut self, message: Self::Message, _context: &Context<Self::Message>) {
+        match message {
+            Message::Picked(path) => self.chosen = Some(path),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let dir = args.next().unwrap_or_else(|| ".".to_string());
+
+    let mut picker = FilePicker::new(dir, Message::Picked);
+    if let Some(glob) = args.next() {
+        picker = picker.filter(glob);
+    }
+
+    // Mouse capture for the wheel and double-clicks; keys work without it.
+    let config = AppConfig {
+        terminal: TerminalConfig {
+            mouse_capture: true,
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    run_with_config(
+        Chooser {
+            picker,
+            chosen: None,
+        },
+        config,
+    )
+}
@@ -0,0 +1,173 @@
+//! A sign-up form with `Form`: typed fields, validation, and submission.
+//!
+//! The form is described once, as a list of fields with their checks; it
+//! handles focus, editing, and error display itself. The username also has
+//! an async check (a pretend server round trip) that runs when you leave
+//! the field, and submission waits for it. Submitting sends the values as
+//! a message, turned into a `Signup` struct on the way.
+//!
+//! Run with: `cargo run --example form`
+
+use anyhow::Result;
+use std::time::Duration;
+use tui_base_framework::layout::{Constraint, Layout};
+use tui_base_framework::style::{Color, Style};
+use tui_base_framework::text::Line;
+use tui_base_framework::widgets::{Block, Field, Form, FormValues, Paragraph};
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig,
+    run_with_config,
+};
+
+struct Signup {
+    username: String,
+    age: u8,
+    plan: String,
+    interests: Vec<String>,
+    newsletter: bool,
+}
+
+impl From<FormValues> for Signup {
+    fn from(values: FormValues) -> Self {
+        Self {
+            username: values.text("username").to_string(),
+            age: values.number("age").unwrap_or_default() as u8,
+            plan: values.choice("plan").unwrap_or_default().to_string(),
+            interests: values.choices("interests").to_vec(),
+            newsletter: values.checked("newsletter"),
+        }
+    }
+}
+
+enum Message {
+    Submitted(Signup),
+}
+
+struct SignupScreen {
+    form: Form<Message>,
+    submitted: Option<Signup>,
+}
+
+/// Pretends to ask a server whether the name is free.
+async fn username_available(name: String) -> Result<(), String> {
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    match name.as_str() {
+        "admin" | "root" => Err(format!("\"{name}\" is taken")),
+        _ => Ok(()),
+    }
+}
+
+fn signup_form() -> Form<Message> {
+    Form::new(|values| Message::Submitted(values.into()))
+        .submit_label("Sign up")
+        .field(
+            Field::text("username", "Username")
+                .required()
+                .placeholder("try \"admin\"")
+                .validate(|value| match value.as_text().len() {
+                    3.. => Ok(()),
+                    _ => Err("At least 3 characters".into()),
+                })
+                .validate_async(|value| username_available(value.as_text().to_string())),
+        )
+        .field(
+            Field::password("password", "Password")
+                .required()
+                .validate(|value| match value.as_text().chars().count() {
+                    8.. => Ok(()),
+                    _ => Err("At least 8 characters".into()),
+                }),
+        )
+        .field(
+            Field::number("age", "Age")
+                .required()
+                .validate(|value| match value.as_number() {
+                    Some(age) if (13.0..=150.0).contains(&age) => Ok(()),
+                    _ => Err("Between 13 and 150".into()),
+                }),
+        )
+        .field(Field::select("plan", "Plan", ["Free", "Pro", "Team"]).initial("Free"))
+        .field(Field::multi_select(
+            "interests",
+            "Interests",
+            ["Rust", "Terminals", "Games"],
+        ))
+        .field(Field::checkbox("newsletter", "Newsletter").initial(true))
+        .field(Field::checkbox("terms", "Accept the terms").required())
+}
+
+impl Component for SignupScreen {
+    type Message = Message;
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let [body, result, footer] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let block = Block::bordered().title(" Sign up ");
+        let inner = block.inner(body);
+        frame.render_widget(block, body);
+        self.form.render(frame, inner);
+
+        let text = match &self.submitted {
+            Some(signup) => format!(
+                "{}, {}, {} plan, interests: [{}], newsletter: {}",
+                signup.username,
+                signup.age,
+                signup.plan,
+                signup.interests.join(", "),
+                if signup.newsletter { "yes" } else { "no" },
+            ),
+            None => "Not submitted yet".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::styled(text, Style::default().fg(Color::Green)))
+                .block(Block::bordered().title(" Submitted ")),
+            result,
+        );
+
+        frame.render_widget(
+            Paragraph::new(
+                " Tab/↑/↓: move | Space: toggle | ←/→: choose | Enter: submit | Esc: quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if event.is_key(KeyCode::Esc) {
+            context.quit();
+            return EventResult::Consumed;
+        }
+        self.form.handle_event(event, context)
+    }
+
+    fn update(&mut self, message: Self::Message, _context: &Context<Self::Message>) {
+        match message {
+            Message::Submitted(signup) => self.submitted = Some(signup),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    // Mouse capture so fields can be clicked; keys work without it.
+    let config = AppConfig {
+        terminal: TerminalConfig {
+            mouse_capture: true,
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    run_with_config(
+        SignupScreen {
+            form: signup_form(),
+            submitted: None,
+        },
+        config,
+    )
+}
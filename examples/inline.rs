@@ -4,8 +4,14 @@
 //!
 //! Output printed before the app starts stays visible above the UI, and the
 //! final frame stays in the scrollback after exit, with the shell prompt
-//! continuing below it. Inline setup queries the cursor position, so this
-//! needs a real interactive terminal (not a pipe).
+//! continuing below it. While it runs, `Context::insert_before` prints a line
+//! above the bar as each chunk completes — those lines scroll up into the
+//! history like a test runner's results, while the bar stays put. Press `d`
+//! and `Context::set_inline_height` opens the viewport into a details panel;
+//! press it again to fold it back.
+//!
+//! Inline setup queries the cursor position, so the live UI needs a real
+//! interactive terminal; piped, the app prints plain text instead.
 //!
 //! Run with: `cargo run --example inline`
 
@@ -13,6 +19,7 @@ use anyhow::Result;
 use std::time::Duration;
 use tui_base_framework::layout::{Constraint, Layout};
 use tui_base_framework::style::{Color, Style};
+use tui_base_framework::text::{Line, Span};
 use tui_base_framework::widgets::{Block, Gauge, Paragraph};
 use tui_base_framework::{
     AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig,
@@ -20,18 +27,31 @@ use tui_base_framework::{
 };
 
 /// How fast the bar fills, in percent per second.
-const FILL_RATE: f64 = 40.0;
+const FILL_RATE: f64 = 10.0;
+
+/// Viewport heights: the bar and help line, and with the details panel open.
+const COMPACT_HEIGHT: u16 = 4;
+const DETAILS_HEIGHT: u16 = 9;
+
+/// The download comes in this many chunks, each logged when it completes.
+const CHUNKS: u32 = 5;
 
 struct InlineDemo {
     percent: f64,
+    chunks_done: u32,
+    details: bool,
 }
 
 impl Component for InlineDemo {
     type Message = ();
 
     fn render(&mut self, frame: &mut Frame, area: Rect) {
-        let [bar, help] =
-            Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).areas(area);
+        let [bar, details, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(area);
 
         frame.render_widget(
             Gauge::default()
@@ -41,8 +61,20 @@ impl Component for InlineDemo {
             bar,
         );
 
+        if self.details {
+            let lines = vec![
+                Line::from(" Mirror:  mirror-2.example.org"),
+                Line::from(format!(" Chunks:  {}/{CHUNKS} verified", self.chunks_done)),
+                Line::from(format!(" Speed:   {FILL_RATE}% per second")),
+            ];
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().title("Details")),
+                details,
+            );
+        }
+
         frame.render_widget(
-            Paragraph::new(" The final frame stays in your scrollback | q to cancel")
+            Paragraph::new(" The final frame stays in your scrollback | d: details | q: cancel")
                 .style(Style::default().fg(Color::DarkGray)),
             help,
         );
@@ -52,11 +84,28 @@ impl Component for InlineDemo {
         match event {
             Event::Tick(elapsed) => {
                 self.percent = (self.percent + elapsed.as_secs_f64() * FILL_RATE).min(100.0);
+                let chunks_done = (self.percent / 100.0 * f64::from(CHUNKS)) as u32;
+                for chunk in self.chunks_done + 1..=chunks_done {
+                    context.insert_before(Line::from(vec![
+                        Span::styled("✓ ", Style::default().fg(Color::Green)),
+                        Span::raw(format!("chunk {chunk}/{CHUNKS} verified")),
+                    ]));
+                }
+                self.chunks_done = chunks_done;
                 if self.percent >= 100.0 {
                     context.quit();
                 }
                 EventResult::Consumed
             }
+            _ if event.is_key(KeyCode::Char('d')) => {
+                self.details = !self.details;
+                context.set_inline_height(if self.details {
+                    DETAILS_HEIGHT
+                } else {
+                    COMPACT_HEIGHT
+                });
+                EventResult::Consumed
+            }
             _ if event.is_key(KeyCode::Char('q')) || event.is_key(KeyCode::Esc) => {
                 context.quit();
                 EventResult::Consumed
@@ -74,13 +123,20 @@ fn main() -> Result<()> {
     let config = AppConfig {
         tick_rate: Duration::from_millis(50),
         terminal: TerminalConfig {
-            viewport: Viewport::Inline(4),
+            viewport: Viewport::Inline(COMPACT_HEIGHT),
             ..TerminalConfig::default()
         },
         ..AppConfig::default()
     };
 
-    run_with_config(InlineDemo { percent: 0.0 }, config)?;
+    run_with_config(
+        InlineDemo {
+            percent: 0.0,
+            chunks_done: 0,
+            details: false,
+        },
+        config,
+    )?;
 
     // ...and the shell (or your program) continues below the final frame.
     println!("done.");
@@ -3,6 +3,9 @@
 //! `Component::render` takes `&mut self`, so widget state like `ListState`
 //! lives directly in your component — no interior mutability needed.
 //!
+//! `List` is built from every item each frame; for thousands of rows or
+//! more, see `virtual_list`.
+//!
 //! Run with: `cargo run --example list_selector`
 
 use anyhow::Result;
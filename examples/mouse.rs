@@ -1,65 +1,123 @@
-//! Mouse input: clicks, drags, and scroll wheel.
+//! Mouse input: clicks, drags, scroll wheel, and hit-testing.
 //!
 //! Mouse capture is off by default (it breaks native text selection), so
 //! this example opts in through `TerminalConfig`.
 //!
+//! Instead of remembering each region's `Rect` and comparing coordinates by
+//! hand, `render` registers what it draws on a `HitMap`; `handle_event` asks
+//! it what's under the pointer and gets hover and click events per region.
+//!
 //! Run with: `cargo run --example mouse`
 
 use anyhow::Result;
 use std::collections::HashSet;
 use tui_base_framework::layout::{Constraint, Layout, Position};
-use tui_base_framework::style::{Color, Style};
+use tui_base_framework::style::{Color, Modifier, Style};
 use tui_base_framework::widgets::{Block, Paragraph};
 use tui_base_framework::{
-    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, MouseButton, MouseEventKind,
-    Rect, TerminalConfig, run_with_config,
+    AppConfig, Component, Context, Event, EventResult, Frame, HitKind, HitMap, KeyCode,
+    MouseButton, MouseEventKind, Rect, TerminalConfig, run_with_config,
 };
 
-const INKS: [Color; 4] = [Color::Cyan, Color::Yellow, Color::Green, Color::Magenta];
+const INKS: [(&str, Color); 4] = [
+    ("Cyan", Color::Cyan),
+    ("Yellow", Color::Yellow),
+    ("Green", Color::Green),
+    ("Magenta", Color::Magenta),
+];
+
+/// Everything clickable, as registered on the hit map.
+#[derive(Clone, Copy, PartialEq)]
+enum Target {
+    Canvas,
+    Swatch(usize),
+}
 
 struct Paint {
     cells: HashSet<(u16, u16)>,
     ink: usize,
-    canvas: Rect,
+    hits: HitMap<Target>,
 }
 
 impl Component for Paint {
     type Message = ();
 
     fn render(&mut self, frame: &mut Frame, area: Rect) {
-        let [canvas_area, help] =
-            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        let [canvas_area, palette, help] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        self.hits.clear();
 
         let block = Block::bordered().title("Canvas");
-        self.canvas = block.inner(canvas_area);
+        let canvas = block.inner(canvas_area);
         frame.render_widget(block, canvas_area);
+        self.hits.register(canvas, Target::Canvas);
 
-        let ink = Style::default().bg(INKS[self.ink]);
+        let ink = Style::default().bg(INKS[self.ink].1);
         for &(x, y) in &self.cells {
-            if self.canvas.contains(Position::new(x, y)) {
+            if canvas.contains(Position::new(x, y)) {
                 frame.buffer_mut().set_style(Rect::new(x, y, 1, 1), ink);
             }
         }
 
+        let swatches = Layout::horizontal([Constraint::Length(11); INKS.len()]).split(palette);
+        for (index, (&(name, color), &swatch)) in INKS.iter().zip(swatches.iter()).enumerate() {
+            let mut style = Style::default().fg(Color::Black).bg(color);
+            if self.ink == index {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            // The hit map knows what's under the pointer; no manual bookkeeping.
+            let label = if self.hits.hovered() == Some(&Target::Swatch(index)) {
+                format!("[{name}]")
+            } else {
+                name.to_string()
+            };
+            frame.render_widget(Paragraph::new(label).centered().style(style), swatch);
+            self.hits.register(swatch, Target::Swatch(index));
+        }
+
         frame.render_widget(
-            Paragraph::new(" Click/drag: paint | Right-click: erase | Scroll: change color | c: clear | q: quit")
-                .style(Style::default().fg(Color::DarkGray)),
+            Paragraph::new(
+                " Click/drag: paint | Right-click: erase | Click swatch or scroll: color | c: clear | q: quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
             help,
         );
     }
 
     fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        // Hover changes redraw the palette; clicks on a swatch pick its ink.
+        let mut consumed = false;
+        for hit in self.hits.handle_event(&event) {
+            match (hit.id, hit.kind) {
+                (Target::Swatch(index), HitKind::Click(MouseButton::Left)) => self.ink = index,
+                (Target::Swatch(_), HitKind::Enter | HitKind::Leave) => {}
+                _ => continue,
+            }
+            consumed = true;
+        }
+
         match event {
             Event::Mouse(mouse) => {
+                let position = Position::new(mouse.column, mouse.row);
+                let on_canvas = self.hits.hit(position) == Some(&Target::Canvas);
                 let cell = (mouse.column, mouse.row);
                 match mouse.kind {
                     MouseEventKind::Down(MouseButton::Left)
-                    | MouseEventKind::Drag(MouseButton::Left) => {
+                    | MouseEventKind::Drag(MouseButton::Left)
+                        if on_canvas =>
+                    {
                         self.cells.insert(cell);
                         EventResult::Consumed
                     }
                     MouseEventKind::Down(MouseButton::Right)
-                    | MouseEventKind::Drag(MouseButton::Right) => {
+                    | MouseEventKind::Drag(MouseButton::Right)
+                        if on_canvas =>
+                    {
                         self.cells.remove(&cell);
                         EventResult::Consumed
                     }
@@ -71,6 +129,7 @@ impl Component for Paint {
                         self.ink = (self.ink + INKS.len() - 1) % INKS.len();
                         EventResult::Consumed
                     }
+                    _ if consumed => EventResult::Consumed,
                     _ => EventResult::Propagate,
                 }
             }
@@ -100,7 +159,7 @@ fn main() -> Result<()> {
         Paint {
             cells: HashSet::new(),
             ink: 0,
-            canvas: Rect::default(),
+            hits: HitMap::new(),
         },
         config,
     )
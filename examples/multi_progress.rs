@@ -0,0 +1,142 @@
+//! A multi-task progress board in an inline viewport, like a package
+//! installer.
+//!
+//! Each download registers with `Context::add_task` and reports through the
+//! returned handle from its own Tokio task; the framework draws a row per
+//! task with throughput and an ETA. The viewport grows as tasks start, and
+//! when one finishes its summary is printed permanently above the viewport
+//! and its row goes away — so the UI shrinks back to the status line as the
+//! work completes, leaving a log of what happened in the scrollback.
+//!
+//! Piped into a file or a CI log there is no terminal to draw on, so the app
+//! falls back to plain text: `plain_text` keeps that output to one status
+//! line per change, with the finished-task summaries printed as they come.
+//!
+//! Run with: `cargo run --example multi_progress`
+
+use anyhow::Result;
+use std::time::Duration;
+use tui_base_framework::style::{Color, Style};
+use tui_base_framework::widgets::Paragraph;
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, ProgressTask, Rect,
+    TerminalConfig, Viewport, run_with_config,
+};
+
+/// Name, size in KiB, and download speed in KiB per 100ms. A size of `0`
+/// stands for a download whose length isn't known up front.
+const PACKAGES: &[(&str, u64, u64)] = &[
+    ("serde 1.0.219", 320, 24),
+    ("tokio 1.52.0", 1_640, 70),
+    ("ratatui 0.30.2", 980, 35),
+    ("regex 1.11.1", 460, 12),
+    ("mirror index", 0, 40),
+    ("openssl-sys 0.9.104", 720, 30),
+];
+
+enum Message {
+    Done,
+}
+
+struct Installer {
+    remaining: usize,
+}
+
+/// The package whose mirror is broken, to show a failure summary.
+const BROKEN: &str = "openssl-sys 0.9.104";
+
+/// Pretends to download `size` KiB (or an unknown amount) at `speed`.
+async fn download(task: ProgressTask, name: &str, size: u64, speed: u64) {
+    let steps = if size == 0 { 30 } else { size.div_ceil(speed) };
+    for step in 0..steps {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        task.inc(speed * 1024);
+        if size == 0 && step == steps / 2 {
+            task.set_message("almost there");
+        }
+    }
+    if size == 0 {
+        task.finish_with_message("index refreshed");
+    } else if name == BROKEN {
+        task.fail("checksum mismatch");
+    } else {
+        task.set_position(size * 1024);
+        task.finish();
+    }
+}
+
+impl Component for Installer {
+    type Message = Message;
+
+    fn init(&mut self, context: &Context<Self::Message>) {
+        for (index, &(name, size, speed)) in PACKAGES.iter().enumerate() {
+            let context = context.clone();
+            tokio::spawn(async move {
+                // Staggered starts, so the viewport visibly grows. Tasks can
+                // register from anywhere that has a context.
+                tokio::time::sleep(Duration::from_millis(300 * index as u64)).await;
+                let task = context.add_task(name, size * 1024).bytes();
+                download(task, name, size, speed).await;
+                let _ = context.sender().send(Message::Done).await;
+            });
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let status = match self.remaining {
+            0 => " Installed.".to_string(),
+            n => format!(" Installing… {n} left | q to cancel"),
+        };
+        frame.render_widget(
+            Paragraph::new(status).style(Style::default().fg(Color::DarkGray)),
+            area,
+        );
+    }
+
+    fn plain_text(&mut self) -> Option<String> {
+        Some(format!("Installing… {} left", self.remaining))
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if event.is_key(KeyCode::Char('q')) || event.is_key(KeyCode::Esc) {
+            context.quit();
+            return EventResult::Consumed;
+        }
+        EventResult::Propagate
+    }
+
+    fn update(&mut self, message: Self::Message, context: &Context<Self::Message>) {
+        match message {
+            Message::Done => {
+                self.remaining -= 1;
+                if self.remaining == 0 {
+                    context.quit();
+                }
+            }
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    println!("$ install --all");
+
+    // One row for the status line; the board adds a row per running task.
+    let config = AppConfig {
+        tick_rate: Duration::from_millis(100),
+        terminal: TerminalConfig {
+            viewport: Viewport::Inline(1),
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    run_with_config(
+        Installer {
+            remaining: PACKAGES.len(),
+        },
+        config,
+    )?;
+
+    println!("done.");
+    Ok(())
+}
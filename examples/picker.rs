@@ -0,0 +1,147 @@
+//! An fzf-style picker that works inside shell substitution.
+//!
+//! The UI draws on the controlling terminal (`OutputTarget::Tty`) instead of
+//! stdout, so stdout stays free for the answer. Enter finishes with
+//! `Context::exit_with(choice)`, and `run_returning` hands the choice back
+//! once the terminal is restored — only the chosen line is printed. Candidates come from stdin when it
+//! is piped; keys are still read from the terminal.
+//!
+//! Run with: `cargo run --example picker`, or try
+//! `echo "you picked $(ls | cargo run -q --example picker)"`
+
+use anyhow::Result;
+use std::io::{self, BufRead, IsTerminal};
+use tui_base_framework::layout::{Constraint, Layout};
+use tui_base_framework::style::{Color, Modifier, Style};
+use tui_base_framework::widgets::{Block, List, ListItem, ListState, TextInput};
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, NonTtyMode, OutputTarget,
+    Rect, TerminalConfig, Viewport, run_returning,
+};
+
+struct Picker {
+    items: Vec<String>,
+    query: TextInput,
+    /// Indices into `items` that match the query.
+    matches: Vec<usize>,
+    state: ListState,
+}
+
+impl Picker {
+    fn new(items: Vec<String>) -> Self {
+        let matches = (0..items.len()).collect();
+        Self {
+            items,
+            query: TextInput::new().placeholder("type to filter"),
+            matches,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn refilter(&mut self) {
+        let query = self.query.value().to_lowercase();
+        self.matches = (0..self.items.len())
+            .filter(|&index| self.items[index].to_lowercase().contains(&query))
+            .collect();
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+}
+
+impl Component for Picker {
+    type Message = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(format!(
+            " {}/{} | Enter: pick, Esc: cancel ",
+            self.matches.len(),
+            self.items.len()
+        ));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [prompt, list] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+        self.query.render(frame, prompt, true);
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|&index| ListItem::new(self.items[index].as_str()))
+            .collect();
+        let list_widget = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("► ");
+        frame.render_stateful_widget(list_widget, list, &mut self.state);
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        let Some(key) = event.key() else {
+            return EventResult::Propagate;
+        };
+        match key.code {
+            KeyCode::Up => self.state.select_previous(),
+            KeyCode::Down => self.state.select_next(),
+            KeyCode::Enter => {
+                let choice = self
+                    .state
+                    .selected()
+                    .and_then(|selected| self.matches.get(selected));
+                if let Some(&index) = choice {
+                    context.exit_with(self.items[index].clone());
+                }
+            }
+            KeyCode::Esc => context.quit(),
+            _ => {
+                if self.query.handle_event(&event).is_consumed() {
+                    self.refilter();
+                    return EventResult::Consumed;
+                }
+                return EventResult::Propagate;
+            }
+        }
+        EventResult::Consumed
+    }
+}
+
+fn main() -> Result<()> {
+    let items = if io::stdin().is_terminal() {
+        [
+            "Rust",
+            "Python",
+            "JavaScript",
+            "Go",
+            "TypeScript",
+            "C++",
+            "Java",
+        ]
+        .map(String::from)
+        .to_vec()
+    } else {
+        io::stdin().lock().lines().collect::<io::Result<_>>()?
+    };
+
+    let config = AppConfig {
+        // A picker without keys would wait forever, so never fall back to
+        // plain output.
+        non_tty: NonTtyMode::Fail,
+        terminal: TerminalConfig {
+            viewport: Viewport::BottomPanel(12),
+            output: OutputTarget::Tty,
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    // `None` when cancelled with Esc or Ctrl-C. The terminal is restored by
+    // now, so the choice is the only thing on stdout.
+    let choice: Option<String> = run_returning(Picker::new(items), config)?;
+    if let Some(choice) = choice {
+        println!("{choice}");
+    }
+    Ok(())
+}
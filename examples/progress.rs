@@ -1,4 +1,4 @@
-//! Tick-driven animation with a progress gauge.
+//! Tick-driven animation: a progress gauge, spinners, and a bouncing bar.
 //!
 //! `Event::Tick` fires at `AppConfig::tick_rate` (250ms by default; this
 //! example speeds it up for a smoother animation) and carries the time
@@ -6,13 +6,17 @@
 //! animation speed independent of the tick rate — try changing `tick_rate`
 //! below and the bar still fills at the same pace.
 //!
+//! The gauge is for work you can measure. For work you can't, `Spinner`
+//! and `BouncingBar` take the same elapsed time and work out their frame
+//! from it, so they keep pace too.
+//!
 //! Run with: `cargo run --example progress`
 
 use anyhow::Result;
 use std::time::Duration;
 use tui_base_framework::layout::{Constraint, Layout};
 use tui_base_framework::style::{Color, Style};
-use tui_base_framework::widgets::{Block, Gauge, Paragraph};
+use tui_base_framework::widgets::{Block, BouncingBar, Gauge, Paragraph, Spinner};
 use tui_base_framework::{
     AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, run_with_config,
 };
@@ -23,15 +27,18 @@ const FILL_RATE: f64 = 25.0;
 struct ProgressDemo {
     percent: f64,
     paused: bool,
+    spinners: [Spinner; 3],
+    bar: BouncingBar,
 }
 
 impl Component for ProgressDemo {
     type Message = ();
 
     fn render(&mut self, frame: &mut Frame, area: Rect) {
-        let [title, bar, info, controls] = Layout::vertical([
+        let [title, bar, indeterminate, info, controls] = Layout::vertical([
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(5),
             Constraint::Min(0),
             Constraint::Length(3),
         ])
@@ -52,6 +59,17 @@ impl Component for ProgressDemo {
             bar,
         );
 
+        let block = Block::bordered().title("Indeterminate");
+        let inner = block.inner(indeterminate);
+        frame.render_widget(block, indeterminate);
+        let rows = Layout::vertical([Constraint::Length(1); 3]).split(inner);
+        let [spinners, _, bouncing] = [rows[0], rows[1], rows[2]];
+        let columns = Layout::horizontal([Constraint::Ratio(1, 3); 3]).split(spinners);
+        for (spinner, column) in self.spinners.iter().zip(columns.iter()) {
+            spinner.render(frame, *column);
+        }
+        self.bar.render(frame, bouncing);
+
         let status = if self.paused { "PAUSED" } else { "RUNNING" };
         frame.render_widget(
             Paragraph::new(format!(
@@ -74,6 +92,10 @@ impl Component for ProgressDemo {
         match event {
             Event::Tick(elapsed) if !self.paused => {
                 self.percent = (self.percent + elapsed.as_secs_f64() * FILL_RATE) % 100.0;
+                for spinner in &mut self.spinners {
+                    spinner.handle_event(&event);
+                }
+                self.bar.handle_event(&event);
                 EventResult::Consumed
             }
             Event::Key(key) => match key.code {
@@ -83,6 +105,7 @@ impl Component for ProgressDemo {
                 }
                 KeyCode::Char('r') | KeyCode::Char('R') => {
                     self.percent = 0.0;
+                    self.bar.reset();
                     self.paused = false;
                     EventResult::Consumed
                 }
@@ -106,6 +129,12 @@ fn main() -> Result<()> {
     let component = ProgressDemo {
         percent: 0.0,
         paused: false,
+        spinners: [
+            Spinner::braille().label("Braille"),
+            Spinner::line().label("Line"),
+            Spinner::dots().label("Dots"),
+        ],
+        bar: BouncingBar::new(),
     };
 
     run_with_config(component, config)
@@ -0,0 +1,131 @@
+//! Record a session, check it replays the same, and watch it back.
+//!
+//! With no argument, runs a small note-taking component headlessly with
+//! scripted input and `record_session` set, replays the recording against a
+//! fresh component to confirm every frame comes out the same, then plays the
+//! recorded frames in the terminal. Pass a session file instead to watch
+//! that one — say, one recorded by your own app for a bug report.
+//!
+//! Run with: `cargo run --example replay`, or
+//! `cargo run --example replay -- session.txt`
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::time::Duration;
+use tui_base_framework::backend::TestBackend;
+use tui_base_framework::widgets::{Block, Paragraph};
+use tui_base_framework::{
+    App, AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, ScriptedInput,
+    Session, SessionPlayer, Terminal,
+};
+
+const SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+
+/// Types notes, with a spinner driven by tick time so the replay has an
+/// animation to reproduce.
+#[derive(Default)]
+struct Notes {
+    lines: Vec<String>,
+    draft: String,
+    elapsed: Duration,
+}
+
+impl Component for Notes {
+    type Message = String;
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let spinner = SPINNER[(self.elapsed.as_millis() / 100) as usize % SPINNER.len()];
+        let mut text = self.lines.join("\n");
+        text.push_str(&format!("\n> {}{spinner}", self.draft));
+        frame.render_widget(
+            Paragraph::new(text).block(Block::bordered().title(" notes ")),
+            area,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if let Event::Tick(elapsed) = event {
+            self.elapsed += elapsed;
+            return EventResult::Consumed;
+        }
+        let Some(key) = event.key() else {
+            return EventResult::Propagate;
+        };
+        match key.code {
+            KeyCode::Char(c) => self.draft.push(c),
+            KeyCode::Backspace => {
+                self.draft.pop();
+            }
+            // Saving goes through `update`, so the recording counts a message.
+            KeyCode::Enter => {
+                let _ = context.try_send(std::mem::take(&mut self.draft));
+            }
+            KeyCode::Esc => context.quit(),
+            _ => return EventResult::Propagate,
+        }
+        EventResult::Consumed
+    }
+
+    fn update(&mut self, line: String, _context: &Context<Self::Message>) {
+        self.lines.push(line);
+    }
+}
+
+/// Runs `Notes` offscreen with scripted keys, recording to a temp file.
+async fn record_demo() -> Result<PathBuf> {
+    let path = std::env::temp_dir().join("tui-base-framework-replay-demo.txt");
+    let config = AppConfig {
+        tick_rate: Duration::from_millis(50),
+        record_session: Some(path.clone()),
+        ..AppConfig::default()
+    };
+    let input = ScriptedInput::new([])
+        .with_delay(Duration::from_millis(60))
+        .text("buy milk")
+        .key(KeyCode::Enter)
+        .text("fix the bug")
+        .key(KeyCode::Backspace)
+        .key(KeyCode::Backspace)
+        .key(KeyCode::Backspace)
+        .text("typo")
+        .key(KeyCode::Enter)
+        .pause(Duration::from_millis(300))
+        .key(KeyCode::Esc);
+
+    let terminal = Terminal::new(TestBackend::new(40, 8))?;
+    App::with_backend(Notes::default(), terminal, config)
+        .with_input(input)
+        .run()
+        .await?;
+    Ok(path)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let (path, check) = match std::env::args_os().nth(1) {
+        Some(path) => (PathBuf::from(path), false),
+        None => (record_demo().await?, true),
+    };
+    let session = Session::open(&path)?;
+
+    let report = if check {
+        let frames = session.replay(Notes::default()).await?;
+        match frames.iter().find(|frame| !frame.matches()) {
+            Some(frame) => format!("replay diverged at {:?}", frame.at),
+            None => format!("all {} frames replayed identically", frames.len()),
+        }
+    } else {
+        format!("{} frames in {}", session.frames().len(), path.display())
+    };
+
+    let config = AppConfig {
+        tick_rate: Duration::from_millis(20),
+        ..AppConfig::default()
+    };
+    App::with_config(SessionPlayer::new(session.frames()), config)?
+        .run()
+        .await?;
+
+    println!("{report}");
+    Ok(())
+}
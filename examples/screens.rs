@@ -180,6 +180,9 @@ impl Component for HomeScreen {
 // Editor screen: two reusable text fields sharing focus.
 // ---------------------------------------------------------------------------
 
+// Fine for two fields; for more, `widgets::Form` (see `examples/form.rs`)
+// does the focus, validation, and submission for you.
+
 struct EditorScreen {
     index: usize,
     title: TextField,
@@ -0,0 +1,134 @@
+//! Scrolling long content with `ScrollView`.
+//!
+//! The view draws the whole document into an off-screen buffer and shows a
+//! window of it, with a scrollbar. It handles the arrow keys, PageUp/PageDown,
+//! Home/End, and the mouse wheel itself; the component only decides what to
+//! draw — and here, jumps between sections with `scroll_to`.
+//!
+//! Run with: `cargo run --example scroll_view`
+
+use anyhow::Result;
+use tui_base_framework::layout::{Constraint, Layout, Position, Size};
+use tui_base_framework::style::{Color, Modifier, Style};
+use tui_base_framework::text::{Line, Text};
+use tui_base_framework::widgets::{Block, Paragraph, ScrollView, Widget};
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig,
+    run_with_config,
+};
+
+const SECTIONS: [&str; 6] = [
+    "Installation",
+    "Configuration",
+    "Usage",
+    "Troubleshooting",
+    "Changelog",
+    "License",
+];
+
+struct Document {
+    text: Text<'static>,
+    /// The row each section heading starts on.
+    headings: Vec<u16>,
+    section: usize,
+    scroll: ScrollView,
+}
+
+impl Document {
+    fn new() -> Self {
+        let mut lines = Vec::new();
+        let mut headings = Vec::new();
+
+        for (index, title) in SECTIONS.iter().enumerate() {
+            headings.push(lines.len() as u16);
+            lines.push(Line::styled(
+                format!("{}. {title}", index + 1),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            for paragraph in 1..=12 {
+                lines.push(Line::from(format!(
+                    "   {title}, line {paragraph}: the view only copies the rows that fit on screen."
+                )));
+            }
+            lines.push(Line::default());
+        }
+
+        Self {
+            text: Text::from(lines),
+            headings,
+            section: 0,
+            scroll: ScrollView::new(),
+        }
+    }
+
+    fn jump(&mut self, section: usize) {
+        self.section = section;
+        self.scroll
+            .scroll_to(Position::new(0, self.headings[section]));
+    }
+}
+
+impl Component for Document {
+    type Message = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let [body, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+
+        let block = Block::bordered().title(format!(" {} ", SECTIONS[self.section]));
+        let inner = block.inner(body);
+        frame.render_widget(block, body);
+
+        let text = &self.text;
+        let size = Size::new(0, text.height() as u16);
+        self.scroll.render(frame, inner, size, |area, buf| {
+            Paragraph::new(text.clone()).render(area, buf);
+        });
+
+        frame.render_widget(
+            Paragraph::new(
+                " ↑/↓ PgUp/PgDn Home/End or wheel: scroll | n/p: next/previous section | q: quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if self.scroll.handle_event(&event).is_consumed() {
+            return EventResult::Consumed;
+        }
+
+        if event.is_key(KeyCode::Char('n')) {
+            self.jump((self.section + 1).min(SECTIONS.len() - 1));
+            return EventResult::Consumed;
+        }
+
+        if event.is_key(KeyCode::Char('p')) {
+            self.jump(self.section.saturating_sub(1));
+            return EventResult::Consumed;
+        }
+
+        if event.is_key(KeyCode::Char('q')) || event.is_key(KeyCode::Esc) {
+            context.quit();
+            return EventResult::Consumed;
+        }
+
+        EventResult::Propagate
+    }
+}
+
+fn main() -> Result<()> {
+    // Mouse capture for the wheel; everything else works without it.
+    let config = AppConfig {
+        terminal: TerminalConfig {
+            mouse_capture: true,
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    run_with_config(Document::new(), config)
+}
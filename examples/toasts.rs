@@ -0,0 +1,142 @@
+//! Toast notifications with `Context::notify` and `Context::toast`.
+//!
+//! Nothing here reserves screen space for status messages: the app loop
+//! draws toasts on top of the component, stacks them in a corner, and
+//! removes them when their time is up. Deleting an item shows a toast with
+//! an Undo action — while it is the newest toast, `u` sends `Message::Undo`.
+//!
+//! Run with: `cargo run --example toasts`
+
+use anyhow::Result;
+use std::time::Duration;
+use tui_base_framework::layout::{Constraint, Layout};
+use tui_base_framework::style::{Color, Modifier, Style};
+use tui_base_framework::widgets::{Block, List, ListItem, ListState, Paragraph};
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig, Toast,
+    ToastConfig, ToastCorner, ToastLevel, run_with_config,
+};
+
+enum Message {
+    Undo,
+    Synced(usize),
+}
+
+struct Inbox {
+    items: Vec<String>,
+    deleted: Option<(usize, String)>,
+    state: ListState,
+}
+
+impl Inbox {
+    fn delete_selected(&mut self, context: &Context<Message>) {
+        let Some(index) = self.state.selected().filter(|&i| i < self.items.len()) else {
+            return;
+        };
+        let item = self.items.remove(index);
+        context.toast(
+            Toast::new(ToastLevel::Info, format!("Deleted \"{item}\""))
+                .action(KeyCode::Char('u'), "Undo", Message::Undo)
+                .duration(Duration::from_secs(8)),
+        );
+        self.deleted = Some((index, item));
+        if index >= self.items.len() {
+            self.state.select(self.items.len().checked_sub(1));
+        }
+    }
+}
+
+impl Component for Inbox {
+    type Message = Message;
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let [body, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| ListItem::new(item.as_str()))
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(" Inbox "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, body, &mut self.state);
+
+        frame.render_widget(
+            Paragraph::new(
+                " ↑/↓: move | d: delete | s: save | w: warn | e: error | y: sync | click toast: dismiss | q: quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        match event.char() {
+            Some('d') => self.delete_selected(context),
+            Some('s') => context.notify(ToastLevel::Success, "Saved"),
+            Some('w') => context.notify(ToastLevel::Warning, "Disk almost full"),
+            Some('e') => context.notify(
+                ToastLevel::Error,
+                "Connection lost — the server stopped answering; retrying in the background",
+            ),
+            Some('y') => {
+                // Toasts can come from background tasks too.
+                let context = context.clone();
+                let count = self.items.len();
+                tokio::spawn(async move {
+                    context.notify(ToastLevel::Info, "Syncing…");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    let _ = context.sender().send(Message::Synced(count)).await;
+                });
+            }
+            Some('q') => context.quit(),
+            _ if event.is_key(KeyCode::Esc) => context.quit(),
+            _ if event.is_key(KeyCode::Down) => self.state.select_next(),
+            _ if event.is_key(KeyCode::Up) => self.state.select_previous(),
+            _ => return EventResult::Propagate,
+        }
+        EventResult::Consumed
+    }
+
+    fn update(&mut self, message: Self::Message, context: &Context<Self::Message>) {
+        match message {
+            Message::Undo => {
+                if let Some((index, item)) = self.deleted.take() {
+                    self.items.insert(index.min(self.items.len()), item);
+                    self.state.select(Some(index));
+                    context.notify(ToastLevel::Success, "Restored");
+                }
+            }
+            Message::Synced(count) => {
+                context.notify(ToastLevel::Success, format!("Synced {count} items"));
+            }
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    // Mouse capture so a click can dismiss a toast.
+    let config = AppConfig {
+        toasts: ToastConfig {
+            corner: ToastCorner::BottomRight,
+            ..ToastConfig::default()
+        },
+        terminal: TerminalConfig {
+            mouse_capture: true,
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    let items = (1..=12).map(|n| format!("Message #{n}")).collect();
+    run_with_config(
+        Inbox {
+            items,
+            deleted: None,
+            state: ListState::default().with_selected(Some(0)),
+        },
+        config,
+    )
+}
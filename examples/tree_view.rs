@@ -0,0 +1,143 @@
+//! A directory browser with `TreeView`, loading folders in the background.
+//!
+//! Every directory starts as a lazy node. Expanding one queues it;
+//! `spawn_loads` runs the listing on the runtime and sends the result back
+//! as a message, and `update` hands it to the tree. Until then the node
+//! shows a spinner — listings are delayed a little so you can see it.
+//!
+//! Run with: `cargo run --example tree_view [path]`
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tui_base_framework::layout::{Constraint, Layout};
+use tui_base_framework::style::{Color, Style};
+use tui_base_framework::widgets::{Block, NodeId, Paragraph, TreeNode, TreeView};
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig,
+    run_with_config,
+};
+
+enum Message {
+    Listed(NodeId, std::io::Result<Vec<TreeNode<PathBuf>>>),
+}
+
+struct Browser {
+    tree: TreeView<PathBuf>,
+}
+
+impl Browser {
+    /// Starts listing any folders that were just opened.
+    fn spawn_loads(&mut self, context: &Context<Message>) {
+        self.tree.spawn_loads(context, |id, path: &PathBuf| {
+            let path = path.clone();
+            async move { Message::Listed(id, list(path).await) }
+        });
+    }
+}
+
+/// Lists `path`: directories first, each lazy, then files.
+async fn list(path: PathBuf) -> std::io::Result<Vec<TreeNode<PathBuf>>> {
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    tokio::task::spawn_blocking(move || {
+        let mut entries: Vec<(bool, String, PathBuf)> = std::fs::read_dir(&path)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                let path = entry.path();
+                (!path.is_dir(), file_name(&path), path)
+            })
+            .collect();
+        entries.sort();
+        Ok(entries
+            .into_iter()
+            .map(|(is_file, name, path)| {
+                if is_file {
+                    TreeNode::leaf(name, path)
+                } else {
+                    TreeNode::lazy(name, path)
+                }
+            })
+            .collect())
+    })
+    .await?
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into(),
+    )
+}
+
+impl Component for Browser {
+    type Message = Message;
+
+    fn init(&mut self, context: &Context<Self::Message>) {
+        if let Some(root) = self.tree.selected() {
+            self.tree.expand(root);
+            self.spawn_loads(context);
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let [body, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+
+        let title = self
+            .tree
+            .selected()
+            .and_then(|id| self.tree.value(id))
+            .map_or_else(String::new, |path| format!(" {} ", path.display()));
+        let block = Block::bordered().title(title);
+        let inner = block.inner(body);
+        frame.render_widget(block, body);
+        self.tree.render(frame, inner);
+
+        frame.render_widget(
+            Paragraph::new(
+                " ↑/↓: move | →/←: open/close | Enter: toggle | double-click: toggle | q: quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if self.tree.handle_event(&event).is_consumed() {
+            self.spawn_loads(context);
+            return EventResult::Consumed;
+        }
+
+        if event.is_key(KeyCode::Char('q')) || event.is_key(KeyCode::Esc) {
+            context.quit();
+            return EventResult::Consumed;
+        }
+
+        EventResult::Propagate
+    }
+
+    fn update(&mut self, message: Self::Message, _context: &Context<Self::Message>) {
+        match message {
+            Message::Listed(id, Ok(children)) => self.tree.set_children(id, children),
+            Message::Listed(id, Err(error)) => self.tree.set_load_error(id, error.to_string()),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let root = std::env::args_os()
+        .nth(1)
+        .map_or_else(|| PathBuf::from("."), PathBuf::from);
+
+    // Mouse capture for clicks and the wheel; keys work without it.
+    let config = AppConfig {
+        terminal: TerminalConfig {
+            mouse_capture: true,
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    let tree = TreeView::new([TreeNode::lazy(file_name(&root), root)]);
+    run_with_config(Browser { tree }, config)
+}
@@ -0,0 +1,139 @@
+//! A million-row list with `VirtualList`.
+//!
+//! `list_selector` builds a Ratatui `List` from every item each frame, which
+//! is fine for a handful of rows. Here the rows aren't even stored: the
+//! `ListSource` formats each one when the list asks for it, and the list
+//! only asks for the rows on screen. Every tenth entry is two rows tall.
+//!
+//! New entries arrive at the top every second; `VirtualList::insert` keeps
+//! the selection on the same entry and the view where it was.
+//!
+//! Run with: `cargo run --example virtual_list`
+
+use anyhow::Result;
+use std::time::Duration;
+use tui_base_framework::buffer::Buffer;
+use tui_base_framework::layout::{Constraint, Layout};
+use tui_base_framework::style::{Color, Modifier, Style};
+use tui_base_framework::text::{Line, Text};
+use tui_base_framework::widgets::{Block, ListSource, Paragraph, VirtualList, Widget};
+use tui_base_framework::{
+    AppConfig, Component, Context, Event, EventResult, Frame, KeyCode, Rect, TerminalConfig,
+    run_with_config,
+};
+
+/// Log entries numbered from 1, newest first. Nothing is stored.
+struct Log {
+    newest: usize,
+}
+
+impl Log {
+    fn entry(&self, index: usize) -> usize {
+        self.newest - index
+    }
+}
+
+impl ListSource for Log {
+    fn len(&self) -> usize {
+        self.newest
+    }
+
+    fn height(&self, index: usize) -> u16 {
+        if self.entry(index).is_multiple_of(10) {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn render(&self, index: usize, area: Rect, buf: &mut Buffer) {
+        let entry = self.entry(index);
+        let mut text = Text::from(format!("#{entry:>7}  request handled in {}ms", entry % 97));
+        if self.height(index) == 2 {
+            text.push_line(Line::styled(
+                "          slow path: cache miss, refilled",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        text.render(area, buf);
+    }
+}
+
+struct Viewer {
+    log: Log,
+    list: VirtualList,
+    since_last: Duration,
+}
+
+impl Component for Viewer {
+    type Message = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let [body, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+
+        let title = match self.list.selected() {
+            Some(index) => format!(" Log: entry #{} selected ", self.log.entry(index)),
+            None => format!(" Log: {} entries ", self.log.len()),
+        };
+        let block = Block::bordered().title(title);
+        let inner = block.inner(body);
+        frame.render_widget(block, body);
+        self.list.render(frame, inner, &self.log);
+
+        frame.render_widget(
+            Paragraph::new(
+                " ↑/↓ PgUp/PgDn Home/End: select | wheel: scroll | click: select | q: quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if self.list.handle_event(&event, &self.log).is_consumed() {
+            return EventResult::Consumed;
+        }
+
+        match event {
+            Event::Tick(elapsed) => {
+                self.since_last += elapsed;
+                if self.since_last >= Duration::from_secs(1) {
+                    self.since_last = Duration::ZERO;
+                    self.log.newest += 1;
+                    self.list.insert(0, 1);
+                    return EventResult::Consumed;
+                }
+                EventResult::Propagate
+            }
+            _ if event.is_key(KeyCode::Char('q')) || event.is_key(KeyCode::Esc) => {
+                context.quit();
+                EventResult::Consumed
+            }
+            _ => EventResult::Propagate,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    // Mouse capture for the wheel and clicks; the keys work without it.
+    let config = AppConfig {
+        terminal: TerminalConfig {
+            mouse_capture: true,
+            ..TerminalConfig::default()
+        },
+        ..AppConfig::default()
+    };
+
+    let viewer = Viewer {
+        log: Log { newest: 1_000_000 },
+        list: VirtualList::new().highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        since_last: Duration::ZERO,
+    };
+    run_with_config(viewer, config)
+}
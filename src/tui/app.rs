@@ -1,21 +1,36 @@
 //! The app loop: terminal lifecycle, event pump, and redraw scheduling.
 
-use crate::tui::component::{Component, Context};
+use crate::tui::clipboard::{self, ClipboardMode};
+use crate::tui::component::{Component, Context, Request};
 use crate::tui::event::Event;
-use crate::tui::terminal::{TerminalConfig, TerminalGuard};
-use anyhow::{Context as AnyhowContext, Result};
-use crossterm::event;
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
-};
+use crate::tui::gesture::{GestureConfig, GestureRecognizer};
+#[cfg(feature = "event-stream")]
+use crate::tui::input::EventStreamInput;
+#[cfg(not(feature = "event-stream"))]
+use crate::tui::input::TerminalInput;
+use crate::tui::input::{EventSender, InputSource, RuntimeEvent};
+use crate::tui::plain::PlainOutput;
+use crate::tui::progress::{ProgressBoard, ProgressConfig};
+use crate::tui::screenshot::{self, ScreenshotConfig};
+use crate::tui::selection::Selection;
+use crate::tui::session::Recorder;
+use crate::tui::terminal::{TerminalBackend, TerminalConfig, TerminalGuard, Viewport};
+use crate::tui::toast::{ToastConfig, ToastLevel, Toasts};
+use anyhow::{Context as AnyhowContext, Result, bail};
+use crossterm::{clipboard::CopyToClipboard, execute};
+use ratatui::backend::Backend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget;
+use ratatui::{Frame, Terminal};
+use std::error::Error as StdError;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
-use tokio::task::JoinHandle;
 use tokio::time::MissedTickBehavior;
 
-type RuntimeEvent = Result<Event>;
-
 /// Runs `component` until it quits, creating the Tokio runtime for you.
 ///
 /// This is all a typical `main` needs:
@@ -35,12 +50,42 @@ pub fn run<C: Component>(component: C) -> Result<()> {
 
 /// Like [`run`], with a custom [`AppConfig`].
 pub fn run_with_config<C: Component>(component: C, config: AppConfig) -> Result<()> {
-    let runtime = tokio::runtime::Builder::new_multi_thread()
+    runtime()?.block_on(async move { App::with_config(component, config)?.run().await })
+}
+
+/// Like [`run_with_config`], for components that finish with an answer:
+/// returns the value passed to [`Context::exit_with`] once the terminal is
+/// restored, or `None` if the app quit another way (Esc, Ctrl-C,
+/// [`Context::quit`]).
+///
+/// ```ignore
+/// let choice: Option<String> = run_returning(Picker::new(items), config)?;
+/// if let Some(choice) = choice {
+///     println!("{choice}");
+/// }
+/// ```
+///
+/// Fails if the component exited with a value of a type other than `T`.
+pub fn run_returning<T, C>(component: C, config: AppConfig) -> Result<Option<T>>
+where
+    T: Send + 'static,
+    C: Component,
+{
+    // The app is dropped at the end of the block, restoring the terminal
+    // before the caller sees the value.
+    runtime()?.block_on(async move {
+        let mut app = App::with_config(component, config)?;
+        app.run().await?;
+        app.take_exit_value()
+    })
+}
+
+/// The runtime behind [`run`] and its variants.
+fn runtime() -> Result<tokio::runtime::Runtime> {
+    tokio::runtime::Builder::new_multi_thread()
         .enable_time()
         .build()
-        .context("build tokio runtime")?;
-
-    runtime.block_on(async move { App::with_config(component, config)?.run().await })
+        .context("build tokio runtime")
 }
 
 /// Runtime tuning knobs. Start from [`AppConfig::default`] and override what
@@ -50,7 +95,9 @@ pub struct AppConfig {
     /// How often [`Event::Tick`] fires. Lower it for smoother animation.
     pub tick_rate: Duration,
     /// How long the input thread blocks waiting for terminal input before
-    /// checking for shutdown. Rarely needs tuning.
+    /// checking for shutdown (see [`crate::tui::TerminalInput`]).
+    /// Unused with the `event-stream` feature, which doesn't poll. Rarely
+    /// needs tuning.
     pub input_poll_rate: Duration,
     /// Capacity of the event and message channels.
     pub channel_capacity: usize,
@@ -62,8 +109,36 @@ pub struct AppConfig {
     /// (`fg`). As with Ctrl-C, the component sees the key press first. Unix
     /// only — on Windows the key reaches the component like any other.
     pub suspend_on_ctrl_z: bool,
+    /// How [`Context::copy_to_clipboard`] reaches the clipboard.
+    pub clipboard: ClipboardMode,
+    /// Select text with the mouse, restoring what mouse capture takes away:
+    /// drag to select (Alt+drag for a rectangle), release to copy to the
+    /// clipboard. The component sees every mouse event first; only presses
+    /// and drags it propagates select. Needs `TerminalConfig::mouse_capture`.
+    pub text_selection: bool,
+    /// Thresholds for the double/triple clicks, drags, and long presses
+    /// delivered as [`Event::Gesture`].
+    pub gestures: GestureConfig,
+    /// Where [`Context::notify`] toasts appear and how long they stay.
+    pub toasts: ToastConfig,
+    /// How the [`Context::add_task`] progress board behaves.
+    pub progress: ProgressConfig,
+    /// A key that saves a screenshot of the frame, and where it goes.
+    pub screenshots: ScreenshotConfig,
+    /// What to do when there is no terminal to take over — stdout is a pipe
+    /// or a CI log (see [`is_interactive`](crate::tui::is_interactive)).
+    pub non_tty: NonTtyMode,
     /// Terminal features to enable (mouse capture, bracketed paste, ...).
     pub terminal: TerminalConfig,
+    /// Record the events the component got, message counts, and frames to
+    /// this file, to reproduce a bug later with [`Session`](crate::tui::Session).
+    /// The file is replaced when the app starts running.
+    pub record_session: Option<PathBuf>,
+    /// Record what the app draws to this file in asciinema's `.cast`
+    /// format, for demos (see [`TerminalGuard::record_cast`]). Only apps
+    /// that take over the terminal record; plain output and
+    /// [`App::with_backend`] don't.
+    pub record_cast: Option<PathBuf>,
 }
 
 impl Default for AppConfig {
@@ -74,11 +149,36 @@ impl Default for AppConfig {
             channel_capacity: 256,
             quit_on_ctrl_c: true,
             suspend_on_ctrl_z: true,
+            clipboard: ClipboardMode::default(),
+            text_selection: false,
+            gestures: GestureConfig::default(),
+            toasts: ToastConfig::default(),
+            progress: ProgressConfig::default(),
+            screenshots: ScreenshotConfig::default(),
+            non_tty: NonTtyMode::default(),
             terminal: TerminalConfig::default(),
+            record_session: None,
+            record_cast: None,
         }
     }
 }
 
+/// How an app runs when stdout or input isn't a terminal. Part of
+/// [`AppConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonTtyMode {
+    /// Run anyway, writing plain text lines instead of drawing (the
+    /// default). The component receives no input events, only ticks and
+    /// messages, so it must finish by itself. After each render the frame —
+    /// or the component's [`plain_text`](Component::plain_text) summary —
+    /// is written as text when it differs from the last write; toasts and
+    /// [`Context::insert_before`] lines are written as they come.
+    #[default]
+    Plain,
+    /// Fail at startup with an error saying there is no terminal.
+    Fail,
+}
+
 impl AppConfig {
     fn channel_capacity(&self) -> usize {
         self.channel_capacity.max(1)
@@ -88,6 +188,7 @@ impl AppConfig {
         non_zero_duration(self.tick_rate, Duration::from_millis(250))
     }
 
+    #[cfg(not(feature = "event-stream"))]
     fn input_poll_rate(&self) -> Duration {
         non_zero_duration(self.input_poll_rate, Duration::from_millis(50))
     }
@@ -96,16 +197,31 @@ impl AppConfig {
 /// Owns the terminal and drives a [`Component`].
 ///
 /// Construction puts the terminal into raw mode and the alternate screen;
-/// dropping the `App` (or panicking) restores it.
-pub struct App<C>
+/// dropping the `App` (or panicking) restores it. Without a terminal, see
+/// [`AppConfig::non_tty`].
+///
+/// `B` is the Ratatui backend drawn on. [`App::new`] and [`App::with_config`]
+/// take over the real terminal through crossterm; [`App::with_backend`]
+/// drives any other backend — a `TestBackend` in tests, an in-memory
+/// backend on a server — with input from [`App::with_input`].
+pub struct App<C, B = TerminalBackend>
 where
     C: Component,
+    B: Backend,
 {
-    terminal_guard: TerminalGuard,
+    screen: Screen<B>,
+    input: Option<Box<dyn InputSource>>,
     component: C,
     config: AppConfig,
     context: Context<C::Message>,
     message_rx: mpsc::Receiver<C::Message>,
+    selection: Selection,
+    gestures: GestureRecognizer,
+    toasts: Toasts,
+    progress: ProgressBoard,
+    recorder: Option<Recorder>,
+    /// Save the next frame drawn as a screenshot.
+    screenshot_pending: bool,
     should_quit: bool,
 }
 
@@ -120,17 +236,86 @@ where
 
     /// Creates an app with a custom [`AppConfig`] and takes over the terminal.
     pub fn with_config(component: C, config: AppConfig) -> Result<Self> {
-        let terminal_guard = TerminalGuard::with_config(config.terminal)?;
+        if config.terminal.output.is_interactive() || config.non_tty == NonTtyMode::Fail {
+            let mut guard = TerminalGuard::with_config(config.terminal)?;
+            if let Some(path) = &config.record_cast {
+                guard.record_cast(path)?;
+            }
+            #[cfg(feature = "event-stream")]
+            let input = EventStreamInput;
+            #[cfg(not(feature = "event-stream"))]
+            let input = TerminalInput::new(config.input_poll_rate());
+            Ok(Self::with_screen(component, Screen::Terminal(guard), config).with_input(input))
+        } else {
+            // Plain output has no terminal to read keys from.
+            let plain = PlainOutput::new(config.terminal);
+            Ok(Self::with_screen(component, Screen::Plain(plain), config))
+        }
+    }
+}
+
+impl<C, B> App<C, B>
+where
+    C: Component,
+    B: Backend,
+    B::Error: StdError + Send + Sync + 'static,
+{
+    /// Creates an app that draws on `terminal` instead of taking over the
+    /// real one, for tests, servers, and non-crossterm backends.
+    ///
+    /// Nothing is set up or restored around it, and there is no input
+    /// until [`App::with_input`] provides some: the component sees only
+    /// ticks and messages. `config.terminal` is ignored — the terminal's
+    /// own viewport is used as it is.
+    ///
+    /// ```ignore
+    /// let terminal = Terminal::new(TestBackend::new(40, 10))?;
+    /// let mut app = App::with_backend(Counter::default(), terminal, AppConfig::default())
+    ///     .with_input(keys);
+    /// app.run().await?;
+    /// ```
+    pub fn with_backend(component: C, terminal: Terminal<B>, config: AppConfig) -> Self {
+        Self::with_screen(component, Screen::Backend(terminal), config)
+    }
+
+    /// Reads events from `input` instead of the default, replacing the
+    /// terminal's keys for an app that took over the terminal.
+    pub fn with_input(mut self, input: impl InputSource + 'static) -> Self {
+        self.input = Some(Box::new(input));
+        self
+    }
+
+    /// The terminal passed to [`App::with_backend`], to inspect what was
+    /// drawn. `None` for an app that took over the real terminal (see
+    /// [`TerminalGuard::terminal`] for owning it directly).
+    pub fn backend_terminal(&mut self) -> Option<&mut Terminal<B>> {
+        match &mut self.screen {
+            Screen::Backend(terminal) => Some(terminal),
+            Screen::Terminal(_) | Screen::Plain(_) => None,
+        }
+    }
+
+    fn with_screen(component: C, screen: Screen<B>, config: AppConfig) -> Self {
         let (message_tx, message_rx) = mpsc::channel(config.channel_capacity());
+        let gestures = GestureRecognizer::new(config.gestures);
+        let toasts = Toasts::new(config.toasts);
+        let progress = ProgressBoard::new(config.progress);
 
-        Ok(Self {
-            terminal_guard,
+        Self {
+            screen,
+            input: None,
             component,
             config,
             context: Context::new(message_tx),
             message_rx,
+            selection: Selection::default(),
+            gestures,
+            toasts,
+            progress,
+            recorder: None,
+            screenshot_pending: false,
             should_quit: false,
-        })
+        }
     }
 
     /// Returns a sender that delivers messages to the component from outside
@@ -139,6 +324,37 @@ where
         self.context.sender()
     }
 
+    /// Restores the terminal and hands back the component, so its final
+    /// state can be read after the UI is gone — a picker's choice printed
+    /// to stdout, say, once the terminal is the shell's again.
+    pub fn into_component(self) -> C {
+        let Self {
+            screen, component, ..
+        } = self;
+        drop(screen);
+        component
+    }
+
+    /// Takes the value the component passed to [`Context::exit_with`] during
+    /// the last [`App::run`], or `None` if it quit another way. The terminal
+    /// is still the app's; see [`run_returning`] to get the value after it
+    /// is restored.
+    ///
+    /// Fails if the value is not a `T`.
+    pub fn take_exit_value<T: 'static>(&mut self) -> Result<Option<T>> {
+        let Some(exit) = self.context.take_exit() else {
+            return Ok(None);
+        };
+        match exit.value.downcast() {
+            Ok(value) => Ok(Some(*value)),
+            Err(_) => bail!(
+                "component exited with a {}, not the {} the caller expected",
+                exit.type_name,
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+
     /// Runs the app loop until the component quits, Ctrl-C is pressed (when
     /// enabled), [`Context::fail`] reports an error, or an input error
     /// occurs.
@@ -146,20 +362,27 @@ where
         self.should_quit = false;
         self.context.reset();
 
+        if self.recorder.is_none()
+            && let Some(path) = &self.config.record_session
+        {
+            self.recorder = Some(Recorder::create(path)?);
+        }
+
         let (event_tx, mut event_rx) = mpsc::channel(self.config.channel_capacity());
-        let shutdown = Arc::new(AtomicBool::new(false));
 
-        let input_handle = spawn_input_loop(
-            event_tx.clone(),
-            self.config.input_poll_rate(),
-            shutdown.clone(),
-        );
-        let tick_handle = tokio::spawn(tick_loop(event_tx, self.config.tick_rate()));
+        let input_handle = self
+            .input
+            .as_mut()
+            .map(|input| input.start(EventSender::new(event_tx.clone())));
+        let tick_handle = tokio::spawn(tick_loop(event_tx.clone(), self.config.tick_rate()));
 
-        let result = self.render_loop(&mut event_rx).await;
+        let result = self.render_loop(&event_tx, &mut event_rx).await;
 
-        shutdown.store(true, Ordering::Relaxed);
-        input_handle.abort();
+        // Blocking sources stop once they see the channel close, when
+        // `event_rx` drops at the end of this function.
+        if let Some(input_handle) = input_handle {
+            input_handle.abort();
+        }
         tick_handle.abort();
 
         result?;
@@ -172,7 +395,11 @@ where
         }
     }
 
-    async fn render_loop(&mut self, event_rx: &mut mpsc::Receiver<RuntimeEvent>) -> Result<()> {
+    async fn render_loop(
+        &mut self,
+        event_tx: &mpsc::Sender<RuntimeEvent>,
+        event_rx: &mut mpsc::Receiver<RuntimeEvent>,
+    ) -> Result<()> {
         let context = self.context.clone();
         let mut needs_render = true;
 
@@ -180,16 +407,27 @@ where
 
         loop {
             self.drain_queued_work(event_rx, &context, &mut needs_render)?;
+            // Before the quit check, so a component can copy its result and
+            // quit in the same handler.
+            self.handle_requests(event_tx, &context, &mut needs_render)?;
 
             if self.quit_pending(&context) {
+                // Print tasks that finished just before quitting, and leave
+                // the frame in the scrollback showing what is left.
+                if self.sync_viewport()? {
+                    self.draw()?;
+                }
                 break;
             }
 
             if needs_render {
+                self.sync_viewport()?;
                 self.draw()?;
                 needs_render = false;
             }
 
+            let long_press = self.gestures.long_press_deadline();
+
             tokio::select! {
                 event = event_rx.recv() => {
                     match event {
@@ -203,7 +441,12 @@ where
                         None => break,
                     }
                 }
-                () = context.quit_notified() => {}
+                () = context.notified() => {}
+                () = sleep_until(long_press) => {
+                    if let Some(gesture) = self.gestures.poll_long_press(Instant::now()) {
+                        self.handle_event(Event::Gesture(gesture), &context, &mut needs_render)?;
+                    }
+                }
             }
         }
 
@@ -269,10 +512,71 @@ where
         let ctrl_c = event.is_ctrl('c');
         #[cfg(unix)]
         let ctrl_z = event.is_ctrl('z');
+        let mouse = match &event {
+            Event::Mouse(mouse) => Some(*mouse),
+            _ => None,
+        };
+
+        if let Event::Tick(elapsed) = event {
+            *needs_render |= self.toasts.advance(elapsed);
+            *needs_render |= self.progress.advance(elapsed);
+        }
+
+        // The screenshot key is the app's, whatever the component binds.
+        if let Some(key) = self.config.screenshots.key
+            && event.is_key_with(key.code, key.modifiers)
+        {
+            self.screenshot_pending = true;
+            *needs_render = true;
+            return Ok(());
+        }
+
+        // The newest toast's action keys, and clicks on toasts, come before
+        // the component: the toast is drawn on top of it.
+        if self.toasts.handle_event(&event) {
+            *needs_render = true;
+            return Ok(());
+        }
+
+        // Only what reaches the component is recorded, so a replay feeds it
+        // exactly that. Gestures are left out: a replay derives them again.
+        if let Some(recorder) = &mut self.recorder
+            && !matches!(event, Event::Gesture(_))
+        {
+            recorder.event(&event)?;
+        }
+
+        if let Event::Resize(width, height) = event
+            && let Screen::Terminal(guard) = &mut self.screen
+        {
+            guard.handle_resize(width, height)?;
+        }
+
+        // Typing or resizing invalidates what a selection highlighted.
+        if self.config.text_selection && (resized || event.key().is_some()) {
+            *needs_render |= self.selection.clear();
+        }
 
         let result = self.component.handle_event(event, context);
         *needs_render |= resized || result.is_consumed();
 
+        // Like the keys below, the mouse only selects text when the
+        // component had no use for the event.
+        if let Some(mouse) = mouse
+            && self.config.text_selection
+            && !result.is_consumed()
+        {
+            *needs_render |= self.selection.handle_mouse(mouse);
+        }
+
+        // A gesture follows the raw event that completed it, so components
+        // can use either level without the two interfering.
+        if let Some(mouse) = mouse
+            && let Some(gesture) = self.gestures.handle_mouse(&mouse, Instant::now())
+        {
+            return self.handle_event(Event::Gesture(gesture), context, needs_render);
+        }
+
         // The component gets first refusal on Ctrl-C and Ctrl-Z: consuming
         // the event overrides the default (e.g. to confirm before quitting).
         if result.is_consumed() {
@@ -297,13 +601,120 @@ where
     /// is resumed (e.g. `fg`), then takes the terminal over again.
     #[cfg(unix)]
     fn suspend(&mut self) -> Result<()> {
-        self.terminal_guard.suspend();
+        let Screen::Terminal(guard) = &mut self.screen else {
+            return Ok(());
+        };
+        guard.suspend();
 
         // The whole process stops inside `raise` and continues from here
         // once the shell resumes it.
         signal_hook::low_level::raise(signal_hook::consts::SIGTSTP).context("raise SIGTSTP")?;
 
-        self.terminal_guard.resume()
+        guard.resume()
+    }
+
+    fn handle_requests(
+        &mut self,
+        event_tx: &mpsc::Sender<RuntimeEvent>,
+        context: &Context<C::Message>,
+        needs_render: &mut bool,
+    ) -> Result<()> {
+        for request in context.take_requests() {
+            match request {
+                Request::Copy(text) => self.copy_to_clipboard(text)?,
+                Request::Paste => paste_from_clipboard(event_tx.clone()),
+                Request::Notify(notification) => match &mut self.screen {
+                    Screen::Terminal(_) | Screen::Backend(_) => {
+                        self.toasts.push(notification);
+                        *needs_render = true;
+                    }
+                    // Without a terminal a toast has nowhere to float; log it.
+                    Screen::Plain(plain) => plain.print(&[Line::from(format!(
+                        "{} {}",
+                        notification.level.icon(),
+                        notification.text
+                    ))])?,
+                },
+                Request::Progress(task) => {
+                    self.progress.push(task);
+                    *needs_render = true;
+                }
+                Request::InlineHeight(height) => {
+                    // Applied with the progress board's rows before the next
+                    // draw.
+                    if let Viewport::Inline(_) = self.config.terminal.viewport {
+                        self.config.terminal.viewport = Viewport::Inline(height);
+                        *needs_render = true;
+                    }
+                }
+                Request::InsertBefore(text) => {
+                    let lines = text
+                        .lines
+                        .into_iter()
+                        .map(|line| line.patch_style(text.style))
+                        .collect();
+                    self.insert_before(lines)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints finished tasks above an inline viewport, then fits the
+    /// viewport to the component's height plus the rows the board still
+    /// needs. Returns whether any tasks finished.
+    fn sync_viewport(&mut self) -> Result<bool> {
+        let finished = self.progress.take_finished();
+        let changed = !finished.is_empty();
+        self.insert_before(finished)?;
+
+        if let Viewport::Inline(height) = self.config.terminal.viewport {
+            let height = height.saturating_add(self.progress.height());
+            match &mut self.screen {
+                Screen::Terminal(guard) => guard.set_inline_height(height)?,
+                Screen::Plain(plain) => plain.set_height(height),
+                // A terminal built elsewhere keeps the viewport it was
+                // built with.
+                Screen::Backend(_) => {}
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Prints `lines` above an inline viewport, wrapped to its width.
+    /// Ratatui's `insert_before` does nothing for other viewports. Plain
+    /// output writes them as they are.
+    fn insert_before(&mut self, lines: Vec<Line<'static>>) -> Result<()> {
+        match &mut self.screen {
+            Screen::Terminal(guard) => insert_before(guard.terminal(), &lines),
+            Screen::Backend(terminal) => insert_before(terminal, &lines),
+            Screen::Plain(plain) => plain.print(&lines),
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, text: String) -> Result<()> {
+        let mode = self.config.clipboard;
+
+        if mode.uses_osc52()
+            && let Screen::Terminal(guard) = &mut self.screen
+        {
+            execute!(
+                guard.terminal().backend_mut(),
+                CopyToClipboard::to_clipboard_from(&text)
+            )
+            .context("write OSC 52 clipboard sequence")?;
+        }
+
+        // Clipboard commands can be slow to start; keep them off the UI.
+        if mode.uses_command(clipboard::over_ssh()) {
+            tokio::task::spawn_blocking(move || {
+                let _ = clipboard::system_copy(&text);
+            });
+        }
+
+        Ok(())
     }
 
     fn handle_message(
@@ -312,54 +723,130 @@ where
         context: &Context<C::Message>,
         needs_render: &mut bool,
     ) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.message();
+        }
         self.component.update(message, context);
         *needs_render = true;
     }
 
     fn draw(&mut self) -> Result<()> {
         let Self {
-            terminal_guard,
+            screen,
             component,
+            selection,
+            toasts,
+            progress,
+            recorder,
+            screenshot_pending,
+            config,
+            context,
             ..
         } = self;
+        let mut selected_text = None;
 
-        terminal_guard
-            .terminal()
-            .draw(|frame| component.render(frame, frame.area()))
-            .context("draw terminal frame")?;
+        let render = |frame: &mut Frame| {
+            let [main, tasks] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(progress.height())])
+                    .areas(frame.area());
+            component.render(frame, main);
+            progress.render(frame, tasks);
+            toasts.render(frame.area(), frame.buffer_mut());
+            selected_text = selection.render(frame.buffer_mut());
+        };
+
+        let buffer = match screen {
+            Screen::Terminal(guard) => {
+                guard
+                    .terminal()
+                    .draw(render)
+                    .context("draw terminal frame")?
+                    .buffer
+            }
+            Screen::Backend(terminal) => {
+                terminal.draw(render).context("draw terminal frame")?.buffer
+            }
+            Screen::Plain(plain) => {
+                let frame_text = plain.draw(render)?;
+                plain.write(component.plain_text().unwrap_or(frame_text))?;
+                plain.buffer()
+            }
+        };
+        if let Some(recorder) = recorder {
+            recorder.frame(buffer)?;
+        }
+        if std::mem::take(screenshot_pending) {
+            save_screenshot(buffer, &config.screenshots, context);
+        }
+
+        // A finished selection copies from the frame it was made on.
+        if let Some(text) = selected_text {
+            self.copy_to_clipboard(text)?;
+        }
 
         Ok(())
     }
 }
 
-fn spawn_input_loop(
-    event_tx: mpsc::Sender<RuntimeEvent>,
-    input_poll_rate: Duration,
-    shutdown: Arc<AtomicBool>,
-) -> JoinHandle<()> {
-    tokio::task::spawn_blocking(move || {
-        while !shutdown.load(Ordering::Relaxed) {
-            match event::poll(input_poll_rate) {
-                Ok(true) => match event::read() {
-                    Ok(event) if event.is_key_release() => {}
-                    Ok(event) => {
-                        if event_tx.blocking_send(Ok(Event::from(event))).is_err() {
-                            break;
-                        }
-                    }
-                    Err(error) => {
-                        let _ = event_tx.blocking_send(Err(error).context("read terminal event"));
-                        break;
-                    }
-                },
-                Ok(false) => {}
-                Err(error) => {
-                    let _ = event_tx.blocking_send(Err(error).context("poll terminal events"));
-                    break;
-                }
+/// Where frames go: the real terminal, a terminal the app was given, or
+/// plain text when there is no terminal at all.
+enum Screen<B: Backend> {
+    Terminal(TerminalGuard),
+    Backend(Terminal<B>),
+    Plain(PlainOutput),
+}
+
+/// Prints `lines` above an inline viewport, wrapped to its width.
+fn insert_before<B>(terminal: &mut Terminal<B>, lines: &[Line<'_>]) -> Result<()>
+where
+    B: Backend,
+    B::Error: StdError + Send + Sync + 'static,
+{
+    let rows = wrap_lines(lines, terminal.get_frame().area().width);
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let height = u16::try_from(rows.len()).unwrap_or(u16::MAX);
+    terminal
+        .insert_before(height, |buf| {
+            for (row, line) in rows.iter().enumerate() {
+                let area = Rect {
+                    y: buf.area.y + row as u16,
+                    height: 1,
+                    ..buf.area
+                };
+                line.render(area, buf);
             }
+        })
+        .context("print above the viewport")
+}
+
+/// Reads the clipboard off the UI thread and delivers it like a terminal
+/// paste.
+fn paste_from_clipboard(event_tx: mpsc::Sender<RuntimeEvent>) {
+    tokio::task::spawn_blocking(move || {
+        if let Ok(text) = clipboard::system_paste() {
+            let _ = event_tx.blocking_send(Ok(Event::Paste(text)));
         }
-    })
+    });
+}
+
+/// Saves `buffer` where `config` says and reports how it went in a toast:
+/// a failed screenshot shouldn't end the app.
+fn save_screenshot<M>(buffer: &Buffer, config: &ScreenshotConfig, context: &Context<M>) {
+    let path = config.next_path();
+    let saved = std::fs::write(&path, screenshot::screenshot(buffer, config.format));
+    match saved {
+        Ok(()) => context.notify(
+            ToastLevel::Success,
+            format!("Saved screenshot to {}", path.display()),
+        ),
+        Err(error) => context.notify(
+            ToastLevel::Error,
+            format!("Couldn't save screenshot to {}: {error}", path.display()),
+        ),
+    }
 }
 
 async fn tick_loop(event_tx: mpsc::Sender<RuntimeEvent>, tick_rate: Duration) {
@@ -385,14 +872,201 @@ async fn tick_loop(event_tx: mpsc::Sender<RuntimeEvent>, tick_rate: Duration) {
     }
 }
 
+/// Breaks `lines` into rows at most `width` cells wide, keeping each
+/// character's style. Empty lines stay as empty rows.
+fn wrap_lines(lines: &[Line<'_>], width: u16) -> Vec<Line<'static>> {
+    let width = usize::from(width);
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let mut rows = Vec::new();
+    for line in lines {
+        let mut row = Line::default();
+        let mut row_width = 0;
+        for grapheme in line.styled_graphemes(Style::default()) {
+            let span = Span::styled(grapheme.symbol.to_string(), grapheme.style);
+            let grapheme_width = span.width();
+            if row_width + grapheme_width > width && row_width > 0 {
+                rows.push(std::mem::take(&mut row));
+                row_width = 0;
+            }
+            row_width += grapheme_width;
+            match row.spans.last_mut() {
+                Some(last) if last.style == span.style => {
+                    last.content.to_mut().push_str(&span.content)
+                }
+                _ => row.spans.push(span),
+            }
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// Sleeps until `deadline`, or forever when there is none.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
 fn non_zero_duration(value: Duration, fallback: Duration) -> Duration {
     if value.is_zero() { fallback } else { value }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{AppConfig, non_zero_duration};
+    use super::{App, AppConfig, non_zero_duration, wrap_lines};
+    use crate::tui::component::{Component, Context};
+    use crate::tui::event::{Event, EventResult};
+    use crate::tui::input::{EventSender, InputSource, ScriptedInput};
+    use crate::tui::session::{self, Record};
+    use crate::tui::toast::{Toast, ToastLevel};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+    use ratatui::text::{Line, Span};
+    use ratatui::widgets::Paragraph;
+    use ratatui::{Frame, Terminal};
     use std::time::Duration;
+    use tokio::task::JoinHandle;
+
+    /// Presses each key in turn.
+    struct Keys(Vec<KeyCode>);
+
+    impl InputSource for Keys {
+        fn start(&mut self, events: EventSender) -> JoinHandle<()> {
+            let keys = self.0.clone();
+            tokio::spawn(async move {
+                for key in keys {
+                    if !events.send(Event::key_press(key)).await {
+                        break;
+                    }
+                }
+            })
+        }
+    }
+
+    /// Collects typed characters and exits with them on Enter.
+    #[derive(Default)]
+    struct Prompt(String);
+
+    impl Component for Prompt {
+        type Message = ();
+
+        fn render(&mut self, frame: &mut Frame, area: Rect) {
+            frame.render_widget(Paragraph::new(format!("> {}", self.0)), area);
+        }
+
+        fn handle_event(&mut self, event: Event, context: &Context<()>) -> EventResult {
+            match event.key().map(|key| key.code) {
+                Some(KeyCode::Char(c)) => self.0.push(c),
+                Some(KeyCode::Enter) => context.exit_with(self.0.clone()),
+                _ => return EventResult::Propagate,
+            }
+            EventResult::Consumed
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_on_a_test_backend_with_input_from_any_source() {
+        let terminal = Terminal::new(TestBackend::new(12, 1)).unwrap();
+        let keys = Keys(vec![KeyCode::Char('h'), KeyCode::Char('i'), KeyCode::Enter]);
+        let mut app =
+            App::with_backend(Prompt::default(), terminal, AppConfig::default()).with_input(keys);
+
+        app.run().await.unwrap();
+
+        assert_eq!(app.take_exit_value::<String>().unwrap(), Some("hi".into()));
+        assert!(
+            app.take_exit_value::<String>().unwrap().is_none(),
+            "the value is taken once"
+        );
+        // Quitting doesn't redraw, so how much of the input the last frame
+        // shows depends on timing; that it was drawn here does not.
+        let terminal = app.backend_terminal().unwrap();
+        assert_eq!(terminal.backend().buffer()[(0, 0)].symbol(), ">");
+    }
+
+    /// Shows a toast with a `'c'` action, and remembers if it ran.
+    #[derive(Default)]
+    struct CopyToast(bool);
+
+    impl Component for CopyToast {
+        type Message = ();
+
+        fn init(&mut self, context: &Context<()>) {
+            context.toast(Toast::new(ToastLevel::Info, "Copied").action(
+                KeyCode::Char('c'),
+                "Copy again",
+                (),
+            ));
+        }
+
+        fn render(&mut self, _frame: &mut Frame, _area: Rect) {}
+
+        fn update(&mut self, (): (), _context: &Context<()>) {
+            self.0 = true;
+        }
+    }
+
+    #[tokio::test]
+    async fn ctrl_c_quits_even_while_a_toast_has_a_c_action() {
+        let terminal = Terminal::new(TestBackend::new(40, 5)).unwrap();
+        let ctrl_c = Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        let input = ScriptedInput::new([])
+            .pause(Duration::from_millis(20))
+            .event(ctrl_c);
+        let mut app = App::with_backend(CopyToast::default(), terminal, AppConfig::default())
+            .with_input(input);
+
+        tokio::time::timeout(Duration::from_secs(2), app.run())
+            .await
+            .expect("Ctrl-C quits")
+            .unwrap();
+
+        assert!(!app.into_component().0, "the toast action didn't run");
+    }
+
+    #[tokio::test]
+    async fn sessions_record_only_what_reached_the_component() {
+        let path = std::env::temp_dir().join(format!("app-session-{}.txt", std::process::id()));
+        let config = AppConfig {
+            record_session: Some(path.clone()),
+            ..AppConfig::default()
+        };
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let input = ScriptedInput::new([])
+            .pause(Duration::from_millis(20))
+            .key(KeyCode::Char('c'))
+            .key(KeyCode::Char('x'))
+            .event(Event::Key(ctrl_c));
+        let terminal = Terminal::new(TestBackend::new(40, 5)).unwrap();
+        let mut app = App::with_backend(CopyToast::default(), terminal, config).with_input(input);
+
+        tokio::time::timeout(Duration::from_secs(2), app.run())
+            .await
+            .expect("Ctrl-C quits")
+            .unwrap();
+        // Dropping the app flushes the recording.
+        let copied = app.into_component().0;
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let keys: Vec<KeyEvent> = session::parse(&text)
+            .unwrap()
+            .into_iter()
+            .filter_map(|(_, record)| match record {
+                Record::Event(Event::Key(key)) => Some(key),
+                _ => None,
+            })
+            .collect();
+        assert!(copied, "the toast took the 'c'");
+        assert_eq!(keys, [KeyEvent::from(KeyCode::Char('x')), ctrl_c]);
+    }
 
     #[test]
     fn app_config_never_uses_a_zero_sized_channel() {
@@ -411,4 +1085,28 @@ mod tests {
             Duration::from_millis(50)
         );
     }
+
+    #[test]
+    fn printed_lines_wrap_to_the_viewport_and_keep_their_styles() {
+        let red = Style::default().fg(Color::Red);
+        let lines = [
+            Line::from(vec![Span::raw("test a ... "), Span::styled("FAILED", red)]),
+            Line::default(),
+            Line::from("ok"),
+        ];
+
+        let rows = wrap_lines(&lines, 8);
+
+        assert_eq!(
+            rows,
+            [
+                Line::from("test a .".to_string()),
+                Line::from(vec![Span::raw(".. "), Span::styled("FAILE", red)]),
+                Line::from(vec![Span::styled("D", red)]),
+                Line::default(),
+                Line::from("ok".to_string()),
+            ]
+        );
+        assert!(wrap_lines(&lines, 0).is_empty());
+    }
 }
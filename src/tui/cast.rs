@@ -0,0 +1,187 @@
+//! Asciicast v2 recordings of what the app writes to the terminal.
+//!
+//! The terminal writer tees its bytes here, and each flush — one per drawn
+//! frame — becomes an output event, so `asciinema play` or the web player
+//! replays the app exactly as it looked, at its real pace. See
+//! <https://docs.asciinema.org/manual/asciicast/v2/> for the format.
+
+use anyhow::{Context as AnyhowContext, Result};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub(crate) struct CastRecorder {
+    out: Box<dyn Write + Send>,
+    started: Instant,
+    /// Bytes written since the last flush, including any partial UTF-8
+    /// character the next write will finish.
+    pending: Vec<u8>,
+}
+
+impl CastRecorder {
+    /// Starts a recording of a `width`×`height` terminal at `path`.
+    pub(crate) fn create(path: &Path, width: u16, height: u16) -> Result<Self> {
+        let file =
+            File::create(path).with_context(|| format!("create cast file {}", path.display()))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Self::with_writer(Box::new(BufWriter::new(file)), width, height, timestamp)
+            .with_context(|| format!("write cast file {}", path.display()))
+    }
+
+    fn with_writer(
+        mut out: Box<dyn Write + Send>,
+        width: u16,
+        height: u16,
+        timestamp: u64,
+    ) -> io::Result<Self> {
+        let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+        writeln!(
+            out,
+            "{{\"version\": 2, \"width\": {width}, \"height\": {height}, \
+             \"timestamp\": {timestamp}, \"env\": {{\"TERM\": {}}}}}",
+            json_string(&term)
+        )?;
+        Ok(Self {
+            out,
+            started: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Holds bytes the terminal was sent until the next flush.
+    pub(crate) fn output(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+    }
+
+    /// Writes what was sent since the last flush as one output event.
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        let text = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.to_string(),
+            // A character cut off at the end waits for the rest of it.
+            Err(error) if error.error_len().is_none() => {
+                let valid = error.valid_up_to();
+                let text = String::from_utf8_lossy(&self.pending[..valid]).into_owned();
+                self.pending.drain(..valid);
+                return self.event("o", &text);
+            }
+            Err(_) => String::from_utf8_lossy(&self.pending).into_owned(),
+        };
+        self.pending.clear();
+        self.event("o", &text)
+    }
+
+    /// Records that the terminal is now `width`×`height`.
+    pub(crate) fn resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        self.flush()?;
+        self.event("r", &format!("{width}x{height}"))
+    }
+
+    fn event(&mut self, kind: &str, data: &str) -> io::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let seconds = self.started.elapsed().as_secs_f64();
+        writeln!(
+            self.out,
+            "[{seconds:.6}, \"{kind}\", {}]",
+            json_string(data)
+        )?;
+        // Flushed per frame, so a crash keeps everything up to the last one.
+        self.out.flush()
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' || c == '\u{7f}' => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CastRecorder;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// The recording's lines, with event times blanked out.
+    fn lines(out: &Shared) -> Vec<String> {
+        let text = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+        text.lines()
+            .map(|line| match line.strip_prefix('[') {
+                Some(event) => format!("[t{}", &event[event.find(',').unwrap()..]),
+                None => line.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn each_flush_is_one_escaped_output_event() {
+        let out = Shared::default();
+        let mut cast = CastRecorder::with_writer(Box::new(out.clone()), 80, 24, 1700).unwrap();
+
+        cast.output(b"\x1b[1;1H\"hi\"\\");
+        cast.output("\r\n".as_bytes());
+        cast.flush().unwrap();
+        // Nothing new, so no event.
+        cast.flush().unwrap();
+
+        let lines = lines(&out);
+        assert!(lines[0].starts_with(
+            "{\"version\": 2, \"width\": 80, \"height\": 24, \"timestamp\": 1700, \"env\": {\"TERM\": "
+        ));
+        assert_eq!(
+            lines[1..],
+            ["[t, \"o\", \"\\u001b[1;1H\\\"hi\\\"\\\\\\r\\n\"]"]
+        );
+    }
+
+    #[test]
+    fn split_characters_wait_for_the_next_flush_and_resizes_are_recorded() {
+        let out = Shared::default();
+        let mut cast = CastRecorder::with_writer(Box::new(out.clone()), 80, 24, 0).unwrap();
+        let bytes = "a日".as_bytes();
+
+        cast.output(&bytes[..2]);
+        cast.flush().unwrap();
+        cast.output(&bytes[2..]);
+        cast.resize(100, 30).unwrap();
+
+        assert_eq!(
+            lines(&out)[1..],
+            [
+                "[t, \"o\", \"a\"]",
+                "[t, \"o\", \"日\"]",
+                "[t, \"r\", \"100x30\"]"
+            ]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Clipboard access behind [`Context::copy_to_clipboard`] and
+//! [`Context::paste_from_clipboard`].
+//!
+//! Copying writes an OSC 52 escape sequence through the terminal, which
+//! reaches the clipboard of the machine the terminal runs on — even over SSH.
+//! Terminals that ignore OSC 52 are covered by the platform's clipboard
+//! command (`pbcopy`, `wl-copy`, `xclip`, `clip`, ...). Pasting always goes
+//! through the clipboard command: OSC 52 reads are disabled in most terminals.
+//!
+//! [`Context::copy_to_clipboard`]: crate::tui::Context::copy_to_clipboard
+//! [`Context::paste_from_clipboard`]: crate::tui::Context::paste_from_clipboard
+
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+/// How [`Context::copy_to_clipboard`](crate::tui::Context::copy_to_clipboard)
+/// reaches the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClipboardMode {
+    /// OSC 52 through the terminal, plus the local clipboard command unless
+    /// the app runs over SSH (where the local clipboard is the server's).
+    #[default]
+    Auto,
+    /// OSC 52 only. Never spawns a process.
+    Osc52,
+    /// The local clipboard command only, for terminals that mishandle OSC 52.
+    System,
+}
+
+impl ClipboardMode {
+    pub(crate) fn uses_osc52(self) -> bool {
+        matches!(self, Self::Auto | Self::Osc52)
+    }
+
+    pub(crate) fn uses_command(self, over_ssh: bool) -> bool {
+        match self {
+            Self::Auto => !over_ssh,
+            Self::Osc52 => false,
+            Self::System => true,
+        }
+    }
+}
+
+/// Returns `true` when the process runs inside an SSH session.
+pub(crate) fn over_ssh() -> bool {
+    ["SSH_TTY", "SSH_CONNECTION", "SSH_CLIENT"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some())
+}
+
+/// Copies `text` with the first clipboard command found on this system.
+pub(crate) fn system_copy(text: &str) -> io::Result<()> {
+    first_available(copy_commands(), |command| {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        // Dropping stdin closes the pipe, which tells the command we're done.
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+
+        check_status(child.wait()?)
+    })
+}
+
+/// Reads the clipboard with the first clipboard command found on this system.
+pub(crate) fn system_paste() -> io::Result<String> {
+    first_available(paste_commands(), |command| {
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let mut text = String::new();
+        if let Some(mut stdout) = child.stdout.take() {
+            stdout.read_to_string(&mut text)?;
+        }
+
+        check_status(child.wait()?)?;
+
+        // Windows' `Get-Clipboard` terminates its output with a line break.
+        if cfg!(windows) && text.ends_with("\r\n") {
+            text.truncate(text.len() - 2);
+        }
+        Ok(text)
+    })
+}
+
+/// Runs `attempt` with each command in turn, skipping commands that are not
+/// installed. Any other failure is returned as-is.
+fn first_available<T>(
+    commands: Vec<(&'static str, &'static [&'static str])>,
+    mut attempt: impl FnMut(&mut Command) -> io::Result<T>,
+) -> io::Result<T> {
+    for (program, args) in commands {
+        match attempt(Command::new(program).args(args)) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+            result => return result,
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no clipboard command found",
+    ))
+}
+
+fn check_status(status: std::process::ExitStatus) -> io::Result<()> {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "clipboard command failed: {status}"
+        )))
+    }
+}
+
+fn copy_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(windows) {
+        vec![("clip", &[])]
+    } else {
+        let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-copy", &[]));
+        }
+        commands.push(("xclip", &["-selection", "clipboard"]));
+        commands.push(("xsel", &["--clipboard", "--input"]));
+        commands
+    }
+}
+
+fn paste_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbpaste", &[])]
+    } else if cfg!(windows) {
+        vec![("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])]
+    } else {
+        let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-paste", &["--no-newline"]));
+        }
+        commands.push(("xclip", &["-selection", "clipboard", "-out"]));
+        commands.push(("xsel", &["--clipboard", "--output"]));
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClipboardMode;
+
+    #[test]
+    fn auto_mode_skips_the_local_command_over_ssh() {
+        assert!(ClipboardMode::Auto.uses_osc52());
+        assert!(ClipboardMode::Auto.uses_command(false));
+        assert!(!ClipboardMode::Auto.uses_command(true));
+    }
+
+    #[test]
+    fn explicit_modes_use_one_mechanism() {
+        assert!(ClipboardMode::Osc52.uses_osc52());
+        assert!(!ClipboardMode::Osc52.uses_command(false));
+        assert!(!ClipboardMode::System.uses_osc52());
+        assert!(ClipboardMode::System.uses_command(true));
+    }
+}
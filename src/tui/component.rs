@@ -2,7 +2,10 @@
 //! back to the app loop.
 
 use crate::tui::event::{Event, EventResult};
-use ratatui::{Frame, layout::Rect};
+use crate::tui::progress::ProgressTask;
+use crate::tui::toast::{Action, Notification, Toast, ToastLevel};
+use ratatui::{Frame, layout::Rect, text::Text};
+use std::any::Any;
 use std::sync::{
     Arc, Mutex,
     atomic::{AtomicBool, Ordering},
@@ -13,15 +16,42 @@ use tokio::sync::{Notify, mpsc};
 ///
 /// A `Context` is cheap to clone and safe to move into background tasks. Use
 /// [`Context::sender`] to report results back to the UI from async work,
-/// [`Context::quit`] to stop the app, and [`Context::fail`] to stop it with
-/// an error.
+/// [`Context::quit`] to stop the app, [`Context::exit_with`] to stop it with
+/// a result, and [`Context::fail`] to stop it with an error.
 ///
 /// `M` is the component's [`Component::Message`] type.
 pub struct Context<M> {
     sender: mpsc::Sender<M>,
     quit_requested: Arc<AtomicBool>,
-    quit_notify: Arc<Notify>,
+    wake: Arc<Notify>,
     error: Arc<Mutex<Option<anyhow::Error>>>,
+    exit_value: Arc<Mutex<Option<ExitValue>>>,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+/// A value passed to [`Context::exit_with`], with its type's name for the
+/// error when the caller asks for a different type.
+pub(crate) struct ExitValue {
+    pub(crate) value: Box<dyn Any + Send>,
+    pub(crate) type_name: &'static str,
+}
+
+/// Work a component asks the app loop to do with the terminal, queued on the
+/// [`Context`] and carried out between events.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Request {
+    /// Put text on the clipboard.
+    Copy(String),
+    /// Read the clipboard and deliver it as [`Event::Paste`].
+    Paste,
+    /// Show a toast.
+    Notify(Notification),
+    /// Put a task on the progress board.
+    Progress(ProgressTask),
+    /// Print lines above an inline viewport.
+    InsertBefore(Text<'static>),
+    /// Change the height of an inline viewport.
+    InlineHeight(u16),
 }
 
 // Manual impl: `Context<M>` is clonable regardless of whether `M` is.
@@ -30,8 +60,10 @@ impl<M> Clone for Context<M> {
         Self {
             sender: self.sender.clone(),
             quit_requested: Arc::clone(&self.quit_requested),
-            quit_notify: Arc::clone(&self.quit_notify),
+            wake: Arc::clone(&self.wake),
             error: Arc::clone(&self.error),
+            exit_value: Arc::clone(&self.exit_value),
+            requests: Arc::clone(&self.requests),
         }
     }
 }
@@ -41,8 +73,10 @@ impl<M> Context<M> {
         Self {
             sender,
             quit_requested: Arc::new(AtomicBool::new(false)),
-            quit_notify: Arc::new(Notify::new()),
+            wake: Arc::new(Notify::new()),
             error: Arc::new(Mutex::new(None)),
+            exit_value: Arc::new(Mutex::new(None)),
+            requests: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -63,13 +97,28 @@ impl<M> Context<M> {
         self.sender.try_send(message)
     }
 
+    /// Sends a message without waiting, falling back to a background send
+    /// when the channel is full rather than dropping it. Needs a Tokio
+    /// runtime in that case.
+    pub(crate) fn deliver(&self, message: M)
+    where
+        M: Send + 'static,
+    {
+        if let Err(mpsc::error::TrySendError::Full(message)) = self.try_send(message) {
+            let sender = self.sender();
+            tokio::spawn(async move {
+                let _ = sender.send(message).await;
+            });
+        }
+    }
+
     /// Asks the app loop to exit.
     ///
     /// Safe to call from event handlers, `update`, or background tasks. The
     /// request latches: it cannot be lost, even under load.
     pub fn quit(&self) {
         self.quit_requested.store(true, Ordering::Relaxed);
-        self.quit_notify.notify_one();
+        self.wake.notify_one();
     }
 
     /// Returns `true` once [`Context::quit`] has been called.
@@ -80,6 +129,51 @@ impl<M> Context<M> {
         self.quit_requested.load(Ordering::Relaxed)
     }
 
+    /// Quits with a result: the terminal is restored and `value` is returned
+    /// from [`run_returning`](crate::tui::run_returning) (or
+    /// [`App::take_exit_value`](crate::tui::App::take_exit_value)) — a
+    /// picker's choice, a prompt's answer.
+    ///
+    /// Safe to call from event handlers, `update`, or background tasks. Like
+    /// [`Context::fail`], the first call wins.
+    pub fn exit_with<T: Send + 'static>(&self, value: T) {
+        let mut slot = self
+            .exit_value
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        slot.get_or_insert_with(|| ExitValue {
+            value: Box::new(value),
+            type_name: std::any::type_name::<T>(),
+        });
+        drop(slot);
+
+        self.quit();
+    }
+
+    /// Takes the value passed to [`Context::exit_with`], if there is one and
+    /// it is a `T`. A value of another type stays put.
+    ///
+    /// The app loop reads it when the run ends; in unit tests, use it to
+    /// assert what a handler exited with.
+    pub fn take_exit_value<T: 'static>(&self) -> Option<T> {
+        let mut slot = self
+            .exit_value
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !slot.as_ref()?.value.is::<T>() {
+            return None;
+        }
+        let exit = slot.take()?;
+        exit.value.downcast().ok().map(|value| *value)
+    }
+
+    pub(crate) fn take_exit(&self) -> Option<ExitValue> {
+        self.exit_value
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
     /// Reports a fatal error and quits: the terminal is restored and the
     /// error is returned from [`run`](crate::tui::run) / `App::run`.
     ///
@@ -111,6 +205,129 @@ impl<M> Context<M> {
             .take()
     }
 
+    /// Copies `text` to the system clipboard.
+    ///
+    /// Writes an OSC 52 sequence through the terminal, which works over SSH
+    /// and with mouse capture on, and falls back to the platform's clipboard
+    /// command (see [`ClipboardMode`]). Copying is best effort: terminals
+    /// that ignore OSC 52 on a machine without a clipboard command drop the
+    /// text silently.
+    ///
+    /// Safe to call from event handlers, `update`, or background tasks.
+    ///
+    /// [`ClipboardMode`]: crate::tui::ClipboardMode
+    pub fn copy_to_clipboard(&self, text: impl Into<String>) {
+        self.request(Request::Copy(text.into()));
+    }
+
+    /// Reads the system clipboard and delivers its contents to
+    /// [`Component::handle_event`] as [`Event::Paste`], exactly like a
+    /// bracketed paste from the terminal.
+    ///
+    /// Uses the platform's clipboard command, so it reads the clipboard of
+    /// the machine the app runs on. Nothing is delivered if the clipboard
+    /// can't be read.
+    pub fn paste_from_clipboard(&self) {
+        self.request(Request::Paste);
+    }
+
+    /// Shows a toast — "Saved", "Connection lost" — on top of the UI for a
+    /// few seconds, without the component reserving space for it.
+    ///
+    /// Toasts stack in the corner set by
+    /// [`AppConfig::toasts`](crate::tui::AppConfig::toasts) and disappear on
+    /// their own; clicking one dismisses it. Safe to call from event
+    /// handlers, `update`, or background tasks.
+    pub fn notify(&self, level: ToastLevel, text: impl Into<String>) {
+        self.request(Request::Notify(Notification {
+            level,
+            text: text.into(),
+            duration: None,
+            actions: Vec::new(),
+        }));
+    }
+
+    /// Shows a [`Toast`] built with its own duration or action keys.
+    pub fn toast(&self, toast: Toast<M>)
+    where
+        M: Send + 'static,
+    {
+        let actions = toast
+            .actions
+            .into_iter()
+            .map(|(key, label, message)| {
+                let context = self.clone();
+                Action {
+                    key,
+                    label,
+                    run: Box::new(move || context.deliver(message)),
+                }
+            })
+            .collect();
+        self.request(Request::Notify(Notification {
+            level: toast.level,
+            text: toast.text,
+            duration: toast.duration,
+            actions,
+        }));
+    }
+
+    /// Prints `text` permanently above an inline viewport, where it scrolls
+    /// up with the terminal's history while the live UI stays below — like a
+    /// test runner printing results above its progress bar. Long lines wrap
+    /// and styles are kept.
+    ///
+    /// Only [`Viewport::Inline`](crate::tui::Viewport::Inline) has a
+    /// scrollback to print into; with other viewports the text is dropped.
+    /// Safe to call from event handlers, `update`, or background tasks.
+    ///
+    /// ```ignore
+    /// context.insert_before(format!("test {name} ... ok"));
+    /// context.insert_before(Line::styled("FAILED", Style::default().fg(Color::Red)));
+    /// ```
+    pub fn insert_before(&self, text: impl Into<Text<'static>>) {
+        self.request(Request::InsertBefore(text.into()));
+    }
+
+    /// Changes the height of an inline viewport, so a one-line status can
+    /// open into a details panel and close again. Growing scrolls the
+    /// terminal up to make room; shrinking clears the rows left behind. The
+    /// top of the viewport stays put either way.
+    ///
+    /// The new height replaces the one from
+    /// [`Viewport::Inline`](crate::tui::Viewport::Inline) for the rest of the
+    /// run, including after Ctrl-Z suspend. Rows the progress board adds come
+    /// on top. Does nothing for other viewports.
+    pub fn set_inline_height(&self, height: u16) {
+        self.request(Request::InlineHeight(height));
+    }
+
+    /// Puts a task on the progress board below the component and returns
+    /// the handle to report its progress through. `total` is in whatever
+    /// units you count — bytes, files, steps — or `0` if unknown, which shows
+    /// a bouncing bar instead of a filling one.
+    ///
+    /// The board shows throughput and an ETA for each task. In an inline
+    /// viewport it grows a row per task; finished tasks are printed above it
+    /// (see [`AppConfig::progress`](crate::tui::AppConfig::progress)) and
+    /// their rows removed. Safe to call from event handlers, `update`, or
+    /// background tasks.
+    ///
+    /// ```ignore
+    /// let task = context.add_task("serde 1.0.219", size).bytes();
+    /// tokio::spawn(async move {
+    ///     while let Some(chunk) = stream.next().await {
+    ///         task.inc(chunk.len() as u64);
+    ///     }
+    ///     task.finish();
+    /// });
+    /// ```
+    pub fn add_task(&self, label: impl Into<String>, total: u64) -> ProgressTask {
+        let task = ProgressTask::new(label.into(), total);
+        self.request(Request::Progress(task.clone()));
+        task
+    }
+
     /// Creates a context for unit-testing components without a terminal,
     /// plus the receiving end of its message channel.
     ///
@@ -130,15 +347,39 @@ impl<M> Context<M> {
         (Self::new(sender), receiver)
     }
 
-    /// Clears quit and error state so `App::run` can be called again.
+    /// Clears quit, error, exit value, and pending requests so `App::run`
+    /// can be called again.
     pub(crate) fn reset(&self) {
         self.quit_requested.store(false, Ordering::Relaxed);
         self.take_error();
+        self.take_exit();
+        self.take_requests();
+    }
+
+    /// Resolves once [`Context::quit`] has been called or a request has been
+    /// queued.
+    pub(crate) async fn notified(&self) {
+        self.wake.notified().await;
+    }
+
+    /// Queues work for the app loop and wakes it, so requests from
+    /// background tasks are handled without waiting for the next event.
+    fn request(&self, request: Request) {
+        self.requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(request);
+        self.wake.notify_one();
     }
 
-    /// Resolves once [`Context::quit`] has been called.
-    pub(crate) async fn quit_notified(&self) {
-        self.quit_notify.notified().await;
+    /// Removes and returns every queued request, oldest first.
+    pub(crate) fn take_requests(&self) -> Vec<Request> {
+        std::mem::take(
+            &mut *self
+                .requests
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
     }
 }
 
@@ -173,6 +414,16 @@ pub trait Component: Send {
     /// `examples/text_input.rs`.
     fn render(&mut self, frame: &mut Frame, area: Rect);
 
+    /// What to print for this frame when the app runs without a terminal
+    /// (see [`AppConfig::non_tty`](crate::tui::AppConfig::non_tty)), called
+    /// after each render. Return a line or two of status ("Downloaded 3/10
+    /// files") to keep CI logs readable; the default, `None`, prints the
+    /// rendered frame as text. Either way, output that reads the same as
+    /// the last write is skipped.
+    fn plain_text(&mut self) -> Option<String> {
+        None
+    }
+
     /// Reacts to a terminal event or tick.
     ///
     /// Return [`EventResult::Consumed`] when the event changed state and the
@@ -201,6 +452,10 @@ where
         (**self).render(frame, area);
     }
 
+    fn plain_text(&mut self) -> Option<String> {
+        (**self).plain_text()
+    }
+
     fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
         (**self).handle_event(event, context)
     }
@@ -212,7 +467,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::Context;
+    use super::{Context, Request};
     use tokio::sync::mpsc;
 
     #[test]
@@ -226,6 +481,19 @@ mod tests {
         assert!(context.quit_requested());
     }
 
+    #[test]
+    fn exit_with_stores_the_first_value_and_quits() {
+        let (context, _messages) = Context::<()>::test();
+
+        context.exit_with(String::from("beta"));
+        context.exit_with(String::from("gamma"));
+
+        assert!(context.quit_requested());
+        assert_eq!(context.take_exit_value::<u32>(), None, "wrong type");
+        assert_eq!(context.take_exit_value(), Some(String::from("beta")));
+        assert_eq!(context.take_exit_value::<String>(), None, "taken");
+    }
+
     #[tokio::test]
     async fn quit_wakes_a_waiting_loop() {
         let (sender, _receiver) = mpsc::channel::<()>(1);
@@ -234,10 +502,26 @@ mod tests {
         context.quit();
 
         // A stored notification must wake the next waiter immediately.
-        context.quit_notified().await;
+        context.notified().await;
         assert!(context.quit_requested());
     }
 
+    #[test]
+    fn viewport_requests_queue_in_order() {
+        let (context, _messages) = Context::<()>::test();
+
+        context.insert_before("done");
+        context.set_inline_height(8);
+
+        assert_eq!(
+            context.take_requests(),
+            [
+                Request::InsertBefore("done".into()),
+                Request::InlineHeight(8)
+            ]
+        );
+    }
+
     #[test]
     fn messages_are_delivered_typed() {
         let (sender, mut receiver) = mpsc::channel(4);
@@ -260,4 +544,19 @@ mod tests {
         assert_eq!(error.to_string(), "disk on fire");
         assert!(context.take_error().is_none(), "take_error consumes");
     }
+
+    #[tokio::test]
+    async fn clipboard_requests_queue_in_order_and_wake_the_loop() {
+        let (context, _messages) = Context::<()>::test();
+
+        context.copy_to_clipboard("hello");
+        context.paste_from_clipboard();
+
+        context.notified().await;
+        assert_eq!(
+            context.take_requests(),
+            [Request::Copy("hello".into()), Request::Paste]
+        );
+        assert!(context.take_requests().is_empty(), "take_requests drains");
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! [`Component::handle_event`]: crate::tui::Component::handle_event
 
+use crate::tui::gesture::Gesture;
 use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use std::time::Duration;
 
@@ -16,6 +17,10 @@ pub enum Event {
     Key(KeyEvent),
     /// A mouse event (requires `TerminalConfig::mouse_capture`).
     Mouse(MouseEvent),
+    /// A click, double/triple click, drag, or long press, recognized from
+    /// the raw mouse events. Delivered right after the [`Event::Mouse`] that
+    /// completed it (or on its own for a long press).
+    Gesture(Gesture),
     /// Pasted text, delivered whole (requires `TerminalConfig::bracketed_paste`).
     Paste(String),
     /// The terminal was resized to (width, height).
@@ -42,6 +47,21 @@ impl Event {
         }
     }
 
+    /// Returns the gesture if this is a recognized mouse gesture.
+    ///
+    /// ```ignore
+    /// if let Some(Gesture::Click { count: 2, position, .. }) = event.gesture() {
+    ///     self.open_at(*position);
+    ///     return EventResult::Consumed;
+    /// }
+    /// ```
+    pub const fn gesture(&self) -> Option<&Gesture> {
+        match self {
+            Self::Gesture(gesture) => Some(gesture),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if this event is a press of `code`, ignoring modifiers.
     ///
     /// Handy for one-key bindings:
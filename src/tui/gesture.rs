@@ -0,0 +1,311 @@
+//! Mouse gestures synthesized from Crossterm's raw button events.
+//!
+//! Terminals only report presses, releases, and motion. The app loop runs
+//! every mouse event through a recognizer and follows it with an
+//! [`Event::Gesture`](crate::tui::Event::Gesture) when it completes one:
+//! single, double, and triple clicks, drags with their start and end
+//! positions, and long presses.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+use std::time::{Duration, Instant};
+
+/// Timing and distance thresholds for gesture recognition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GestureConfig {
+    /// The longest gap between clicks that still counts toward a double or
+    /// triple click.
+    pub multi_click_interval: Duration,
+    /// How far (in cells, either axis) a click may land from the previous
+    /// one and still count toward a double or triple click.
+    pub click_distance: u16,
+    /// How long the left button must stay down without moving to fire
+    /// [`Gesture::LongPress`]. `None` disables long presses.
+    pub long_press: Option<Duration>,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            multi_click_interval: Duration::from_millis(500),
+            click_distance: 1,
+            long_press: Some(Duration::from_millis(600)),
+        }
+    }
+}
+
+/// A completed mouse gesture. Positions are screen coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    /// A press and release without dragging. `count` is 1 for a single
+    /// click, 2 for a double click, and 3 for a triple click; a fourth quick
+    /// click starts over at 1.
+    Click {
+        button: MouseButton,
+        position: Position,
+        count: u8,
+    },
+    /// The pointer moved to another cell with `button` held.
+    DragStart {
+        button: MouseButton,
+        start: Position,
+    },
+    /// `button` was released after a drag.
+    DragEnd {
+        button: MouseButton,
+        start: Position,
+        end: Position,
+    },
+    /// The left button was held in place for `GestureConfig::long_press`.
+    /// The release that follows is not a click.
+    LongPress { position: Position },
+}
+
+/// Turns raw mouse events into [`Gesture`]s.
+#[derive(Debug, Clone)]
+pub(crate) struct GestureRecognizer {
+    config: GestureConfig,
+    press: Option<Press>,
+    last_click: Option<LastClick>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    button: MouseButton,
+    start: Position,
+    at: Instant,
+    dragging: bool,
+    long_pressed: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LastClick {
+    button: MouseButton,
+    position: Position,
+    at: Instant,
+    count: u8,
+}
+
+impl GestureRecognizer {
+    pub(crate) fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            press: None,
+            last_click: None,
+        }
+    }
+
+    pub(crate) fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    pub(crate) fn config_mut(&mut self) -> &mut GestureConfig {
+        &mut self.config
+    }
+
+    /// Feeds one raw mouse event; returns the gesture it completes, if any.
+    pub(crate) fn handle_mouse(&mut self, mouse: &MouseEvent, now: Instant) -> Option<Gesture> {
+        let position = Position::new(mouse.column, mouse.row);
+
+        match mouse.kind {
+            MouseEventKind::Down(button) => {
+                self.press = Some(Press {
+                    button,
+                    start: position,
+                    at: now,
+                    dragging: false,
+                    long_pressed: false,
+                });
+                None
+            }
+            MouseEventKind::Drag(button) => {
+                let press = self.press.as_mut()?;
+                if press.button != button || press.dragging || press.start == position {
+                    return None;
+                }
+                press.dragging = true;
+                Some(Gesture::DragStart {
+                    button,
+                    start: press.start,
+                })
+            }
+            MouseEventKind::Up(button) => {
+                let press = self.press.take().filter(|press| press.button == button)?;
+                if press.dragging {
+                    return Some(Gesture::DragEnd {
+                        button,
+                        start: press.start,
+                        end: position,
+                    });
+                }
+                if press.long_pressed {
+                    return None;
+                }
+
+                let count = match self.last_click {
+                    Some(last)
+                        if last.button == button
+                            && last.count < 3
+                            && now.duration_since(last.at) <= self.config.multi_click_interval
+                            && distance(last.position, position) <= self.config.click_distance =>
+                    {
+                        last.count + 1
+                    }
+                    _ => 1,
+                };
+                self.last_click = Some(LastClick {
+                    button,
+                    position,
+                    at: now,
+                    count,
+                });
+                Some(Gesture::Click {
+                    button,
+                    position,
+                    count,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// When a held left button turns into a long press, if one is pending.
+    pub(crate) fn long_press_deadline(&self) -> Option<Instant> {
+        let press = self.press?;
+        let hold = self.config.long_press?;
+        (press.button == MouseButton::Left && !press.dragging && !press.long_pressed)
+            .then(|| press.at + hold)
+    }
+
+    /// Fires the pending long press once its deadline has passed.
+    pub(crate) fn poll_long_press(&mut self, now: Instant) -> Option<Gesture> {
+        let deadline = self.long_press_deadline()?;
+        let press = self.press.as_mut()?;
+        if now < deadline {
+            return None;
+        }
+        press.long_pressed = true;
+        self.last_click = None;
+        Some(Gesture::LongPress {
+            position: press.start,
+        })
+    }
+}
+
+/// Distance in cells along the farther axis.
+fn distance(a: Position, b: Position) -> u16 {
+    a.x.abs_diff(b.x).max(a.y.abs_diff(b.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gesture, GestureConfig, GestureRecognizer};
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::layout::Position;
+    use std::time::{Duration, Instant};
+
+    const LEFT: MouseButton = MouseButton::Left;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn click(recognizer: &mut GestureRecognizer, column: u16, at: Instant) -> Option<Gesture> {
+        recognizer.handle_mouse(&mouse(MouseEventKind::Down(LEFT), column, 0), at);
+        recognizer.handle_mouse(&mouse(MouseEventKind::Up(LEFT), column, 0), at)
+    }
+
+    fn count(gesture: Option<Gesture>) -> u8 {
+        match gesture {
+            Some(Gesture::Click { count, .. }) => count,
+            other => panic!("expected a click, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn quick_clicks_count_up_to_three_then_start_over() {
+        let mut recognizer = GestureRecognizer::new(GestureConfig::default());
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+
+        assert_eq!(count(click(&mut recognizer, 5, start)), 1);
+        assert_eq!(count(click(&mut recognizer, 5, start + ms(100))), 2);
+        assert_eq!(count(click(&mut recognizer, 6, start + ms(200))), 3);
+        assert_eq!(count(click(&mut recognizer, 6, start + ms(300))), 1);
+    }
+
+    #[test]
+    fn slow_or_distant_clicks_start_a_new_count() {
+        let mut recognizer = GestureRecognizer::new(GestureConfig::default());
+        let start = Instant::now();
+
+        assert_eq!(count(click(&mut recognizer, 5, start)), 1);
+        assert_eq!(
+            count(click(&mut recognizer, 5, start + Duration::from_secs(1))),
+            1
+        );
+        assert_eq!(
+            count(click(
+                &mut recognizer,
+                9,
+                start + Duration::from_millis(1100)
+            )),
+            1
+        );
+    }
+
+    #[test]
+    fn drags_report_start_and_end_instead_of_a_click() {
+        let mut recognizer = GestureRecognizer::new(GestureConfig::default());
+        let now = Instant::now();
+
+        recognizer.handle_mouse(&mouse(MouseEventKind::Down(LEFT), 1, 1), now);
+        assert_eq!(
+            recognizer.handle_mouse(&mouse(MouseEventKind::Drag(LEFT), 2, 1), now),
+            Some(Gesture::DragStart {
+                button: LEFT,
+                start: Position::new(1, 1)
+            })
+        );
+        assert_eq!(
+            recognizer.handle_mouse(&mouse(MouseEventKind::Drag(LEFT), 3, 2), now),
+            None
+        );
+        assert_eq!(
+            recognizer.handle_mouse(&mouse(MouseEventKind::Up(LEFT), 4, 2), now),
+            Some(Gesture::DragEnd {
+                button: LEFT,
+                start: Position::new(1, 1),
+                end: Position::new(4, 2)
+            })
+        );
+    }
+
+    #[test]
+    fn holding_still_fires_one_long_press_and_no_click() {
+        let mut recognizer = GestureRecognizer::new(GestureConfig::default());
+        let start = Instant::now();
+        let hold = GestureConfig::default().long_press.expect("enabled");
+
+        recognizer.handle_mouse(&mouse(MouseEventKind::Down(LEFT), 3, 0), start);
+        assert_eq!(recognizer.long_press_deadline(), Some(start + hold));
+        assert_eq!(recognizer.poll_long_press(start), None, "too early");
+
+        assert_eq!(
+            recognizer.poll_long_press(start + hold),
+            Some(Gesture::LongPress {
+                position: Position::new(3, 0)
+            })
+        );
+        assert_eq!(recognizer.long_press_deadline(), None, "fires once");
+        assert_eq!(
+            recognizer.handle_mouse(&mouse(MouseEventKind::Up(LEFT), 3, 0), start + hold),
+            None
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! Mouse hit-testing: map pointer events to the regions a component drew.
+//!
+//! Instead of keeping the `Rect` of every clickable thing from the last
+//! render and comparing coordinates by hand, register regions on a
+//! [`HitMap`] while rendering and let it turn raw mouse events into
+//! per-region events:
+//!
+//! ```ignore
+//! fn render(&mut self, frame: &mut Frame, area: Rect) {
+//!     self.hits.clear();
+//!     for (index, button_area) in self.button_areas(area).into_iter().enumerate() {
+//!         frame.render_widget(self.button(index), button_area);
+//!         self.hits.register(button_area, index);
+//!     }
+//! }
+//!
+//! fn handle_event(&mut self, event: Event, context: &Context<Msg>) -> EventResult {
+//!     for hit in self.hits.handle_event(&event) {
+//!         if let HitKind::Click(MouseButton::Left) = hit.kind {
+//!             self.press(hit.id);
+//!         }
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use crate::tui::event::Event;
+use crate::tui::gesture::{Gesture, GestureConfig, GestureRecognizer};
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use std::time::{Duration, Instant};
+
+/// The longest gap between two clicks that still counts as a double-click.
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What happened to a registered region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitKind {
+    /// The pointer moved onto the region.
+    Enter,
+    /// The pointer moved off the region (onto another one or onto nothing).
+    Leave,
+    /// A button was pressed and released on the region without dragging.
+    Click(MouseButton),
+    /// The second click of a quick pair, delivered instead of a second
+    /// [`HitKind::Click`].
+    DoubleClick(MouseButton),
+    /// The pointer moved with a button held after pressing on the region.
+    /// Fires once per drag; `position` is where the press happened.
+    DragStart(MouseButton),
+}
+
+/// A mouse event routed to the topmost region under the pointer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HitEvent<Id> {
+    /// The id the region was registered with.
+    pub id: Id,
+    /// What happened.
+    pub kind: HitKind,
+    /// The pointer position relative to the region's top-left corner.
+    pub position: Position,
+    /// Modifier keys held during the event.
+    pub modifiers: KeyModifiers,
+}
+
+/// Regions registered during `render`, and the pointer state needed to turn
+/// raw mouse events into hover, click, double-click, and drag events.
+///
+/// Register regions back to front: when regions overlap, the one registered
+/// last is on top and receives the event, like widgets drawn last.
+///
+/// Clicks and drags come from the same recognizer as [`Event::Gesture`];
+/// a double-click also needs both clicks on the same region.
+#[derive(Debug, Clone)]
+pub struct HitMap<Id> {
+    regions: Vec<(Rect, Id)>,
+    hovered: Option<Id>,
+    pressed: Option<(Id, Position)>,
+    /// The first click of a possible pair. The recognizer doesn't know
+    /// about regions, so pairing happens here: same region, same button.
+    last_click: Option<(Id, MouseButton, Instant)>,
+    gestures: GestureRecognizer,
+}
+
+impl<Id> Default for HitMap<Id> {
+    fn default() -> Self {
+        Self {
+            regions: Vec::new(),
+            hovered: None,
+            pressed: None,
+            last_click: None,
+            gestures: GestureRecognizer::new(GestureConfig {
+                multi_click_interval: DOUBLE_CLICK_INTERVAL,
+                ..GestureConfig::default()
+            }),
+        }
+    }
+}
+
+impl<Id: Clone + PartialEq> HitMap<Id> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the longest gap between clicks that counts as a double-click
+    /// (default [`DOUBLE_CLICK_INTERVAL`]).
+    pub fn with_double_click_interval(mut self, interval: Duration) -> Self {
+        self.gestures.config_mut().multi_click_interval = interval;
+        self
+    }
+
+    /// Forgets every region. Call at the start of `render`, then register
+    /// what this frame draws. Hover and press state survive, so a region
+    /// that is redrawn under the pointer doesn't flicker out of hover.
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Registers `area` under `id`, on top of everything registered so far.
+    pub fn register(&mut self, area: Rect, id: Id) {
+        self.regions.push((area, id));
+    }
+
+    /// Returns the id of the topmost region containing `position`.
+    pub fn hit(&self, position: Position) -> Option<&Id> {
+        self.hit_region(position).map(|(_, id)| id)
+    }
+
+    /// Returns the id of the region under the pointer, as of the last
+    /// mouse event.
+    pub fn hovered(&self) -> Option<&Id> {
+        self.hovered.as_ref()
+    }
+
+    /// Routes a mouse event to the regions it affects. Non-mouse events
+    /// (including [`Event::Gesture`]) produce nothing.
+    pub fn handle_event(&mut self, event: &Event) -> Vec<HitEvent<Id>> {
+        match event {
+            Event::Mouse(mouse) => self.handle_mouse_at(mouse, Instant::now()),
+            _ => Vec::new(),
+        }
+    }
+
+    fn handle_mouse_at(&mut self, mouse: &MouseEvent, now: Instant) -> Vec<HitEvent<Id>> {
+        let pointer = Position::new(mouse.column, mouse.row);
+        let target = self.hit_region(pointer).cloned();
+        let event = |id: Id, kind, origin: Rect| HitEvent {
+            id,
+            kind,
+            position: relative(pointer, origin),
+            modifiers: mouse.modifiers,
+        };
+        let mut events = Vec::new();
+
+        // Hover follows the pointer on every event, not only on moves.
+        let target_id = target.as_ref().map(|(_, id)| id);
+        if self.hovered.as_ref() != target_id {
+            if let Some(left) = self.hovered.take() {
+                let origin = self.area_of(&left).unwrap_or_default();
+                events.push(event(left, HitKind::Leave, origin));
+            }
+            if let Some((area, id)) = &target {
+                events.push(event(id.clone(), HitKind::Enter, *area));
+            }
+            self.hovered = target_id.cloned();
+        }
+
+        // Presses belong to the region they started on: a drag or click is
+        // reported there, or nowhere if the press missed every region.
+        if let MouseEventKind::Down(_) = mouse.kind {
+            self.pressed = target
+                .as_ref()
+                .map(|(area, id)| (id.clone(), relative(pointer, *area)));
+        }
+
+        match self.gestures.handle_mouse(mouse, now) {
+            Some(Gesture::DragStart { button, .. }) => {
+                if let Some((id, position)) = &self.pressed {
+                    events.push(HitEvent {
+                        id: id.clone(),
+                        kind: HitKind::DragStart(button),
+                        position: *position,
+                        modifiers: mouse.modifiers,
+                    });
+                }
+            }
+            Some(Gesture::Click { button, .. }) => {
+                if let (Some((pressed, _)), Some((area, id))) = (self.pressed.take(), &target)
+                    && pressed == *id
+                {
+                    let interval = self.gestures.config().multi_click_interval;
+                    let paired = self
+                        .last_click
+                        .take()
+                        .is_some_and(|(last, last_button, at)| {
+                            last == *id
+                                && last_button == button
+                                && now.duration_since(at) <= interval
+                        });
+                    let kind = if paired {
+                        HitKind::DoubleClick(button)
+                    } else {
+                        self.last_click = Some((id.clone(), button, now));
+                        HitKind::Click(button)
+                    };
+                    events.push(event(id.clone(), kind, *area));
+                }
+            }
+            _ => {}
+        }
+
+        events
+    }
+
+    fn hit_region(&self, position: Position) -> Option<&(Rect, Id)> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(position))
+    }
+
+    fn area_of(&self, id: &Id) -> Option<Rect> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|(_, candidate)| candidate == id)
+            .map(|(area, _)| *area)
+    }
+}
+
+fn relative(pointer: Position, origin: Rect) -> Position {
+    Position::new(
+        pointer.x.saturating_sub(origin.x),
+        pointer.y.saturating_sub(origin.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HitKind, HitMap};
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::layout::{Position, Rect};
+    use std::time::{Duration, Instant};
+
+    const LEFT: MouseButton = MouseButton::Left;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn kinds(
+        map: &mut HitMap<&'static str>,
+        event: MouseEvent,
+        now: Instant,
+    ) -> Vec<(&'static str, HitKind)> {
+        map.handle_mouse_at(&event, now)
+            .into_iter()
+            .map(|hit| (hit.id, hit.kind))
+            .collect()
+    }
+
+    fn buttons() -> HitMap<&'static str> {
+        let mut map = HitMap::new();
+        map.register(Rect::new(0, 0, 10, 1), "ok");
+        map.register(Rect::new(10, 0, 10, 1), "cancel");
+        map
+    }
+
+    #[test]
+    fn topmost_region_wins() {
+        let mut map = HitMap::new();
+        map.register(Rect::new(0, 0, 20, 10), "background");
+        map.register(Rect::new(5, 5, 4, 2), "popup");
+
+        assert_eq!(map.hit(Position::new(6, 6)), Some(&"popup"));
+        assert_eq!(map.hit(Position::new(1, 1)), Some(&"background"));
+        assert_eq!(map.hit(Position::new(30, 1)), None);
+    }
+
+    #[test]
+    fn hover_reports_leave_before_enter() {
+        let mut map = buttons();
+        let now = Instant::now();
+
+        assert_eq!(
+            kinds(&mut map, mouse(MouseEventKind::Moved, 2, 0), now),
+            [("ok", HitKind::Enter)]
+        );
+        assert_eq!(kinds(&mut map, mouse(MouseEventKind::Moved, 3, 0), now), []);
+        assert_eq!(
+            kinds(&mut map, mouse(MouseEventKind::Moved, 12, 0), now),
+            [("ok", HitKind::Leave), ("cancel", HitKind::Enter)]
+        );
+        assert_eq!(
+            kinds(&mut map, mouse(MouseEventKind::Moved, 40, 0), now),
+            [("cancel", HitKind::Leave)]
+        );
+    }
+
+    #[test]
+    fn click_positions_are_relative_to_the_region() {
+        let mut map = buttons();
+        let now = Instant::now();
+
+        map.handle_mouse_at(&mouse(MouseEventKind::Down(LEFT), 13, 0), now);
+        let hits = map.handle_mouse_at(&mouse(MouseEventKind::Up(LEFT), 13, 0), now);
+
+        let click = hits.last().expect("click");
+        assert_eq!((click.id, click.kind), ("cancel", HitKind::Click(LEFT)));
+        assert_eq!(click.position, Position::new(3, 0));
+    }
+
+    #[test]
+    fn quick_second_click_is_a_double_click() {
+        let mut map = buttons();
+        let start = Instant::now();
+        let click = |map: &mut HitMap<_>, at| {
+            map.handle_mouse_at(&mouse(MouseEventKind::Down(LEFT), 1, 0), at);
+            kinds(map, mouse(MouseEventKind::Up(LEFT), 1, 0), at)
+        };
+
+        assert_eq!(click(&mut map, start), [("ok", HitKind::Click(LEFT))]);
+        assert_eq!(
+            click(&mut map, start + Duration::from_millis(200)),
+            [("ok", HitKind::DoubleClick(LEFT))]
+        );
+        // A third click starts a new pair rather than another double.
+        assert_eq!(
+            click(&mut map, start + Duration::from_millis(300)),
+            [("ok", HitKind::Click(LEFT))]
+        );
+        assert_eq!(
+            click(&mut map, start + Duration::from_millis(400)),
+            [("ok", HitKind::DoubleClick(LEFT))]
+        );
+        assert_eq!(
+            click(&mut map, start + Duration::from_secs(2)),
+            [("ok", HitKind::Click(LEFT))]
+        );
+    }
+
+    #[test]
+    fn quick_clicks_on_adjacent_regions_are_two_clicks() {
+        let mut map = HitMap::new();
+        map.register(Rect::new(0, 0, 1, 1), "left");
+        map.register(Rect::new(1, 0, 1, 1), "right");
+        let start = Instant::now();
+
+        for (column, at, id) in [(0, 0, "left"), (1, 100, "right")] {
+            let at = start + Duration::from_millis(at);
+            map.handle_mouse_at(&mouse(MouseEventKind::Down(LEFT), column, 0), at);
+            let hits = kinds(&mut map, mouse(MouseEventKind::Up(LEFT), column, 0), at);
+            assert_eq!(hits.last(), Some(&(id, HitKind::Click(LEFT))));
+        }
+    }
+
+    #[test]
+    fn dragging_reports_drag_start_once_and_no_click() {
+        let mut map = buttons();
+        let now = Instant::now();
+
+        map.handle_mouse_at(&mouse(MouseEventKind::Down(LEFT), 4, 0), now);
+        let started = map.handle_mouse_at(&mouse(MouseEventKind::Drag(LEFT), 5, 0), now);
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].kind, HitKind::DragStart(LEFT));
+        assert_eq!(started[0].position, Position::new(4, 0), "press position");
+
+        assert_eq!(
+            kinds(&mut map, mouse(MouseEventKind::Drag(LEFT), 6, 0), now),
+            []
+        );
+        assert_eq!(
+            kinds(&mut map, mouse(MouseEventKind::Up(LEFT), 6, 0), now),
+            []
+        );
+    }
+}
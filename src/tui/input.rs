@@ -0,0 +1,425 @@
+//! Where the app loop's events come from.
+//!
+//! [`App`](crate::tui::App) reads keys, mouse, paste, and resize events from
+//! an [`InputSource`]. The default, [`TerminalInput`], polls the real
+//! terminal through crossterm — or, with the `event-stream` feature,
+//! `EventStreamInput` waits on crossterm's async stream instead. Apps on
+//! another backend plug in their own with
+//! [`App::with_input`](crate::tui::App::with_input) — as do demos and tests,
+//! with [`ScriptedInput`], and bug reports, with [`ReplayInput`].
+
+use crate::tui::event::Event;
+use crate::tui::session::{self, Record};
+use anyhow::{Context as AnyhowContext, Result};
+use crossterm::event::{self, KeyCode};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// The app loop's event channel. Errors end the run.
+pub(crate) type RuntimeEvent = Result<Event>;
+
+/// A source of events for the app loop.
+///
+/// [`start`](InputSource::start) is called at the beginning of every
+/// [`App::run`](crate::tui::App::run) and should deliver events in the
+/// background until the [`EventSender`] closes. The app also aborts the
+/// returned task when the run ends; blocking loops, which abort can't stop,
+/// should check [`EventSender::is_closed`] between reads.
+///
+/// ```ignore
+/// struct Keys(Vec<KeyCode>);
+///
+/// impl InputSource for Keys {
+///     fn start(&mut self, events: EventSender) -> JoinHandle<()> {
+///         let keys = self.0.clone();
+///         tokio::spawn(async move {
+///             for key in keys {
+///                 if !events.send(Event::key_press(key)).await {
+///                     break;
+///                 }
+///             }
+///         })
+///     }
+/// }
+/// ```
+pub trait InputSource: Send {
+    /// Starts delivering events to `events`.
+    fn start(&mut self, events: EventSender) -> JoinHandle<()>;
+}
+
+/// Delivers events from an [`InputSource`] to the app loop.
+#[derive(Clone)]
+pub struct EventSender {
+    sender: mpsc::Sender<RuntimeEvent>,
+}
+
+impl EventSender {
+    pub(crate) fn new(sender: mpsc::Sender<RuntimeEvent>) -> Self {
+        Self { sender }
+    }
+
+    /// Delivers `event`, waiting while the app is busy. Returns `false`
+    /// once the run has ended and nothing is listening.
+    pub async fn send(&self, event: Event) -> bool {
+        self.sender.send(Ok(event)).await.is_ok()
+    }
+
+    /// Like [`send`](Self::send), for sources that run on a blocking
+    /// thread. Panics if called from async code.
+    pub fn blocking_send(&self, event: Event) -> bool {
+        self.sender.blocking_send(Ok(event)).is_ok()
+    }
+
+    /// Ends the run with `error`, as a failed terminal read does.
+    pub async fn fail(&self, error: anyhow::Error) {
+        let _ = self.sender.send(Err(error)).await;
+    }
+
+    /// Like [`fail`](Self::fail), for sources that run on a blocking thread.
+    pub fn blocking_fail(&self, error: anyhow::Error) {
+        let _ = self.sender.blocking_send(Err(error));
+    }
+
+    /// Whether the run has ended, so there is no point reading more input.
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+/// Reads the real terminal through crossterm, polling on a blocking thread.
+///
+/// The default input for apps that take over the terminal. Key releases
+/// are dropped, so components only see presses.
+#[derive(Debug, Clone, Copy)]
+pub struct TerminalInput {
+    poll_rate: Duration,
+}
+
+impl TerminalInput {
+    /// Polls for input every `poll_rate` — also how long the app waits for
+    /// the input thread to notice a run has ended.
+    pub fn new(poll_rate: Duration) -> Self {
+        Self { poll_rate }
+    }
+}
+
+impl InputSource for TerminalInput {
+    fn start(&mut self, events: EventSender) -> JoinHandle<()> {
+        let poll_rate = self.poll_rate;
+        tokio::task::spawn_blocking(move || {
+            while !events.is_closed() {
+                match event::poll(poll_rate) {
+                    Ok(true) => match event::read() {
+                        Ok(event) if event.is_key_release() => {}
+                        Ok(event) => {
+                            if !events.blocking_send(Event::from(event)) {
+                                break;
+                            }
+                        }
+                        Err(error) => {
+                            events.blocking_fail(
+                                anyhow::Error::new(error).context("read terminal event"),
+                            );
+                            break;
+                        }
+                    },
+                    Ok(false) => {}
+                    Err(error) => {
+                        events.blocking_fail(
+                            anyhow::Error::new(error).context("poll terminal events"),
+                        );
+                        break;
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Reads the real terminal through crossterm's async `EventStream`
+/// (requires the `event-stream` feature).
+///
+/// Unlike [`TerminalInput`] there is no thread polling on a timer: the task
+/// sleeps until there is input, and stops the moment the run ends instead
+/// of at the next poll. With the feature enabled, apps that take over the
+/// terminal use it by default and `AppConfig::input_poll_rate` goes unused.
+#[cfg(feature = "event-stream")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventStreamInput;
+
+#[cfg(feature = "event-stream")]
+impl InputSource for EventStreamInput {
+    fn start(&mut self, events: EventSender) -> JoinHandle<()> {
+        use futures_core::Stream;
+        use std::pin::Pin;
+
+        tokio::spawn(async move {
+            // Dropping the stream (when the app aborts this task) wakes and
+            // stops crossterm's reader straight away.
+            let mut stream = event::EventStream::new();
+            loop {
+                let next = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+                match next {
+                    Some(Ok(event)) if event.is_key_release() => {}
+                    Some(Ok(event)) => {
+                        if !events.send(Event::from(event)).await {
+                            break;
+                        }
+                    }
+                    Some(Err(error)) => {
+                        events
+                            .fail(anyhow::Error::new(error).context("read terminal event"))
+                            .await;
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        })
+    }
+}
+
+/// Plays a fixed list of events, with pauses — for demos, and for tests that
+/// drive a whole app.
+///
+/// ```ignore
+/// let script = ScriptedInput::new([])
+///     .with_delay(Duration::from_millis(80))
+///     .text("ferris")
+///     .pause(Duration::from_secs(1))
+///     .key(KeyCode::Enter);
+/// App::new(Form::new())?.with_input(script).run().await?;
+/// ```
+///
+/// Input ends with the script; the app keeps running until something quits
+/// it, so end with the key that does (Ctrl-C quits by default).
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedInput {
+    steps: Vec<Step>,
+    delay: Duration,
+}
+
+#[derive(Debug, Clone)]
+enum Step {
+    Event(Event),
+    Pause(Duration),
+}
+
+impl ScriptedInput {
+    /// Starts a script with `events`, delivered as fast as the app takes
+    /// them.
+    pub fn new(events: impl IntoIterator<Item = Event>) -> Self {
+        Self {
+            steps: events.into_iter().map(Step::Event).collect(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Waits `delay` before each event, like a person typing.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Adds an event.
+    pub fn event(mut self, event: Event) -> Self {
+        self.steps.push(Step::Event(event));
+        self
+    }
+
+    /// Adds a key press.
+    pub fn key(self, code: KeyCode) -> Self {
+        self.event(Event::key_press(code))
+    }
+
+    /// Adds a key press for each character of `text`.
+    pub fn text(self, text: &str) -> Self {
+        text.chars()
+            .fold(self, |script, c| script.key(KeyCode::Char(c)))
+    }
+
+    /// Adds a pause before the next event.
+    pub fn pause(mut self, duration: Duration) -> Self {
+        self.steps.push(Step::Pause(duration));
+        self
+    }
+}
+
+impl InputSource for ScriptedInput {
+    fn start(&mut self, events: EventSender) -> JoinHandle<()> {
+        let steps = self.steps.clone();
+        let delay = self.delay;
+        tokio::spawn(async move {
+            for step in steps {
+                match step {
+                    Step::Pause(duration) => tokio::time::sleep(duration).await,
+                    Step::Event(event) => {
+                        tokio::time::sleep(delay).await;
+                        if !events.send(event).await {
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Replays the events of a recorded session file, at the pace they were
+/// recorded — to reproduce a bug report, or rerun a demo.
+///
+/// Recorded ticks are skipped: the app's own tick timer keeps running
+/// during a replay, and delivering both would double every animation.
+/// Messages and frames in the file are skipped too; see
+/// [`Session::replay`](crate::tui::Session::replay) for checking
+/// frames against the component.
+///
+/// Keys the app kept for itself, like a toast's action keys, aren't in the
+/// file, so a replay doesn't press them.
+#[derive(Debug, Clone)]
+pub struct ReplayInput {
+    events: Vec<(Duration, Event)>,
+    speed: f64,
+}
+
+const MIN_SPEED: f64 = 0.01;
+const MAX_SPEED: f64 = 100.0;
+
+impl ReplayInput {
+    /// Reads a session file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("read session {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("parse session {}", path.display()))
+    }
+
+    /// Parses the text of a session file.
+    pub fn parse(text: &str) -> Result<Self> {
+        let events = session::parse(text)?
+            .into_iter()
+            .filter_map(|(at, record)| match record {
+                Record::Event(Event::Tick(_)) => None,
+                Record::Event(event) => Some((at, event)),
+                Record::Messages(_) | Record::Frame(_) => None,
+            })
+            .collect();
+        Ok(Self { events, speed: 1.0 })
+    }
+
+    /// Plays back `speed` times as fast as recorded: `2.0` for double speed.
+    ///
+    /// Speeds are clamped to 0.01–100×; zero, negative, and NaN speeds are
+    /// ignored, leaving the current one.
+    pub fn speed(mut self, speed: f64) -> Self {
+        if speed > 0.0 {
+            self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        }
+        self
+    }
+}
+
+impl InputSource for ReplayInput {
+    fn start(&mut self, events: EventSender) -> JoinHandle<()> {
+        let recorded = self.events.clone();
+        let speed = self.speed;
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now();
+            for (at, event) in recorded {
+                let at = at.div_f64(speed);
+                tokio::time::sleep_until(start + at).await;
+                if !events.send(event).await {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventSender, InputSource, ReplayInput, ScriptedInput};
+    use crate::tui::event::Event;
+    use crossterm::event::KeyCode;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// Runs `input` to the end and returns what it delivered.
+    async fn collect(mut input: impl InputSource) -> Vec<String> {
+        let (sender, mut receiver) = mpsc::channel(16);
+        input.start(EventSender::new(sender)).await.unwrap();
+        let mut delivered = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            delivered.push(format!("{:?}", event.unwrap()));
+        }
+        delivered
+    }
+
+    #[tokio::test]
+    async fn sender_reports_when_the_run_has_ended() {
+        let (sender, mut receiver) = mpsc::channel(4);
+        let events = EventSender::new(sender);
+
+        assert!(events.send(Event::key_press(KeyCode::Enter)).await);
+        assert!(
+            receiver
+                .recv()
+                .await
+                .unwrap()
+                .unwrap()
+                .is_key(KeyCode::Enter)
+        );
+
+        drop(receiver);
+        assert!(events.is_closed());
+        assert!(!events.send(Event::key_press(KeyCode::Enter)).await);
+    }
+
+    #[tokio::test]
+    async fn scripts_play_their_events_in_order() {
+        let script = ScriptedInput::new([Event::Resize(80, 24)])
+            .with_delay(Duration::from_millis(1))
+            .text("hi")
+            .pause(Duration::from_millis(5))
+            .key(KeyCode::Enter);
+
+        let expected: Vec<String> = [
+            Event::Resize(80, 24),
+            Event::key_press(KeyCode::Char('h')),
+            Event::key_press(KeyCode::Char('i')),
+            Event::key_press(KeyCode::Enter),
+        ]
+        .iter()
+        .map(|event| format!("{event:?}"))
+        .collect();
+        assert_eq!(collect(script).await, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replays_skip_ticks_and_keep_the_recorded_pace() {
+        let session = "# session\n0 tick 250000\n20 key - 'j'\n200 key - enter\n";
+        let replay = ReplayInput::parse(session).unwrap().speed(2.0);
+
+        let started = tokio::time::Instant::now();
+        let delivered = collect(replay).await;
+
+        assert_eq!(delivered.len(), 2, "the tick is dropped: {delivered:?}");
+        assert_eq!(
+            started.elapsed(),
+            Duration::from_millis(100),
+            "double speed"
+        );
+    }
+
+    #[test]
+    fn replay_speeds_are_kept_finite_and_positive() {
+        let replay = ReplayInput::parse("20 key - 'j'\n").unwrap();
+        for ignored in [0.0, -2.0, f64::NAN] {
+            assert_eq!(replay.clone().speed(ignored).speed, 1.0, "{ignored}");
+        }
+        assert_eq!(replay.clone().speed(f64::INFINITY).speed, 100.0);
+        assert_eq!(replay.speed(1e-9).speed, 0.01);
+    }
+}
@@ -6,14 +6,39 @@
 //! a binary-only project unchanged.
 
 pub mod app;
+pub(crate) mod cast;
+pub mod clipboard;
 pub mod component;
 pub mod event;
+pub mod gesture;
+pub mod hit_test;
+pub mod input;
+pub(crate) mod plain;
+pub mod progress;
+pub mod screenshot;
+pub(crate) mod selection;
+pub mod session;
 pub mod terminal;
+pub mod toast;
+pub mod widgets;
 
-pub use app::{App, AppConfig, run, run_with_config};
+pub use app::{App, AppConfig, NonTtyMode, run, run_returning, run_with_config};
+pub use clipboard::ClipboardMode;
 pub use component::{Component, Context};
 pub use event::{Event, EventResult};
-pub use terminal::{TerminalConfig, TerminalGuard, TerminalType, Viewport};
+pub use gesture::{Gesture, GestureConfig};
+pub use hit_test::{HitEvent, HitKind, HitMap};
+#[cfg(feature = "event-stream")]
+pub use input::EventStreamInput;
+pub use input::{EventSender, InputSource, ReplayInput, ScriptedInput, TerminalInput};
+pub use progress::{ProgressConfig, ProgressTask};
+pub use screenshot::{ScreenshotConfig, ScreenshotFormat, save_screenshot, screenshot};
+pub use session::{ReplayedFrame, Session, SessionPlayer};
+pub use terminal::{
+    OutputTarget, TerminalBackend, TerminalConfig, TerminalGuard, TerminalType, TerminalWriter,
+    Viewport, is_interactive,
+};
+pub use toast::{Toast, ToastConfig, ToastCorner, ToastLevel};
 
 // Input types every component needs, so app code can import from one place.
 pub use crossterm::event::{
@@ -22,9 +47,10 @@ pub use crossterm::event::{
 
 // Ratatui building blocks used by nearly every `render` implementation.
 // `Terminal` and `backend` are re-exported so component tests can render
-// into `Terminal<TestBackend>` without importing ratatui directly.
+// into `Terminal<TestBackend>` without importing ratatui directly. Ratatui's
+// widgets are re-exported from `widgets`, next to the framework's own.
 pub use ratatui::{
-    Terminal, backend, layout,
+    Terminal, backend, buffer, layout,
     prelude::{Frame, Rect},
-    style, text, widgets,
+    style, text,
 };
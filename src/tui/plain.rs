@@ -0,0 +1,164 @@
+//! Plain-text output for running without a terminal.
+//!
+//! When stdout is a pipe or a CI log there is nothing to take over: no raw
+//! mode, no cursor, no input. The app loop renders into an offscreen buffer
+//! instead and writes each frame — or the component's
+//! [`plain_text`](crate::tui::Component::plain_text) summary — as ordinary
+//! lines, skipping frames that read the same as the last one written.
+
+use crate::tui::screenshot;
+use crate::tui::terminal::{OutputTarget, TerminalConfig, Viewport};
+use anyhow::{Context, Result};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::text::Line;
+use ratatui::{Frame, Terminal};
+use std::io::{self, Write};
+
+/// Frame size when the viewport doesn't give one.
+const DEFAULT_WIDTH: u16 = 80;
+const DEFAULT_HEIGHT: u16 = 24;
+
+pub(crate) struct PlainOutput {
+    terminal: Terminal<TestBackend>,
+    out: Box<dyn Write + Send>,
+    last: Option<String>,
+}
+
+impl PlainOutput {
+    /// Renders frames at the viewport's size and writes them where the
+    /// terminal UI would have gone. An app drawing on stderr or the tty
+    /// keeps stdout for its result, so without a terminal its frames go to
+    /// stderr.
+    pub(crate) fn new(config: TerminalConfig) -> Self {
+        let out: Box<dyn Write + Send> = match config.output {
+            OutputTarget::Stdout => Box::new(io::stdout()),
+            OutputTarget::Stderr | OutputTarget::Tty => Box::new(io::stderr()),
+        };
+        Self::with_writer(config.viewport, out)
+    }
+
+    fn with_writer(viewport: Viewport, out: Box<dyn Write + Send>) -> Self {
+        let (width, height) = match viewport {
+            Viewport::Fullscreen => (DEFAULT_WIDTH, DEFAULT_HEIGHT),
+            Viewport::Inline(height) | Viewport::BottomPanel(height) => (DEFAULT_WIDTH, height),
+            Viewport::Fixed(area) => (area.width, area.height),
+        };
+        // The test backend's error type is uninhabited.
+        let Ok(terminal) = Terminal::new(TestBackend::new(width, height));
+        Self {
+            terminal,
+            out,
+            last: None,
+        }
+    }
+
+    /// Changes the frame height, as an inline viewport would change its
+    /// own. The next draw picks it up.
+    pub(crate) fn set_height(&mut self, height: u16) {
+        let width = self.terminal.backend().buffer().area.width;
+        self.terminal.backend_mut().resize(width, height);
+    }
+
+    /// Renders a frame offscreen and returns it as text.
+    pub(crate) fn draw(&mut self, render: impl FnOnce(&mut Frame)) -> Result<String> {
+        let frame = self.terminal.draw(render).context("draw offscreen frame")?;
+        Ok(buffer_text(frame.buffer))
+    }
+
+    /// The last frame drawn.
+    pub(crate) fn buffer(&self) -> &Buffer {
+        self.terminal.backend().buffer()
+    }
+
+    /// Writes `text` unless it is what was written last.
+    pub(crate) fn write(&mut self, text: String) -> Result<()> {
+        if text.is_empty() || self.last.as_ref() == Some(&text) {
+            return Ok(());
+        }
+        writeln!(self.out, "{text}").context("write plain output")?;
+        self.out.flush().context("write plain output")?;
+        self.last = Some(text);
+        Ok(())
+    }
+
+    /// Writes lines that would go above an inline viewport, unconditionally.
+    pub(crate) fn print(&mut self, lines: &[Line<'_>]) -> Result<()> {
+        for line in lines {
+            writeln!(self.out, "{line}").context("write plain output")?;
+        }
+        self.out.flush().context("write plain output")
+    }
+}
+
+/// The buffer's text, row by row, without trailing spaces or blank rows.
+fn buffer_text(buffer: &Buffer) -> String {
+    let rows = screenshot::lines(buffer);
+    let used = rows
+        .iter()
+        .rposition(|row| !row.is_empty())
+        .map_or(0, |last| last + 1);
+    rows[..used].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlainOutput;
+    use crate::tui::terminal::Viewport;
+    use ratatui::text::Line;
+    use ratatui::widgets::Paragraph;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// A writer the test can read back.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Shared {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn frames_are_written_as_trimmed_text_once_per_change() {
+        let out = Shared::default();
+        let mut plain = PlainOutput::with_writer(Viewport::Inline(3), Box::new(out.clone()));
+
+        for text in ["Building  ", "Building  ", "Done", "日本 ok"] {
+            let frame = plain
+                .draw(|frame| frame.render_widget(Paragraph::new(text), frame.area()))
+                .unwrap();
+            plain.write(frame).unwrap();
+        }
+
+        assert_eq!(
+            out.text(),
+            "Building\nDone\n日本 ok\n",
+            "no repeats, no blank rows, no filler after wide characters"
+        );
+    }
+
+    #[test]
+    fn printed_lines_are_always_written() {
+        let out = Shared::default();
+        let mut plain = PlainOutput::with_writer(Viewport::Fullscreen, Box::new(out.clone()));
+
+        plain.write("status".into()).unwrap();
+        plain
+            .print(&[Line::from("test a ... ok"), Line::from("test a ... ok")])
+            .unwrap();
+
+        assert_eq!(out.text(), "status\ntest a ... ok\ntest a ... ok\n");
+    }
+}
@@ -0,0 +1,528 @@
+//! A multi-task progress board in the spirit of `indicatif::MultiProgress`.
+//!
+//! [`Context::add_task`](crate::tui::Context::add_task) registers a task and
+//! returns a [`ProgressTask`] handle to report through — from the component
+//! or from background tasks. The app loop draws one row per running task
+//! below the component: a bar (or a bouncing bar when the total is unknown),
+//! throughput, and an ETA worked out from the elapsed time in
+//! [`Event::Tick`](crate::tui::Event::Tick).
+//!
+//! In [`Viewport::Inline`](crate::tui::Viewport::Inline) the viewport grows
+//! by a row per task. A finished task's summary is printed permanently above
+//! the viewport and its row is removed, so the viewport shrinks back as the
+//! work completes.
+
+use crate::tui::widgets::BouncingBar;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// The stretch of recent progress throughput is measured over, so the
+/// estimate follows changes in speed without jumping on every tick.
+const RATE_WINDOW: Duration = Duration::from_secs(3);
+
+/// The widest the label column gets.
+const MAX_LABEL_WIDTH: u16 = 32;
+
+/// How the progress board behaves. Part of
+/// [`AppConfig`](crate::tui::AppConfig).
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressConfig {
+    /// Print a summary line above an inline viewport when a task finishes.
+    /// Without it, finished tasks just disappear.
+    pub print_finished: bool,
+    /// The most task rows shown at once; the rest are counted in a
+    /// "… N more" row.
+    pub max_visible: usize,
+}
+
+impl Default for ProgressConfig {
+    fn default() -> Self {
+        Self {
+            print_finished: true,
+            max_visible: 8,
+        }
+    }
+}
+
+/// A handle to one task on the progress board, returned by
+/// [`Context::add_task`](crate::tui::Context::add_task).
+///
+/// Cheap to clone and safe to move into background tasks; every clone
+/// reports on the same task. The board reads the latest state on each tick,
+/// so updating as often as you like costs no redraws.
+///
+/// A task whose handles are all dropped before it finishes shows as failed.
+#[derive(Debug, Clone)]
+pub struct ProgressTask {
+    state: Arc<Mutex<TaskState>>,
+}
+
+#[derive(Debug)]
+struct TaskState {
+    label: String,
+    message: String,
+    position: u64,
+    total: u64,
+    bytes: bool,
+    outcome: Option<Outcome>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Finished,
+    Failed(String),
+}
+
+impl ProgressTask {
+    pub(crate) fn new(label: String, total: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TaskState {
+                label,
+                message: String::new(),
+                position: 0,
+                total,
+                bytes: false,
+                outcome: None,
+            })),
+        }
+    }
+
+    /// Shows position and throughput as byte sizes (`4.2 MiB/s`) instead of
+    /// plain counts.
+    pub fn bytes(self) -> Self {
+        self.lock().bytes = true;
+        self
+    }
+
+    /// Moves the task on by `delta`.
+    pub fn inc(&self, delta: u64) {
+        let mut state = self.lock();
+        state.position = state.position.saturating_add(delta);
+    }
+
+    /// Sets how far the task has got.
+    pub fn set_position(&self, position: u64) {
+        self.lock().position = position;
+    }
+
+    /// Sets the total, once it is known; `0` means unknown.
+    pub fn set_total(&self, total: u64) {
+        self.lock().total = total;
+    }
+
+    /// Sets a short status shown after the label ("resolving", "unpacking").
+    pub fn set_message(&self, message: impl Into<String>) {
+        self.lock().message = message.into();
+    }
+
+    /// How far the task has got.
+    pub fn position(&self) -> u64 {
+        self.lock().position
+    }
+
+    /// Marks the task done. Its row is removed on the next tick.
+    pub fn finish(&self) {
+        self.lock().outcome.get_or_insert(Outcome::Finished);
+    }
+
+    /// Marks the task done with a final message for its summary line.
+    pub fn finish_with_message(&self, message: impl Into<String>) {
+        let mut state = self.lock();
+        state.message = message.into();
+        state.outcome.get_or_insert(Outcome::Finished);
+    }
+
+    /// Marks the task failed, with the reason for its summary line.
+    pub fn fail(&self, reason: impl Into<String>) {
+        self.lock()
+            .outcome
+            .get_or_insert(Outcome::Failed(reason.into()));
+    }
+
+    /// Whether [`finish`](Self::finish) or [`fail`](Self::fail) was called.
+    pub fn is_finished(&self) -> bool {
+        self.lock().outcome.is_some()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TaskState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// Two handles are equal when they report on the same task.
+impl PartialEq for ProgressTask {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl Eq for ProgressTask {}
+
+/// A task on the board, with what the board has worked out about its speed.
+struct Entry {
+    task: ProgressTask,
+    elapsed: Duration,
+    /// `(elapsed, position)` at each tick within the last [`RATE_WINDOW`],
+    /// plus the one before, oldest first.
+    samples: VecDeque<(Duration, u64)>,
+    bar: BouncingBar,
+}
+
+impl Entry {
+    fn advance(&mut self, elapsed: Duration) {
+        self.elapsed += elapsed;
+        self.bar.advance(elapsed);
+
+        self.samples.push_back((self.elapsed, self.task.position()));
+        while let Some(&(at, _)) = self.samples.get(1)
+            && self.elapsed - at >= RATE_WINDOW
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Units per second over the recent window; `None` until the first tick.
+    fn rate(&self) -> Option<f64> {
+        let (&(from, start), &(to, end)) = (self.samples.front()?, self.samples.back()?);
+        let seconds = (to - from).as_secs_f64();
+        (seconds > 0.0).then(|| end.saturating_sub(start) as f64 / seconds)
+    }
+
+    /// Time left at the current rate, when the total is known.
+    fn eta(&self, position: u64, total: u64) -> Option<Duration> {
+        let rate = self.rate().filter(|rate| *rate > 0.0)?;
+        (total > 0).then(|| Duration::from_secs_f64(total.saturating_sub(position) as f64 / rate))
+    }
+
+    /// The text right of the bar: position, throughput, and ETA or elapsed
+    /// time.
+    fn stats(&self, state: &TaskState) -> String {
+        let rate = self.rate().unwrap_or(0.0);
+        let rate = format!("{}/s", format_amount(rate as u64, state.bytes));
+        if state.total == 0 {
+            return format!(
+                "{}  {rate}  {}",
+                format_amount(state.position, state.bytes),
+                format_duration(self.elapsed)
+            );
+        }
+        let eta = match self.eta(state.position, state.total) {
+            Some(eta) => format_duration(eta),
+            None => "--".to_string(),
+        };
+        format!(
+            "{}/{}  {rate}  ETA {eta}",
+            format_amount(state.position, state.bytes),
+            format_amount(state.total, state.bytes)
+        )
+    }
+
+    /// The line printed above the viewport once the task is done.
+    fn summary(&self, state: &TaskState, outcome: &Outcome) -> Line<'static> {
+        match outcome {
+            Outcome::Finished => {
+                let mut line = Line::from(vec![
+                    Span::styled("✓ ", Style::default().fg(Color::Green)),
+                    Span::styled(
+                        state.label.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                ]);
+                if !state.message.is_empty() {
+                    line.push_span(Span::raw(format!(" — {}", state.message)));
+                }
+                let rate = state.position as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON);
+                line.push_span(Span::styled(
+                    format!(
+                        "  {} in {} ({}/s)",
+                        format_amount(state.position, state.bytes),
+                        format_duration(self.elapsed),
+                        format_amount(rate as u64, state.bytes)
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                line
+            }
+            Outcome::Failed(reason) => Line::from(vec![
+                Span::styled("✗ ", Style::default().fg(Color::Red)),
+                Span::styled(
+                    state.label.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" — {reason}"), Style::default().fg(Color::Red)),
+            ]),
+        }
+    }
+}
+
+/// The running tasks, drawn by the app loop below the component.
+pub(crate) struct ProgressBoard {
+    config: ProgressConfig,
+    entries: Vec<Entry>,
+}
+
+impl ProgressBoard {
+    pub(crate) fn new(config: ProgressConfig) -> Self {
+        Self {
+            config,
+            entries: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, task: ProgressTask) {
+        self.entries.push(Entry {
+            task,
+            elapsed: Duration::ZERO,
+            samples: VecDeque::from([(Duration::ZERO, 0)]),
+            bar: BouncingBar::new(),
+        });
+    }
+
+    /// Moves every task's clock on. Returns whether the board needs a
+    /// redraw, which is whenever it shows anything.
+    pub(crate) fn advance(&mut self, elapsed: Duration) -> bool {
+        for entry in &mut self.entries {
+            entry.advance(elapsed);
+        }
+        !self.entries.is_empty()
+    }
+
+    /// Removes finished tasks, returning the summary lines to print for them
+    /// (none when [`ProgressConfig::print_finished`] is off).
+    pub(crate) fn take_finished(&mut self) -> Vec<Line<'static>> {
+        let mut summaries = Vec::new();
+        self.entries.retain(|entry| {
+            // The board holds one handle; if it is the last, nobody can
+            // finish the task any more.
+            let abandoned = Arc::strong_count(&entry.task.state) == 1;
+            let state = entry.task.lock();
+            let outcome = match &state.outcome {
+                Some(outcome) => outcome.clone(),
+                None if abandoned => Outcome::Failed("stopped before finishing".to_string()),
+                None => return true,
+            };
+            if self.config.print_finished {
+                summaries.push(entry.summary(&state, &outcome));
+            }
+            false
+        });
+        summaries
+    }
+
+    /// Rows the board needs: one per visible task, plus one counting the
+    /// rest.
+    pub(crate) fn height(&self) -> u16 {
+        let max = self.config.max_visible.max(1);
+        let rows = self.entries.len().min(max) + usize::from(self.entries.len() > max);
+        u16::try_from(rows).unwrap_or(u16::MAX)
+    }
+
+    pub(crate) fn render(&self, frame: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+        let max = self.config.max_visible.max(1);
+        let visible = &self.entries[..self.entries.len().min(max)];
+
+        let rows: Vec<(String, String)> = visible
+            .iter()
+            .map(|entry| {
+                let state = entry.task.lock();
+                let label = match state.message.as_str() {
+                    "" => state.label.clone(),
+                    message => format!("{} · {message}", state.label),
+                };
+                (label, entry.stats(&state))
+            })
+            .collect();
+        let label_width =
+            column_width(rows.iter().map(|(label, _)| label), area.width / 3).min(MAX_LABEL_WIDTH);
+        let stats_width = column_width(rows.iter().map(|(_, stats)| stats), area.width / 2);
+
+        for (row, (entry, (label, stats))) in visible.iter().zip(rows).enumerate() {
+            let Some(y) = area
+                .y
+                .checked_add(row as u16)
+                .filter(|y| *y < area.bottom())
+            else {
+                break;
+            };
+            let [label_area, bar_area, stats_area] = Layout::horizontal([
+                Constraint::Length(label_width),
+                Constraint::Min(0),
+                Constraint::Length(stats_width),
+            ])
+            .spacing(1)
+            .areas(Rect {
+                y,
+                height: 1,
+                ..area
+            });
+
+            frame.render_widget(Line::from(label), label_area);
+            let state = entry.task.lock();
+            if state.total == 0 {
+                entry.bar.render(frame, bar_area);
+            } else {
+                render_bar(frame, bar_area, state.position, state.total);
+            }
+            frame.render_widget(
+                Line::styled(stats, Style::default().fg(Color::DarkGray)),
+                stats_area,
+            );
+        }
+
+        let hidden = self.entries.len() - visible.len();
+        if hidden > 0 && area.height as usize > visible.len() {
+            frame.render_widget(
+                Line::styled(
+                    format!("… {hidden} more"),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Rect {
+                    y: area.y + visible.len() as u16,
+                    height: 1,
+                    ..area
+                },
+            );
+        }
+    }
+}
+
+/// Width of the widest cell, capped at `max`.
+fn column_width<'a>(cells: impl Iterator<Item = &'a String>, max: u16) -> u16 {
+    let widest = cells.map(|cell| cell.chars().count()).max().unwrap_or(0);
+    u16::try_from(widest).unwrap_or(u16::MAX).min(max)
+}
+
+fn render_bar(frame: &mut Frame, area: Rect, position: u64, total: u64) {
+    let ratio = (position as f64 / total as f64).clamp(0.0, 1.0);
+    let filled = (ratio * f64::from(area.width)).round() as u16;
+    let line = Line::from(vec![
+        Span::styled("━".repeat(filled.into()), Style::default().fg(Color::Cyan)),
+        Span::styled(
+            "━".repeat((area.width - filled).into()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]);
+    frame.render_widget(line, area);
+}
+
+/// `1234` as `1.2k`, or as `1.2 KiB` for byte counts.
+fn format_amount(amount: u64, bytes: bool) -> String {
+    let (base, units): (f64, &[&str]) = if bytes {
+        (1024.0, &["B", "KiB", "MiB", "GiB", "TiB"])
+    } else {
+        (1000.0, &["", "k", "M", "G", "T"])
+    };
+    let mut value = amount as f64;
+    let mut unit = 0;
+    while value >= base && unit + 1 < units.len() {
+        value /= base;
+        unit += 1;
+    }
+    let separator = if bytes { " " } else { "" };
+    if unit == 0 {
+        format!("{amount}{separator}{}", units[0])
+    } else {
+        format!("{value:.1}{separator}{}", units[unit])
+    }
+}
+
+/// `75s` as `1m15s`.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m{:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h{:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProgressBoard, ProgressConfig, ProgressTask, format_amount, format_duration};
+    use std::time::Duration;
+
+    fn board() -> ProgressBoard {
+        ProgressBoard::new(ProgressConfig::default())
+    }
+
+    #[test]
+    fn throughput_and_eta_come_from_tick_time() {
+        let mut board = board();
+        let task = ProgressTask::new("download".into(), 100);
+        board.push(task.clone());
+
+        task.set_position(10);
+        board.advance(Duration::from_secs(1));
+        let entry = &board.entries[0];
+        assert_eq!(entry.rate(), Some(10.0));
+        assert_eq!(entry.eta(10, 100), Some(Duration::from_secs(9)));
+        assert_eq!(
+            entry.stats(&task.lock()),
+            "10/100  10/s  ETA 9s",
+            "position, throughput, time left"
+        );
+
+        // Twice as fast for a while: only the last few seconds count.
+        task.inc(20);
+        board.advance(Duration::from_secs(1));
+        assert_eq!(board.entries[0].rate(), Some(15.0));
+        for _ in 0..3 {
+            task.inc(20);
+            board.advance(Duration::from_secs(1));
+        }
+        assert_eq!(board.entries[0].rate(), Some(20.0));
+    }
+
+    #[test]
+    fn finished_and_abandoned_tasks_leave_a_summary_and_free_their_row() {
+        let mut board = ProgressBoard::new(ProgressConfig {
+            max_visible: 2,
+            ..ProgressConfig::default()
+        });
+        let done = ProgressTask::new("fetch".into(), 4);
+        let running = ProgressTask::new("build".into(), 0);
+        board.push(done.clone());
+        board.push(running.clone());
+        board.push(ProgressTask::new("lost".into(), 1)); // handle dropped
+        assert_eq!(board.height(), 3, "two rows and a \"1 more\" row");
+
+        done.set_position(4);
+        board.advance(Duration::from_secs(2));
+        done.finish_with_message("up to date");
+        let printed: Vec<String> = board
+            .take_finished()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            printed,
+            [
+                "✓ fetch — up to date  4 in 2s (2/s)",
+                "✗ lost — stopped before finishing",
+            ]
+        );
+        assert_eq!(board.height(), 1, "the viewport shrinks to what is left");
+        assert!(!running.is_finished());
+    }
+
+    #[test]
+    fn amounts_and_durations_read_naturally() {
+        assert_eq!(format_amount(999, false), "999");
+        assert_eq!(format_amount(1_240, false), "1.2k");
+        assert_eq!(format_amount(512, true), "512 B");
+        assert_eq!(format_amount(3 * 1024 * 1024, true), "3.0 MiB");
+        assert_eq!(format_duration(Duration::from_secs(75)), "1m15s");
+        assert_eq!(format_duration(Duration::from_secs(3_720)), "1h02m");
+    }
+}
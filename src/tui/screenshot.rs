@@ -0,0 +1,532 @@
+//! Screenshots of a rendered frame, for docs and bug reports.
+//!
+//! [`screenshot`] turns a [`Buffer`] into plain text, ANSI-colored text,
+//! HTML, or SVG. Take the buffer from the frame `Terminal::draw` returns, or
+//! from `TestBackend::buffer` in a test. To save screenshots from a running
+//! app instead, bind a key in [`AppConfig::screenshots`](crate::tui::AppConfig::screenshots).
+
+use anyhow::{Context as AnyhowContext, Result};
+use crossterm::event::KeyEvent;
+use ratatui::buffer::{Buffer, CellDiffOption, CellWidth};
+use ratatui::style::{Color, Modifier};
+use ratatui::text::Span;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Colors that stand in for the terminal's own defaults in HTML and SVG.
+const FOREGROUND: (u8, u8, u8) = (229, 229, 229);
+const BACKGROUND: (u8, u8, u8) = (30, 30, 30);
+
+/// The 16 named colors, in ANSI order, as xterm draws them.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// SVG cell size in pixels, for a 15px monospace font.
+const CELL_WIDTH: u32 = 9;
+const CELL_HEIGHT: u32 = 18;
+const FONT_SIZE: u32 = 15;
+
+/// What [`screenshot`] produces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    /// The characters only, one line per row.
+    Text,
+    /// Text with ANSI escape codes, for `cat` in a terminal.
+    Ansi,
+    /// A `<pre>` block with inline styles, to paste into a page.
+    Html,
+    /// A standalone image that renders the same everywhere.
+    #[default]
+    Svg,
+}
+
+impl ScreenshotFormat {
+    /// The file extension screenshots in this format are saved with.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Ansi => "ansi",
+            Self::Html => "html",
+            Self::Svg => "svg",
+        }
+    }
+
+    /// The format a path's extension names, if any.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        [Self::Text, Self::Ansi, Self::Html, Self::Svg]
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+}
+
+/// Saving screenshots from a running app. Part of
+/// [`AppConfig`](crate::tui::AppConfig).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenshotConfig {
+    /// The key that saves the next frame, taken before the component sees
+    /// it. `None`, the default, binds nothing.
+    pub key: Option<KeyEvent>,
+    /// What the key saves: an SVG image by default.
+    pub format: ScreenshotFormat,
+    /// Where screenshots go, named `screenshot-<unix time>.<extension>`.
+    pub directory: PathBuf,
+}
+
+impl Default for ScreenshotConfig {
+    fn default() -> Self {
+        Self {
+            key: None,
+            format: ScreenshotFormat::default(),
+            directory: PathBuf::from("."),
+        }
+    }
+}
+
+impl ScreenshotConfig {
+    /// A path for a new screenshot that doesn't overwrite an older one.
+    pub(crate) fn next_path(&self) -> PathBuf {
+        let seconds = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let extension = self.format.extension();
+        let mut path = self
+            .directory
+            .join(format!("screenshot-{seconds}.{extension}"));
+        let mut n = 2;
+        while path.exists() {
+            path = self
+                .directory
+                .join(format!("screenshot-{seconds}-{n}.{extension}"));
+            n += 1;
+        }
+        path
+    }
+}
+
+/// Serializes what `buffer` shows.
+pub fn screenshot(buffer: &Buffer, format: ScreenshotFormat) -> String {
+    let rows = rows(buffer);
+    match format {
+        ScreenshotFormat::Text => text(&rows),
+        ScreenshotFormat::Ansi => ansi(&rows),
+        ScreenshotFormat::Html => html(&rows),
+        ScreenshotFormat::Svg => svg(&rows, buffer.area.width),
+    }
+}
+
+/// Writes a screenshot of `buffer` to `path`, in the format its extension
+/// names (`.txt`, `.ansi`, `.html`, or `.svg`).
+pub fn save_screenshot(buffer: &Buffer, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let format = ScreenshotFormat::from_path(path).with_context(|| {
+        format!(
+            "no screenshot format for {}: use .txt, .ansi, .html, or .svg",
+            path.display()
+        )
+    })?;
+    std::fs::write(path, screenshot(buffer, format))
+        .with_context(|| format!("write screenshot {}", path.display()))
+}
+
+/// Neighbouring cells on a row that look the same.
+#[derive(Debug, PartialEq)]
+struct Run {
+    /// First cell, and how many cells the text covers.
+    x: u16,
+    width: u16,
+    fg: Color,
+    bg: Color,
+    modifier: Modifier,
+    text: String,
+}
+
+impl Run {
+    fn is_plain(&self) -> bool {
+        self.fg == Color::Reset && self.bg == Color::Reset && self.modifier.is_empty()
+    }
+}
+
+/// Splits each row into runs of one style. A wide character covers the
+/// cell after it, which is left out; trailing blank cells are dropped.
+fn rows(buffer: &Buffer) -> Vec<Vec<Run>> {
+    let width = usize::from(buffer.area.width.max(1));
+    buffer
+        .content
+        .chunks(width)
+        .map(|cells| {
+            let mut runs: Vec<Run> = Vec::new();
+            let mut covered_until = 0;
+            for (x, cell) in (0..).zip(cells) {
+                if x < covered_until || cell.diff_option == CellDiffOption::Skip {
+                    continue;
+                }
+                let cell_width = cell.cell_width().max(1);
+                covered_until = x + cell_width;
+                match runs.last_mut() {
+                    Some(run)
+                        if run.fg == cell.fg
+                            && run.bg == cell.bg
+                            && run.modifier == cell.modifier =>
+                    {
+                        run.text.push_str(cell.symbol());
+                        run.width += cell_width;
+                    }
+                    _ => runs.push(Run {
+                        x,
+                        width: cell_width,
+                        fg: cell.fg,
+                        bg: cell.bg,
+                        modifier: cell.modifier,
+                        text: cell.symbol().to_string(),
+                    }),
+                }
+            }
+            if let Some(last) = runs.last_mut()
+                && last.is_plain()
+            {
+                let trimmed = last.text.trim_end().len();
+                // Blanks can be wide (U+3000), so this is columns, not bytes.
+                let blank = Span::raw(&last.text[trimmed..]).width() as u16;
+                last.width = last.width.saturating_sub(blank);
+                last.text.truncate(trimmed);
+            }
+            runs.retain(|run| !run.text.is_empty());
+            runs
+        })
+        .collect()
+}
+
+/// The buffer's text, one line per row, without trailing spaces. Shared
+/// with plain output, so both read wide characters the same way.
+pub(crate) fn lines(buffer: &Buffer) -> Vec<String> {
+    rows(buffer).iter().map(|runs| line(runs)).collect()
+}
+
+fn line(runs: &[Run]) -> String {
+    let line: String = runs.iter().map(|run| run.text.as_str()).collect();
+    line.trim_end().to_string()
+}
+
+fn text(rows: &[Vec<Run>]) -> String {
+    let mut out = String::new();
+    for runs in rows {
+        out.push_str(&line(runs));
+        out.push('\n');
+    }
+    out
+}
+
+fn ansi(rows: &[Vec<Run>]) -> String {
+    let mut out = String::new();
+    for runs in rows {
+        for run in runs {
+            if run.is_plain() {
+                out.push_str(&run.text);
+                continue;
+            }
+            let mut codes = Vec::new();
+            if let Some(code) = sgr_color(run.fg, false) {
+                codes.push(code);
+            }
+            if let Some(code) = sgr_color(run.bg, true) {
+                codes.push(code);
+            }
+            let modifiers = [
+                (Modifier::BOLD, "1"),
+                (Modifier::DIM, "2"),
+                (Modifier::ITALIC, "3"),
+                (Modifier::UNDERLINED, "4"),
+                (Modifier::SLOW_BLINK, "5"),
+                (Modifier::RAPID_BLINK, "6"),
+                (Modifier::REVERSED, "7"),
+                (Modifier::HIDDEN, "8"),
+                (Modifier::CROSSED_OUT, "9"),
+            ];
+            codes.extend(
+                modifiers
+                    .iter()
+                    .filter(|(modifier, _)| run.modifier.contains(*modifier))
+                    .map(|(_, code)| code.to_string()),
+            );
+            let _ = write!(out, "\x1b[{}m{}\x1b[0m", codes.join(";"), run.text);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The SGR parameters that set `color`, or `None` for the terminal default.
+fn sgr_color(color: Color, background: bool) -> Option<String> {
+    let offset = if background { 10 } else { 0 };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", 38 + offset),
+        Color::Indexed(index) => format!("{};5;{index}", 38 + offset),
+        named => {
+            let index = palette_index(named)?;
+            if index < 8 {
+                (30 + offset + index).to_string()
+            } else {
+                (90 + offset + index - 8).to_string()
+            }
+        }
+    };
+    Some(code)
+}
+
+fn palette_index(color: Color) -> Option<u8> {
+    Some(match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Reset | Color::Rgb(..) | Color::Indexed(_) => return None,
+    })
+}
+
+/// `color` as `#rrggbb`, with `default` for the terminal default.
+fn hex(color: Color, default: (u8, u8, u8)) -> String {
+    let (r, g, b) = match color {
+        Color::Reset => default,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) => indexed(index),
+        named => palette_index(named).map_or(default, |index| PALETTE[usize::from(index)]),
+    };
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// The xterm 256-color palette: the 16 named colors, a 6×6×6 cube, then 24
+/// grays.
+fn indexed(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..16 => PALETTE[usize::from(index)],
+        16..232 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        232.. => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// A run's colors as `#rrggbb`, after reversing and hiding.
+fn run_colors(run: &Run) -> (String, String) {
+    let mut fg = hex(run.fg, FOREGROUND);
+    let mut bg = hex(run.bg, BACKGROUND);
+    if run.modifier.contains(Modifier::REVERSED) {
+        std::mem::swap(&mut fg, &mut bg);
+    }
+    if run.modifier.contains(Modifier::HIDDEN) {
+        fg.clone_from(&bg);
+    }
+    (fg, bg)
+}
+
+fn text_decoration(modifier: Modifier) -> Option<&'static str> {
+    match (
+        modifier.contains(Modifier::UNDERLINED),
+        modifier.contains(Modifier::CROSSED_OUT),
+    ) {
+        (true, true) => Some("underline line-through"),
+        (true, false) => Some("underline"),
+        (false, true) => Some("line-through"),
+        (false, false) => None,
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn html(rows: &[Vec<Run>]) -> String {
+    let mut out = format!(
+        "<pre style=\"background-color:{};color:{};font-family:monospace;line-height:1.2;padding:0.5em\">",
+        hex(Color::Reset, BACKGROUND),
+        hex(Color::Reset, FOREGROUND)
+    );
+    for runs in rows {
+        for run in runs {
+            if run.is_plain() {
+                out.push_str(&escape(&run.text));
+                continue;
+            }
+            let (fg, bg) = run_colors(run);
+            let mut style = format!("color:{fg}");
+            if run.bg != Color::Reset || run.modifier.contains(Modifier::REVERSED) {
+                let _ = write!(style, ";background-color:{bg}");
+            }
+            if run.modifier.contains(Modifier::BOLD) {
+                style.push_str(";font-weight:bold");
+            }
+            if run.modifier.contains(Modifier::ITALIC) {
+                style.push_str(";font-style:italic");
+            }
+            if run.modifier.contains(Modifier::DIM) {
+                style.push_str(";opacity:0.6");
+            }
+            if let Some(decoration) = text_decoration(run.modifier) {
+                let _ = write!(style, ";text-decoration:{decoration}");
+            }
+            let _ = write!(out, "<span style=\"{style}\">{}</span>", escape(&run.text));
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n");
+    out
+}
+
+fn svg(rows: &[Vec<Run>], columns: u16) -> String {
+    let width = u32::from(columns) * CELL_WIDTH;
+    let height = rows.len() as u32 * CELL_HEIGHT;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"ui-monospace, Menlo, Consolas, monospace\" \
+         font-size=\"{FONT_SIZE}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(Color::Reset, BACKGROUND)
+    );
+    for (row, runs) in rows.iter().enumerate() {
+        let y = row as u32 * CELL_HEIGHT;
+        for run in runs {
+            let x = u32::from(run.x) * CELL_WIDTH;
+            let run_width = u32::from(run.width) * CELL_WIDTH;
+            let (fg, bg) = run_colors(run);
+            if run.bg != Color::Reset || run.modifier.contains(Modifier::REVERSED) {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{run_width}\" height=\"{CELL_HEIGHT}\" fill=\"{bg}\"/>"
+                );
+            }
+            if run.text.trim().is_empty() {
+                continue;
+            }
+            let mut attributes = format!("fill=\"{fg}\"");
+            if run.modifier.contains(Modifier::BOLD) {
+                attributes.push_str(" font-weight=\"bold\"");
+            }
+            if run.modifier.contains(Modifier::ITALIC) {
+                attributes.push_str(" font-style=\"italic\"");
+            }
+            if run.modifier.contains(Modifier::DIM) {
+                attributes.push_str(" opacity=\"0.6\"");
+            }
+            if let Some(decoration) = text_decoration(run.modifier) {
+                let _ = write!(attributes, " text-decoration=\"{decoration}\"");
+            }
+            // Pinning the text to its cells keeps columns lined up whatever
+            // font the viewer substitutes.
+            let _ = writeln!(
+                out,
+                "<text x=\"{x}\" y=\"{}\" {attributes} textLength=\"{run_width}\" \
+                 lengthAdjust=\"spacingAndGlyphs\" xml:space=\"preserve\">{}</text>",
+                y + FONT_SIZE,
+                escape(&run.text)
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScreenshotFormat, screenshot};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Modifier, Style};
+
+    fn sample() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 2));
+        buffer.set_string(0, 0, "a<b", Style::default());
+        buffer.set_string(4, 0, "ok", Style::default().fg(Color::Green));
+        buffer.set_string(0, 1, "日本", Style::default().add_modifier(Modifier::BOLD));
+        buffer.set_string(5, 1, "!", Style::default().bg(Color::Rgb(1, 2, 3)));
+        buffer
+    }
+
+    #[test]
+    fn text_keeps_columns_and_skips_cells_under_wide_characters() {
+        assert_eq!(
+            screenshot(&sample(), ScreenshotFormat::Text),
+            "a<b ok\n日本 !\n"
+        );
+        assert_eq!(
+            ScreenshotFormat::from_path("shot.HTML"),
+            Some(ScreenshotFormat::Html)
+        );
+    }
+
+    #[test]
+    fn ansi_sets_and_resets_each_styled_run() {
+        assert_eq!(
+            screenshot(&sample(), ScreenshotFormat::Ansi),
+            "a<b \x1b[32mok\x1b[0m\n\x1b[1m日本\x1b[0m \x1b[48;2;1;2;3m!\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn html_and_svg_escape_text_and_use_inline_colors() {
+        let html = screenshot(&sample(), ScreenshotFormat::Html);
+        assert!(html.contains("a&lt;b <span style=\"color:#00cd00\">ok</span>\n"));
+        assert!(html.contains("<span style=\"color:#e5e5e5;font-weight:bold\">日本</span>"));
+
+        let svg = screenshot(&sample(), ScreenshotFormat::Svg);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"108\""));
+        assert!(svg.contains(">a&lt;b </text>"));
+        assert!(svg.contains("fill=\"#010203\""));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn trailing_wide_blanks_trim_by_their_width() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_string(0, 0, "\u{3000}", Style::default());
+        assert_eq!(screenshot(&buffer, ScreenshotFormat::Text), "\n");
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buffer.set_string(0, 0, "a\u{3000} ", Style::default());
+        let svg = screenshot(&buffer, ScreenshotFormat::Svg);
+        assert!(svg.contains("textLength=\"9\""), "{svg}");
+    }
+}
@@ -0,0 +1,301 @@
+//! Mouse text selection over the rendered frame.
+//!
+//! Mouse capture takes the terminal's own selection away. With
+//! `AppConfig::text_selection` on, the app loop gives it back: drag with the
+//! left button to select (hold Alt for a rectangular block), the selection is
+//! highlighted on top of the component's frame, and releasing the button
+//! copies the selected text to the clipboard.
+//!
+//! Components get first refusal on every mouse event, exactly as with
+//! Ctrl-C: only presses and drags they propagate start or extend a
+//! selection, so clickable widgets keep working.
+
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::Span;
+
+/// The shape a drag selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SelectionMode {
+    /// Reading order, like a text editor: the rest of the first row, every
+    /// row in between, and the start of the last row.
+    Linewise,
+    /// The rectangle spanned by the two corners (Alt+drag).
+    Block,
+}
+
+/// An in-progress or finished mouse selection, in screen coordinates.
+#[derive(Debug, Default)]
+pub(crate) struct Selection {
+    region: Option<Region>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    anchor: Position,
+    cursor: Position,
+    mode: SelectionMode,
+    /// The button is still down.
+    dragging: bool,
+    /// Released but not copied yet: the next render copies it.
+    copy_pending: bool,
+}
+
+impl Selection {
+    /// Starts, extends, or finishes a selection. Returns `true` when the
+    /// highlight changed and the frame needs a redraw.
+    pub(crate) fn handle_mouse(&mut self, mouse: MouseEvent) -> bool {
+        let position = Position::new(mouse.column, mouse.row);
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let mode = if mouse.modifiers.contains(KeyModifiers::ALT) {
+                    SelectionMode::Block
+                } else {
+                    SelectionMode::Linewise
+                };
+                let had_highlight = self.is_visible();
+                self.region = Some(Region {
+                    anchor: position,
+                    cursor: position,
+                    mode,
+                    dragging: true,
+                    copy_pending: false,
+                });
+                had_highlight
+            }
+            MouseEventKind::Drag(MouseButton::Left) => match &mut self.region {
+                Some(region) if region.dragging && region.cursor != position => {
+                    region.cursor = position;
+                    true
+                }
+                _ => false,
+            },
+            MouseEventKind::Up(MouseButton::Left) => match &mut self.region {
+                Some(region) if region.dragging => {
+                    region.cursor = position;
+                    region.dragging = false;
+                    // A click without a drag selects nothing.
+                    if region.anchor == region.cursor {
+                        self.region = None;
+                    } else {
+                        region.copy_pending = true;
+                    }
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Drops the selection. Returns `true` if a highlight disappeared.
+    pub(crate) fn clear(&mut self) -> bool {
+        let had_highlight = self.is_visible();
+        self.region = None;
+        had_highlight
+    }
+
+    /// Highlights the selection on top of `buffer`. Returns the selected
+    /// text once, on the first render after the button is released.
+    pub(crate) fn render(&mut self, buffer: &mut Buffer) -> Option<String> {
+        let region = self.region.as_mut()?;
+
+        let copied = region.copy_pending.then(|| selected_text(buffer, *region));
+        region.copy_pending = false;
+
+        for (row, span) in region.rows(*buffer.area()) {
+            for x in span.left()..span.right() {
+                if let Some(cell) = buffer.cell_mut(Position::new(x, row)) {
+                    cell.modifier.toggle(Modifier::REVERSED);
+                }
+            }
+        }
+
+        copied
+    }
+
+    fn is_visible(&self) -> bool {
+        self.region
+            .is_some_and(|region| region.anchor != region.cursor)
+    }
+}
+
+impl Region {
+    /// The selected cells of each row, clipped to `area`.
+    fn rows(self, area: Rect) -> impl Iterator<Item = (u16, Rect)> {
+        let clamp = |position: Position| {
+            Position::new(
+                position
+                    .x
+                    .clamp(area.left(), area.right().saturating_sub(1)),
+                position
+                    .y
+                    .clamp(area.top(), area.bottom().saturating_sub(1)),
+            )
+        };
+        let empty = area.is_empty();
+        // An empty area has no cell to clamp to, and its bounds are inverted.
+        let (anchor, cursor) = if empty {
+            (self.anchor, self.cursor)
+        } else {
+            (clamp(self.anchor), clamp(self.cursor))
+        };
+
+        // Reading order: the earlier point starts the selection.
+        let (start, end) = if (anchor.y, anchor.x) <= (cursor.y, cursor.x) {
+            (anchor, cursor)
+        } else {
+            (cursor, anchor)
+        };
+        let mode = self.mode;
+
+        (start.y..=end.y).filter(move |_| !empty).map(move |row| {
+            let (left, right) = match mode {
+                SelectionMode::Block => (anchor.x.min(cursor.x), anchor.x.max(cursor.x)),
+                SelectionMode::Linewise => (
+                    if row == start.y { start.x } else { area.left() },
+                    if row == end.y {
+                        end.x
+                    } else {
+                        area.right().saturating_sub(1)
+                    },
+                ),
+            };
+            (row, Rect::new(left, row, right - left + 1, 1))
+        })
+    }
+}
+
+/// Collects the symbols under `region`, one line per row, with trailing
+/// blanks trimmed like a terminal's own selection.
+fn selected_text(buffer: &Buffer, region: Region) -> String {
+    let mut lines = Vec::new();
+
+    for (row, span) in region.rows(*buffer.area()) {
+        let mut line = String::new();
+        let mut x = span.left();
+
+        while x < span.right() {
+            let Some(cell) = buffer.cell(Position::new(x, row)) else {
+                break;
+            };
+            let symbol = cell.symbol();
+            line.push_str(symbol);
+            // A wide character is followed by filler cells it covers on
+            // screen; skip them so they don't turn into extra spaces.
+            x += (Span::raw(symbol).width() as u16).max(1);
+        }
+
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selection;
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::{Position, Rect};
+    use ratatui::style::Modifier;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16, modifiers: KeyModifiers) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers,
+        }
+    }
+
+    fn drag(selection: &mut Selection, from: (u16, u16), to: (u16, u16), modifiers: KeyModifiers) {
+        let left = MouseButton::Left;
+        selection.handle_mouse(mouse(MouseEventKind::Down(left), from.0, from.1, modifiers));
+        selection.handle_mouse(mouse(MouseEventKind::Drag(left), to.0, to.1, modifiers));
+        selection.handle_mouse(mouse(MouseEventKind::Up(left), to.0, to.1, modifiers));
+    }
+
+    fn screen() -> Buffer {
+        Buffer::with_lines(["hello world", "second line", "third"])
+    }
+
+    #[test]
+    fn linewise_drag_copies_in_reading_order() {
+        let mut selection = Selection::default();
+        let mut buffer = screen();
+
+        // Dragging backwards selects the same text as dragging forwards.
+        drag(&mut selection, (3, 1), (6, 0), KeyModifiers::NONE);
+
+        assert_eq!(
+            selection.render(&mut buffer).as_deref(),
+            Some("world\nseco")
+        );
+        assert_eq!(selection.render(&mut buffer), None, "copies only once");
+    }
+
+    #[test]
+    fn alt_drag_selects_a_block() {
+        let mut selection = Selection::default();
+        let mut buffer = screen();
+
+        drag(&mut selection, (0, 0), (2, 2), KeyModifiers::ALT);
+
+        assert_eq!(
+            selection.render(&mut buffer).as_deref(),
+            Some("hel\nsec\nthi")
+        );
+    }
+
+    #[test]
+    fn highlight_reverses_selected_cells_only() {
+        let mut selection = Selection::default();
+        let mut buffer = screen();
+
+        drag(&mut selection, (0, 0), (1, 0), KeyModifiers::NONE);
+        selection.render(&mut buffer);
+
+        let reversed = |x, y| {
+            buffer[Position::new(x, y)]
+                .modifier
+                .contains(Modifier::REVERSED)
+        };
+        assert!(reversed(0, 0) && reversed(1, 0));
+        assert!(!reversed(2, 0) && !reversed(0, 1));
+    }
+
+    #[test]
+    fn click_without_drag_selects_nothing() {
+        let mut selection = Selection::default();
+        let mut buffer = screen();
+
+        drag(&mut selection, (4, 1), (4, 1), KeyModifiers::NONE);
+
+        assert_eq!(selection.render(&mut buffer), None);
+        assert!(!selection.clear());
+    }
+
+    #[test]
+    fn wide_characters_copy_without_filler_cells() {
+        let mut selection = Selection::default();
+        let mut buffer = Buffer::with_lines(["日本 ok"]);
+
+        drag(&mut selection, (0, 0), (6, 0), KeyModifiers::NONE);
+
+        assert_eq!(selection.render(&mut buffer).as_deref(), Some("日本 ok"));
+    }
+
+    #[test]
+    fn zero_height_screens_select_nothing() {
+        let mut selection = Selection::default();
+        let mut buffer = Buffer::empty(Rect::new(0, 2, 10, 0));
+
+        drag(&mut selection, (1, 2), (4, 2), KeyModifiers::NONE);
+
+        assert_eq!(selection.render(&mut buffer).as_deref(), Some(""));
+    }
+}
@@ -0,0 +1,933 @@
+//! Session files: a recording of everything that reached the component —
+//! events, message counts, and the frames it drew — one record per line,
+//! stamped with the milliseconds since the session started.
+//!
+//! ```text
+//! # tui-base-framework session v1
+//! 0 resize 80 24
+//! 3 frame 80 24
+//! 3 cells 0 0 Reset/Reset/Reset/0 "Count: 0"
+//! 412 key - 'j'
+//! 530 messages 2
+//! 531 frame 80 24
+//! 531 cells 7 0 Reset/Reset/Reset/1 "2"
+//! 900 mouse down-left 12 4 -
+//! 1250 paste "two\nlines"
+//! ```
+//!
+//! Frames are stored as the cells that changed since the previous frame, in
+//! runs of one style: `cells <x> <y> <fg>/<bg>/<underline>/<modifier bits> "<text>"`.
+//! `messages N` counts the messages delivered to
+//! [`Component::update`] since the last record.
+//!
+//! Lines are plain text so a session attached to a bug report can be read —
+//! and trimmed — by hand. Blank lines and `#` comments are ignored, and so
+//! are record kinds this version doesn't know.
+//!
+//! Set [`AppConfig::record_session`](crate::tui::AppConfig::record_session)
+//! to record one, then [`Session::replay`] it against the component or
+//! watch its frames with [`SessionPlayer`].
+
+use crate::tui::component::{Component, Context};
+use crate::tui::event::Event;
+use crate::tui::event::EventResult;
+use crate::tui::gesture::{GestureConfig, GestureRecognizer};
+use anyhow::{Context as AnyhowContext, Result, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::{Frame, Terminal};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+pub(crate) const HEADER: &str = "# tui-base-framework session v1";
+
+/// How long a replay waits for a message the recording says arrived, before
+/// deciding the component's background work went differently this time.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One line of a session file, with the `cells` lines of a frame folded into
+/// it.
+#[derive(Debug, Clone)]
+pub(crate) enum Record {
+    Event(Event),
+    Messages(usize),
+    Frame(FrameDiff),
+}
+
+/// The cells that changed between one frame and the one before it.
+#[derive(Debug, Clone)]
+pub(crate) struct FrameDiff {
+    width: u16,
+    height: u16,
+    runs: Vec<Run>,
+}
+
+/// Neighbouring cells on one row that share a style.
+#[derive(Debug, Clone)]
+struct Run {
+    x: u16,
+    y: u16,
+    style: Style,
+    text: String,
+}
+
+impl FrameDiff {
+    /// Applies the diff on top of the frame before it, or on a blank frame
+    /// when the size changed.
+    fn apply(&self, previous: Option<Buffer>) -> Buffer {
+        let area = Rect::new(0, 0, self.width, self.height);
+        let mut buffer = match previous {
+            Some(buffer) if buffer.area == area => buffer,
+            _ => Buffer::empty(area),
+        };
+        for run in &self.runs {
+            buffer.set_stringn(run.x, run.y, &run.text, usize::MAX, run.style);
+        }
+        buffer
+    }
+}
+
+/// A recorded session, read back for replaying.
+#[derive(Debug, Clone)]
+pub struct Session {
+    records: Vec<(Duration, Record)>,
+}
+
+/// A frame from the recording next to the one the component draws when the
+/// session is replayed against it.
+#[derive(Debug, Clone)]
+pub struct ReplayedFrame {
+    /// When the frame was drawn, since the session started.
+    pub at: Duration,
+    /// The frame as the session file has it.
+    pub recorded: Buffer,
+    /// What the component drew at the same point of the replay.
+    pub replayed: Buffer,
+}
+
+impl ReplayedFrame {
+    /// Whether the component drew what was recorded.
+    pub fn matches(&self) -> bool {
+        self.recorded == self.replayed
+    }
+}
+
+impl Session {
+    /// Reads a session file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("read session {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("parse session {}", path.display()))
+    }
+
+    /// Parses the text of a session file.
+    pub fn parse(text: &str) -> Result<Self> {
+        Ok(Self {
+            records: parse(text)?,
+        })
+    }
+
+    /// The recorded frames, rebuilt in full, with when each was drawn.
+    pub fn frames(&self) -> Vec<(Duration, Buffer)> {
+        let mut frames: Vec<(Duration, Buffer)> = Vec::new();
+        for (at, record) in &self.records {
+            if let Record::Frame(diff) = record {
+                let previous = frames.last().map(|(_, buffer)| buffer.clone());
+                frames.push((*at, diff.apply(previous)));
+            }
+        }
+        frames
+    }
+
+    /// Feeds the recorded events and messages to `component` in their
+    /// recorded order, and renders it wherever a frame was recorded — as
+    /// fast as it can, ignoring the recorded pace, so ticks carry the
+    /// recorded elapsed time and the run comes out the same every time.
+    ///
+    /// Messages aren't recorded, only how many arrived when: the replay
+    /// waits for the component's own tasks to send as many again, and
+    /// fails if they don't within a few seconds. Gestures are recognized
+    /// from the mouse events with the default [`GestureConfig`].
+    ///
+    /// Events the app kept for itself — the screenshot key, a toast's
+    /// action keys and clicks — were never recorded, so the component gets
+    /// just what it got live. Ctrl-C and Ctrl-Z went to the component
+    /// before the app acted on them, so they're replayed too.
+    ///
+    /// Only the component is replayed. Frames that showed a toast, the
+    /// progress board, or a text selection won't match.
+    pub async fn replay<C: Component>(&self, mut component: C) -> Result<Vec<ReplayedFrame>> {
+        let (sender, mut messages) = mpsc::channel(256);
+        let context = Context::new(sender);
+        let mut gestures = GestureRecognizer::new(GestureConfig::default());
+        // Gesture timing runs on the recorded clock, not the replay's.
+        let started = Instant::now();
+        let mut frames: Vec<ReplayedFrame> = Vec::new();
+
+        component.init(&context);
+
+        for (at, record) in &self.records {
+            if let Some(gesture) = gestures.poll_long_press(started + *at) {
+                component.handle_event(Event::Gesture(gesture), &context);
+            }
+            match record {
+                Record::Event(event) => {
+                    component.handle_event(event.clone(), &context);
+                    if let Event::Mouse(mouse) = event
+                        && let Some(gesture) = gestures.handle_mouse(mouse, started + *at)
+                    {
+                        component.handle_event(Event::Gesture(gesture), &context);
+                    }
+                }
+                Record::Messages(count) => {
+                    for _ in 0..*count {
+                        match tokio::time::timeout(MESSAGE_TIMEOUT, messages.recv()).await {
+                            Ok(Some(message)) => component.update(message, &context),
+                            _ => bail!(
+                                "replay diverged at {at:?}: the component sent fewer than the \
+                                 {count} recorded messages"
+                            ),
+                        }
+                    }
+                }
+                Record::Frame(diff) => {
+                    let previous = frames.last().map(|frame| frame.recorded.clone());
+                    let recorded = diff.apply(previous);
+                    // The test backend's error type is uninhabited.
+                    let Ok(mut terminal) = Terminal::new(TestBackend::new(diff.width, diff.height));
+                    let Ok(_) = terminal.draw(|frame| component.render(frame, frame.area()));
+                    frames.push(ReplayedFrame {
+                        at: *at,
+                        recorded,
+                        replayed: terminal.backend().buffer().clone(),
+                    });
+                }
+            }
+        }
+        Ok(frames)
+    }
+}
+
+/// A component that plays back recorded frames at the pace they were drawn,
+/// for watching a bug report rather than reading it.
+///
+/// Space pauses and resumes, Left and Right step a frame at a time, Home
+/// goes back to the start, and `q` or Esc quits. The bottom row shows where
+/// playback is. Lower [`AppConfig::tick_rate`](crate::tui::AppConfig#structfield.tick_rate)
+/// for smoother playback: frames advance on ticks.
+pub struct SessionPlayer {
+    frames: Vec<(Duration, Buffer)>,
+    index: usize,
+    /// Playback time since the first frame.
+    clock: Duration,
+    playing: bool,
+}
+
+impl SessionPlayer {
+    /// Plays `frames`, as returned by [`Session::frames`].
+    pub fn new(frames: Vec<(Duration, Buffer)>) -> Self {
+        Self {
+            frames,
+            index: 0,
+            clock: Duration::ZERO,
+            playing: true,
+        }
+    }
+
+    fn seek(&mut self, index: usize) {
+        self.index = index.min(self.frames.len().saturating_sub(1));
+        self.clock = self
+            .frames
+            .get(self.index)
+            .map_or(Duration::ZERO, |(at, _)| *at);
+    }
+}
+
+impl Component for SessionPlayer {
+    type Message = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = area.height.saturating_sub(1);
+        if let Some((at, recorded)) = self.frames.get(self.index) {
+            let width = recorded.area.width.min(area.width);
+            let height = recorded.area.height.min(rows);
+            for y in 0..height {
+                for x in 0..width {
+                    frame.buffer_mut()[(area.x + x, area.y + y)] = recorded[(x, y)].clone();
+                }
+            }
+            let status = format!(
+                " {} frame {}/{} at {:.1}s | Space: pause, ←/→: step, q: quit ",
+                if self.playing { "▶" } else { "⏸" },
+                self.index + 1,
+                self.frames.len(),
+                at.as_secs_f64()
+            );
+            let status_area = Rect::new(area.x, area.y + rows, area.width, area.height.min(1));
+            frame.render_widget(
+                Line::from(status).style(Style::default().add_modifier(Modifier::REVERSED)),
+                status_area,
+            );
+        } else {
+            frame.render_widget(Line::from(" no frames recorded (q: quit)"), area);
+        }
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<()>) -> EventResult {
+        if let Event::Tick(elapsed) = event {
+            if !self.playing {
+                return EventResult::Propagate;
+            }
+            let before = self.index;
+            self.clock += elapsed;
+            while self
+                .frames
+                .get(self.index + 1)
+                .is_some_and(|(at, _)| *at <= self.clock)
+            {
+                self.index += 1;
+            }
+            if self.index + 1 >= self.frames.len() {
+                self.playing = false;
+            }
+            return if self.index != before || !self.playing {
+                EventResult::Consumed
+            } else {
+                EventResult::Propagate
+            };
+        }
+
+        let Some(key) = event.key() else {
+            return EventResult::Propagate;
+        };
+        match key.code {
+            KeyCode::Char(' ') => {
+                if !self.playing && self.index + 1 >= self.frames.len() {
+                    self.seek(0);
+                }
+                self.playing = !self.playing;
+            }
+            KeyCode::Right => {
+                self.playing = false;
+                self.seek(self.index + 1);
+            }
+            KeyCode::Left => {
+                self.playing = false;
+                self.seek(self.index.saturating_sub(1));
+            }
+            KeyCode::Home => self.seek(0),
+            KeyCode::Char('q') | KeyCode::Esc => context.quit(),
+            _ => return EventResult::Propagate,
+        }
+        EventResult::Consumed
+    }
+}
+
+/// Writes a session file as the app runs.
+pub(crate) struct Recorder {
+    out: Box<dyn Write + Send>,
+    started: Instant,
+    /// Messages delivered since the last record, written out lazily so a
+    /// burst of them takes one line.
+    messages: usize,
+    last_frame: Option<Buffer>,
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("create session file {}", path.display()))?;
+        Self::with_writer(Box::new(BufWriter::new(file)))
+    }
+
+    fn with_writer(mut out: Box<dyn Write + Send>) -> Result<Self> {
+        writeln!(out, "{HEADER}").context("record session")?;
+        Ok(Self {
+            out,
+            started: Instant::now(),
+            messages: 0,
+            last_frame: None,
+        })
+    }
+
+    /// Records an event as it reaches the app loop.
+    pub(crate) fn event(&mut self, event: &Event) -> Result<()> {
+        let Some(text) = format_event(event) else {
+            return Ok(());
+        };
+        self.flush_messages()?;
+        self.line(&text)
+    }
+
+    /// Counts a message delivered to the component.
+    pub(crate) fn message(&mut self) {
+        self.messages += 1;
+    }
+
+    /// Records what changed in `buffer` since the last frame. Flushes the
+    /// file, so a crash leaves everything up to the last frame on disk.
+    pub(crate) fn frame(&mut self, buffer: &Buffer) -> Result<()> {
+        // Inline viewports sit lower on the screen; frames start at the top.
+        let mut buffer = buffer.clone();
+        buffer.area.x = 0;
+        buffer.area.y = 0;
+
+        let (previous, resized) = match self.last_frame.take() {
+            Some(previous) if previous.area == buffer.area => (previous, false),
+            _ => (Buffer::empty(buffer.area), true),
+        };
+        let runs = runs(previous.diff(&buffer));
+        if runs.is_empty() && !resized {
+            self.last_frame = Some(buffer);
+            return Ok(());
+        }
+
+        self.flush_messages()?;
+        self.line(&format!(
+            "frame {} {}",
+            buffer.area.width, buffer.area.height
+        ))?;
+        for run in runs {
+            self.line(&format!(
+                "cells {} {} {} {:?}",
+                run.x,
+                run.y,
+                format_style(run.style),
+                run.text
+            ))?;
+        }
+        self.out.flush().context("record session")?;
+        self.last_frame = Some(buffer);
+        Ok(())
+    }
+
+    fn flush_messages(&mut self) -> Result<()> {
+        if self.messages == 0 {
+            return Ok(());
+        }
+        let count = std::mem::take(&mut self.messages);
+        self.line(&format!("messages {count}"))
+    }
+
+    fn line(&mut self, text: &str) -> Result<()> {
+        let millis = self.started.elapsed().as_millis();
+        writeln!(self.out, "{millis} {text}").context("record session")
+    }
+}
+
+/// Groups diff cells into runs of one style along a row.
+fn runs(diff: Vec<(u16, u16, &Cell)>) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    let mut next_x = 0;
+    for (x, y, cell) in diff {
+        let style = Style::default()
+            .fg(cell.fg)
+            .bg(cell.bg)
+            .underline_color(cell.underline_color)
+            .add_modifier(cell.modifier);
+        match runs.last_mut() {
+            Some(run) if run.y == y && run.style == style && x == next_x => {
+                run.text.push_str(cell.symbol());
+            }
+            _ => runs.push(Run {
+                x,
+                y,
+                style,
+                text: cell.symbol().to_string(),
+            }),
+        }
+        next_x = x + Span::raw(cell.symbol()).width().max(1) as u16;
+    }
+    runs
+}
+
+/// Formats a style as `<fg>/<bg>/<underline>/<modifier bits in hex>`.
+fn format_style(style: Style) -> String {
+    let color = |color: Option<Color>| color.unwrap_or_default().to_string();
+    format!(
+        "{}/{}/{}/{:x}",
+        color(style.fg),
+        color(style.bg),
+        color(style.underline_color),
+        style.add_modifier.bits()
+    )
+}
+
+/// Parses what [`format_style`] writes into a style that sets every cell
+/// field, so cells come out exactly as recorded.
+fn parse_style(text: &str) -> Result<Style> {
+    let fields: Vec<&str> = text.split('/').collect();
+    let [fg, bg, underline, modifier] = fields[..] else {
+        bail!("style needs fg/bg/underline/modifier");
+    };
+    let color = |text: &str| {
+        text.parse::<Color>()
+            .with_context(|| format!("bad color {text:?}"))
+    };
+    let modifier =
+        Modifier::from_bits_truncate(u16::from_str_radix(modifier, 16).context("bad modifier")?);
+    Ok(Style::default()
+        .fg(color(fg)?)
+        .bg(color(bg)?)
+        .underline_color(color(underline)?)
+        .add_modifier(modifier)
+        .remove_modifier(Modifier::all() - modifier))
+}
+
+/// Parses a whole session file into timestamped records.
+pub(crate) fn parse(text: &str) -> Result<Vec<(Duration, Record)>> {
+    let mut records = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        parse_line(line, &mut records).with_context(|| format!("session line {}", index + 1))?;
+    }
+    Ok(records)
+}
+
+/// Parses `<millis> <kind> ...` onto `records`. Kinds this version doesn't
+/// know are skipped.
+fn parse_line(line: &str, records: &mut Vec<(Duration, Record)>) -> Result<()> {
+    let (millis, rest) = line.split_once(' ').context("missing record")?;
+    let at = Duration::from_millis(millis.parse().context("bad timestamp")?);
+    let (kind, fields) = rest.split_once(' ').unwrap_or((rest, ""));
+    match kind {
+        "messages" => {
+            let count = fields.parse().context("bad message count")?;
+            records.push((at, Record::Messages(count)));
+        }
+        "frame" => {
+            let (width, height) = fields.split_once(' ').context("frame needs a size")?;
+            let diff = FrameDiff {
+                width: width.parse().context("bad width")?,
+                height: height.parse().context("bad height")?,
+                runs: Vec::new(),
+            };
+            records.push((at, Record::Frame(diff)));
+        }
+        "cells" => {
+            let Some((_, Record::Frame(diff))) = records.last_mut() else {
+                bail!("cells outside a frame");
+            };
+            let mut parts = fields.splitn(4, ' ');
+            let (Some(x), Some(y), Some(style), Some(text)) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                bail!("cells needs a position, a style, and text");
+            };
+            diff.runs.push(Run {
+                x: x.parse().context("bad column")?,
+                y: y.parse().context("bad row")?,
+                style: parse_style(style)?,
+                text: unquote(text, '"')?,
+            });
+        }
+        _ => {
+            if let Some(event) = parse_event(rest)? {
+                records.push((at, Record::Event(event)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Formats an event as it appears after the timestamp, or `None` for events
+/// that aren't recorded: gestures (recognized again from the mouse events
+/// on replay) and keys the format has no name for.
+pub(crate) fn format_event(event: &Event) -> Option<String> {
+    Some(match event {
+        Event::Key(key) => format!(
+            "key {} {}",
+            format_modifiers(key.modifiers),
+            format_key_code(key.code)?
+        ),
+        Event::Mouse(mouse) => format!(
+            "mouse {} {} {} {}",
+            format_mouse_kind(mouse.kind),
+            mouse.column,
+            mouse.row,
+            format_modifiers(mouse.modifiers)
+        ),
+        Event::Paste(text) => format!("paste {text:?}"),
+        Event::Resize(width, height) => format!("resize {width} {height}"),
+        Event::FocusGained => "focus-gained".to_string(),
+        Event::FocusLost => "focus-lost".to_string(),
+        Event::Tick(elapsed) => format!("tick {}", elapsed.as_micros()),
+        Event::Gesture(_) => return None,
+    })
+}
+
+/// Parses what [`format_event`] writes, or `None` if `text` isn't an event.
+fn parse_event(text: &str) -> Result<Option<Event>> {
+    let (kind, rest) = text.split_once(' ').unwrap_or((text, ""));
+    let event = match kind {
+        "key" => {
+            let (modifiers, code) = rest
+                .split_once(' ')
+                .context("key needs modifiers and a code")?;
+            Event::Key(KeyEvent::new(
+                parse_key_code(code)?,
+                parse_modifiers(modifiers)?,
+            ))
+        }
+        "mouse" => {
+            let fields: Vec<&str> = rest.split(' ').collect();
+            let [kind, column, row, modifiers] = fields[..] else {
+                bail!("mouse needs a kind, column, row, and modifiers");
+            };
+            Event::Mouse(MouseEvent {
+                kind: parse_mouse_kind(kind)?,
+                column: column.parse().context("bad column")?,
+                row: row.parse().context("bad row")?,
+                modifiers: parse_modifiers(modifiers)?,
+            })
+        }
+        "paste" => Event::Paste(unquote(rest, '"')?),
+        "resize" => {
+            let (width, height) = rest.split_once(' ').context("resize needs a size")?;
+            Event::Resize(
+                width.parse().context("bad width")?,
+                height.parse().context("bad height")?,
+            )
+        }
+        "focus-gained" => Event::FocusGained,
+        "focus-lost" => Event::FocusLost,
+        "tick" => Event::Tick(Duration::from_micros(
+            rest.parse().context("bad tick duration")?,
+        )),
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+const MODIFIER_NAMES: [(KeyModifiers, &str); 6] = [
+    (KeyModifiers::CONTROL, "ctrl"),
+    (KeyModifiers::ALT, "alt"),
+    (KeyModifiers::SHIFT, "shift"),
+    (KeyModifiers::SUPER, "super"),
+    (KeyModifiers::HYPER, "hyper"),
+    (KeyModifiers::META, "meta"),
+];
+
+fn parse_modifiers(text: &str) -> Result<KeyModifiers> {
+    if text == "-" {
+        return Ok(KeyModifiers::NONE);
+    }
+    text.split('+')
+        .try_fold(KeyModifiers::NONE, |modifiers, name| {
+            let (flag, _) = MODIFIER_NAMES
+                .iter()
+                .find(|(_, known)| *known == name)
+                .with_context(|| format!("unknown modifier {name:?}"))?;
+            Ok(modifiers | *flag)
+        })
+}
+
+fn format_modifiers(modifiers: KeyModifiers) -> String {
+    let names: Vec<&str> = MODIFIER_NAMES
+        .iter()
+        .filter(|(flag, _)| modifiers.contains(*flag))
+        .map(|&(_, name)| name)
+        .collect();
+    if names.is_empty() {
+        "-".to_string()
+    } else {
+        names.join("+")
+    }
+}
+
+const KEY_NAMES: [(KeyCode, &str); 15] = [
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "page-up"),
+    (KeyCode::PageDown, "page-down"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "back-tab"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Esc, "esc"),
+];
+
+fn parse_key_code(text: &str) -> Result<KeyCode> {
+    if text.starts_with('\'') {
+        let text = unquote(text, '\'')?;
+        let mut chars = text.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(KeyCode::Char(c)),
+            _ => bail!("a char key holds exactly one character"),
+        };
+    }
+    if let Some(n) = text.strip_prefix('f')
+        && let Ok(n) = n.parse()
+    {
+        return Ok(KeyCode::F(n));
+    }
+    KEY_NAMES
+        .iter()
+        .find(|(_, name)| *name == text)
+        .map(|&(code, _)| code)
+        .with_context(|| format!("unknown key {text:?}"))
+}
+
+fn format_key_code(code: KeyCode) -> Option<String> {
+    match code {
+        KeyCode::Char(c) => Some(format!("{c:?}")),
+        KeyCode::F(n) => Some(format!("f{n}")),
+        _ => KEY_NAMES
+            .iter()
+            .find(|(known, _)| *known == code)
+            .map(|(_, name)| name.to_string()),
+    }
+}
+
+fn parse_mouse_kind(text: &str) -> Result<MouseEventKind> {
+    let button = |name: &str| match name {
+        "left" => Ok(MouseButton::Left),
+        "right" => Ok(MouseButton::Right),
+        "middle" => Ok(MouseButton::Middle),
+        _ => bail!("unknown mouse button {name:?}"),
+    };
+    Ok(match text.split_once('-') {
+        Some(("down", name)) => MouseEventKind::Down(button(name)?),
+        Some(("up", name)) => MouseEventKind::Up(button(name)?),
+        Some(("drag", name)) => MouseEventKind::Drag(button(name)?),
+        _ => match text {
+            "moved" => MouseEventKind::Moved,
+            "scroll-down" => MouseEventKind::ScrollDown,
+            "scroll-up" => MouseEventKind::ScrollUp,
+            "scroll-left" => MouseEventKind::ScrollLeft,
+            "scroll-right" => MouseEventKind::ScrollRight,
+            _ => bail!("unknown mouse event {text:?}"),
+        },
+    })
+}
+
+fn format_mouse_kind(kind: MouseEventKind) -> String {
+    let button = |button: MouseButton| match button {
+        MouseButton::Left => "left",
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+    };
+    match kind {
+        MouseEventKind::Down(b) => format!("down-{}", button(b)),
+        MouseEventKind::Up(b) => format!("up-{}", button(b)),
+        MouseEventKind::Drag(b) => format!("drag-{}", button(b)),
+        MouseEventKind::Moved => "moved".to_string(),
+        MouseEventKind::ScrollDown => "scroll-down".to_string(),
+        MouseEventKind::ScrollUp => "scroll-up".to_string(),
+        MouseEventKind::ScrollLeft => "scroll-left".to_string(),
+        MouseEventKind::ScrollRight => "scroll-right".to_string(),
+    }
+}
+
+/// Reverses Rust's `{:?}` quoting of a string or char: `quote` at both ends,
+/// with `\n`, `\t`, `\r`, `\0`, `\\`, `\'`, `\"`, and `\u{..}` escapes.
+fn unquote(text: &str, quote: char) -> Result<String> {
+    let inner = text
+        .strip_prefix(quote)
+        .and_then(|text| text.strip_suffix(quote))
+        .with_context(|| format!("expected {quote}-quoted text"))?;
+
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some(c @ ('\\' | '\'' | '"')) => c,
+            Some('u') => {
+                let hex: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let hex = hex.strip_prefix('{').context("bad unicode escape")?;
+                char::from_u32(u32::from_str_radix(hex, 16).context("bad unicode escape")?)
+                    .context("bad unicode escape")?
+            }
+            _ => bail!("bad escape in {text:?}"),
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Record, Recorder, Session, format_event, parse};
+    use crate::tui::component::{Component, Context};
+    use crate::tui::event::Event;
+    use crate::tui::event::EventResult;
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::Frame;
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Modifier, Style};
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn events_survive_a_round_trip() {
+        let events = [
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Event::key_press(KeyCode::Char(' ')),
+            Event::key_press(KeyCode::Char('\'')),
+            Event::key_press(KeyCode::F(5)),
+            Event::Key(KeyEvent::new(
+                KeyCode::PageDown,
+                KeyModifiers::SHIFT | KeyModifiers::ALT,
+            )),
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Middle),
+                column: 12,
+                row: 4,
+                modifiers: KeyModifiers::NONE,
+            }),
+            Event::Paste("two\n\"lines\" — ok\t".to_string()),
+            Event::Resize(80, 24),
+            Event::FocusLost,
+            Event::Tick(Duration::from_micros(250_123)),
+        ];
+
+        let text: String = events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| format!("{} {}\n", index * 10, format_event(event).unwrap()))
+            .collect();
+        let parsed = parse(&text).unwrap();
+
+        assert_eq!(parsed.len(), events.len());
+        for (index, ((at, parsed), event)) in parsed.iter().zip(&events).enumerate() {
+            assert_eq!(*at, Duration::from_millis(index as u64 * 10));
+            let Record::Event(parsed) = parsed else {
+                panic!("expected an event, got {parsed:?}");
+            };
+            assert_eq!(format!("{parsed:?}"), format!("{event:?}"));
+        }
+    }
+
+    #[test]
+    fn comments_and_unknown_records_are_skipped_but_bad_events_are_errors() {
+        let text = "# header\n\n5 sparkle 0 0 something\n10 key - enter\n";
+        let parsed = parse(text).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert!(matches!(&parsed[0].1, Record::Event(event) if event.is_key(KeyCode::Enter)));
+
+        let error = parse("10 key - 'ab'\n").unwrap_err();
+        assert_eq!(error.to_string(), "session line 1");
+    }
+
+    /// Collects what a recorder writes.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Shared {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn recorded_frames_are_rebuilt_exactly() {
+        let out = Shared::default();
+        let mut recorder = Recorder::with_writer(Box::new(out.clone())).unwrap();
+
+        let mut first = Buffer::empty(Rect::new(0, 5, 8, 2));
+        first.set_string(0, 5, "héllo", Style::default().fg(Color::Indexed(42)));
+        first.set_string(1, 6, "日本", Style::default().add_modifier(Modifier::BOLD));
+        let mut second = first.clone();
+        second.set_string(1, 5, "a", Style::default().bg(Color::Rgb(1, 2, 3)));
+        let third = Buffer::empty(Rect::new(0, 0, 3, 1));
+
+        for buffer in [&first, &second, &second, &third] {
+            recorder.frame(buffer).unwrap();
+        }
+        drop(recorder);
+
+        let frames = Session::parse(&out.text()).unwrap().frames();
+        // The unchanged frame isn't recorded again.
+        assert_eq!(frames.len(), 3);
+        for ((_, rebuilt), mut expected) in frames.into_iter().zip([first, second, third]) {
+            expected.area.y = 0;
+            assert_eq!(rebuilt, expected);
+        }
+    }
+
+    /// Counts keys through a message, the way a component hands work to
+    /// `update`.
+    #[derive(Default)]
+    struct Counter(usize);
+
+    impl Component for Counter {
+        type Message = usize;
+
+        fn render(&mut self, frame: &mut Frame, area: Rect) {
+            frame.render_widget(format!("count {}", self.0), area);
+        }
+
+        fn handle_event(&mut self, event: Event, context: &Context<usize>) -> EventResult {
+            if event.key().is_none() {
+                return EventResult::Propagate;
+            }
+            let _ = context.try_send(1);
+            EventResult::Consumed
+        }
+
+        fn update(&mut self, step: usize, _context: &Context<usize>) {
+            self.0 += step;
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_match_the_recording_until_the_component_changes() {
+        let text = "\
+0 frame 10 1
+0 cells 0 0 Reset/Reset/Reset/0 \"count 0\"
+10 key - 'a'
+12 key - 'b'
+15 messages 2
+15 frame 10 1
+15 cells 6 0 Reset/Reset/Reset/0 \"2\"
+";
+        let session = Session::parse(text).unwrap();
+
+        let frames = session.replay(Counter::default()).await.unwrap();
+        assert_eq!(frames.len(), 2);
+        assert!(frames.iter().all(|frame| frame.matches()));
+
+        let frames = session.replay(Counter(1)).await.unwrap();
+        assert!(!frames[0].matches());
+        assert_eq!(frames[1].at, Duration::from_millis(15));
+    }
+}
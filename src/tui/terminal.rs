@@ -1,6 +1,7 @@
 //! Terminal setup and RAII cleanup.
 
-use anyhow::{Context, Result};
+use crate::tui::cast::CastRecorder;
+use anyhow::{Context, Result, bail};
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
     event::{
@@ -8,17 +9,31 @@ use crossterm::{
         EnableFocusChange, EnableMouseCapture,
     },
     execute,
+    style::Print,
     terminal::{
-        Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
-        enable_raw_mode,
+        self as crossterm_terminal, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen,
+        disable_raw_mode, enable_raw_mode,
     },
 };
+use ratatui::layout::{Rect, Size};
 use ratatui::{Terminal, TerminalOptions, backend::CrosstermBackend};
-use std::io::{self, Stdout};
-use std::sync::Once;
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, Once};
+
+/// The Ratatui backend [`TerminalGuard`] draws with.
+pub type TerminalBackend = CrosstermBackend<TerminalWriter>;
 
 /// The concrete Ratatui terminal type used by this template.
-pub type TerminalType = Terminal<CrosstermBackend<Stdout>>;
+pub type TerminalType = Terminal<TerminalBackend>;
+
+/// The controlling terminal, opened directly.
+const TTY_PATH: &str = if cfg!(windows) { "CONOUT$" } else { "/dev/tty" };
+
+/// Where the terminal was last taken over, so the panic hook restores the
+/// same stream.
+static ACTIVE_OUTPUT: Mutex<OutputTarget> = Mutex::new(OutputTarget::Stdout);
 
 /// Where the UI draws.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -30,16 +45,119 @@ pub enum Viewport {
     /// Draw in `height` rows of the normal scrollback at the cursor position,
     /// like a progress display. No alternate screen: output printed before
     /// the app ran stays visible, and the UI's final frame stays in the
-    /// scrollback after exit.
+    /// scrollback after exit. The height can change while the app runs, with
+    /// [`Context::set_inline_height`](crate::tui::Context::set_inline_height).
     ///
     /// Setup locates the viewport by querying the cursor position through
-    /// stdin, so inline apps need a real interactive terminal (not a pipe).
+    /// the terminal, so inline apps need a real interactive terminal (piped,
+    /// they fall back to [`AppConfig::non_tty`](crate::tui::AppConfig::non_tty)).
+    /// The query always goes through stdout, even with another
+    /// [`OutputTarget`].
     Inline(u16),
+    /// Draw into a fixed region of the screen, in screen coordinates, with
+    /// no alternate screen — for a tool that runs in part of a multiplexer
+    /// pane, or next to output you lay out yourself. The region is cleared
+    /// on start and keeps its size when the terminal resizes.
+    Fixed(Rect),
+    /// Reserve the bottom `height` rows of the screen for the UI, with no
+    /// alternate screen: what is already on screen scrolls up just enough
+    /// to make room, and the panel follows the bottom edge when the terminal
+    /// resizes. Unlike [`Viewport::Inline`], setup needs no cursor query.
+    BottomPanel(u16),
+}
+
+/// The stream the UI is drawn on.
+///
+/// Drawing somewhere other than stdout leaves stdout free for a result, so
+/// a picker works inside shell substitution, like fzf:
+/// `cd "$(my-picker)"`. The shell captures stdout while the UI goes to the
+/// terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputTarget {
+    /// Standard output (the default).
+    #[default]
+    Stdout,
+    /// Standard error, which shell substitution and `>` redirects leave on
+    /// the terminal.
+    Stderr,
+    /// The controlling terminal (`/dev/tty`, or the console on Windows),
+    /// opened directly — works even when both stdout and stderr are
+    /// redirected.
+    Tty,
+}
+
+impl OutputTarget {
+    /// Whether there is a terminal to take over through this output: it is a
+    /// terminal, and keys can be read — from stdin, or on Unix from the
+    /// controlling terminal when stdin is redirected, as in
+    /// `data | my-picker`.
+    pub fn is_interactive(self) -> bool {
+        let output = match self {
+            Self::Stdout => io::stdout().is_terminal(),
+            Self::Stderr => io::stderr().is_terminal(),
+            Self::Tty => open_tty().is_ok(),
+        };
+        output && (io::stdin().is_terminal() || cfg!(unix) && File::open("/dev/tty").is_ok())
+    }
+
+    fn writer(self) -> io::Result<TerminalWriter> {
+        let inner: Box<dyn Write + Send> = match self {
+            Self::Stdout => Box::new(io::stdout()),
+            Self::Stderr => Box::new(io::stderr()),
+            Self::Tty => Box::new(open_tty()?),
+        };
+        Ok(TerminalWriter { inner, cast: None })
+    }
+}
+
+/// The stream behind [`TerminalType`]: whichever [`OutputTarget`] the
+/// terminal was configured with.
+pub struct TerminalWriter {
+    inner: Box<dyn Write + Send>,
+    /// Gets a copy of everything written while a recording is running.
+    cast: Option<CastSlot>,
+}
+
+/// Where a [`TerminalGuard`] and its writers share the cast recording, so it
+/// can start after the terminal is built and outlive rebuilds.
+type CastSlot = Arc<Mutex<Option<CastRecorder>>>;
+
+impl Write for TerminalWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if let Some(slot) = &self.cast
+            && let Some(cast) = &mut *lock(slot)
+        {
+            cast.output(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        if let Some(slot) = &self.cast
+            && let Some(cast) = &mut *lock(slot)
+        {
+            cast.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Locks `mutex`, carrying on past a panic in another holder: terminal
+/// state must stay reachable for cleanup.
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn open_tty() -> io::Result<File> {
+    OpenOptions::new().read(true).write(true).open(TTY_PATH)
 }
 
 /// Optional terminal features. Mouse capture and focus change are off by
 /// default because they alter normal terminal behavior (for example, mouse
-/// capture breaks native text selection).
+/// capture breaks native text selection — `AppConfig::text_selection` brings
+/// it back inside the app).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TerminalConfig {
     /// Receive [`Event::Mouse`](crate::tui::Event::Mouse) events.
@@ -50,8 +168,12 @@ pub struct TerminalConfig {
     /// Receive [`Event::FocusGained`](crate::tui::Event::FocusGained) and
     /// [`Event::FocusLost`](crate::tui::Event::FocusLost) events.
     pub focus_change: bool,
-    /// Draw fullscreen (default) or inline in the scrollback.
+    /// Draw fullscreen (default), inline in the scrollback, or in a region
+    /// of the screen.
     pub viewport: Viewport,
+    /// The stream to draw on: stdout (default), stderr, or the controlling
+    /// terminal.
+    pub output: OutputTarget,
 }
 
 impl Default for TerminalConfig {
@@ -61,10 +183,24 @@ impl Default for TerminalConfig {
             bracketed_paste: true,
             focus_change: false,
             viewport: Viewport::Fullscreen,
+            output: OutputTarget::Stdout,
         }
     }
 }
 
+/// Whether there is a terminal to take over through stdout: stdout is a
+/// terminal, and keys can be read — from stdin, or on Unix from the
+/// controlling terminal (`/dev/tty`) when stdin is redirected, as in
+/// `data | my-picker`.
+///
+/// False in CI logs, under `| tee`, and in cron jobs. [`App`](crate::tui::App)
+/// checks the configured output the same way (see
+/// [`OutputTarget::is_interactive`]) to choose between the terminal and plain
+/// output (see [`AppConfig::non_tty`](crate::tui::AppConfig::non_tty)).
+pub fn is_interactive() -> bool {
+    OutputTarget::Stdout.is_interactive()
+}
+
 /// Puts the terminal into raw mode (and, for fullscreen apps, the alternate
 /// screen) on construction and restores it on drop — including during
 /// unwinding, and via a panic hook so panic messages print to a sane terminal
@@ -72,6 +208,7 @@ impl Default for TerminalConfig {
 pub struct TerminalGuard {
     terminal: TerminalType,
     config: TerminalConfig,
+    cast: CastSlot,
 }
 
 impl TerminalGuard {
@@ -81,26 +218,48 @@ impl TerminalGuard {
     }
 
     /// Takes over the terminal with the given feature set.
+    ///
+    /// Fails straight away, rather than hanging on terminal queries, when
+    /// there is no terminal to take over (see
+    /// [`OutputTarget::is_interactive`]).
     pub fn with_config(config: TerminalConfig) -> Result<Self> {
+        if !config.output.is_interactive() {
+            bail!("not running in an interactive terminal (output or input is redirected)");
+        }
+        // Crossterm sends the cursor query an inline viewport starts with to
+        // stdout, whatever the output.
+        if matches!(config.viewport, Viewport::Inline(_)) && !io::stdout().is_terminal() {
+            bail!("inline viewports need stdout to be a terminal; use a bottom panel instead");
+        }
+
         install_panic_hook();
+        *lock(&ACTIVE_OUTPUT) = config.output;
 
         enable_raw_mode().context("enable terminal raw mode")?;
 
-        let mut stdout = io::stdout();
-        if let Err(error) = Self::enter_terminal(&mut stdout, config) {
-            restore_terminal();
+        let entered = config
+            .output
+            .writer()
+            .and_then(|writer| Self::enter_terminal(writer, config));
+        if let Err(error) = entered {
+            restore_terminal(config.output);
             return Err(error).context("enter terminal");
         }
 
-        let terminal = match Self::build_terminal(config) {
+        let cast = CastSlot::default();
+        let terminal = match Self::build_terminal(config, &cast) {
             Ok(terminal) => terminal,
             Err(error) => {
-                restore_terminal();
+                restore_terminal(config.output);
                 return Err(error);
             }
         };
 
-        Ok(Self { terminal, config })
+        Ok(Self {
+            terminal,
+            config,
+            cast,
+        })
     }
 
     /// Access the underlying Ratatui terminal.
@@ -123,30 +282,109 @@ impl TerminalGuard {
     /// forces a full repaint on the next draw.
     pub fn resume(&mut self) -> Result<()> {
         enable_raw_mode().context("re-enable terminal raw mode")?;
-        Self::enter_terminal(&mut io::stdout(), self.config).context("re-enter terminal")?;
+        self.config
+            .output
+            .writer()
+            .and_then(|writer| Self::enter_terminal(writer, self.config))
+            .context("re-enter terminal")?;
 
         // Rebuild rather than reuse the ratatui terminal: this re-anchors an
         // inline viewport at the current cursor position (whatever ran while
         // suspended has scrolled the screen) and starts from empty buffers so
         // the next draw repaints everything.
-        self.terminal = Self::build_terminal(self.config)?;
+        self.terminal = Self::build_terminal(self.config, &self.cast)?;
+        Ok(())
+    }
+
+    /// Records everything drawn from now on to `path` as an asciicast v2
+    /// `.cast` file, for `asciinema play` or a demo on a web page. Replaces
+    /// any recording already running.
+    pub fn record_cast(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let (width, height) = crossterm_terminal::size().context("read terminal size")?;
+        let cast = CastRecorder::create(path.as_ref(), width, height)?;
+        *lock(&self.cast) = Some(cast);
+        Ok(())
+    }
+
+    /// Changes the height of an inline viewport, keeping its top row where
+    /// it is: growing scrolls the terminal up to make room, shrinking clears
+    /// the rows left behind. The next draw repaints everything, and
+    /// [`TerminalGuard::resume`] keeps the new height. Does nothing for
+    /// other viewports.
+    ///
+    /// Inside the app loop, use
+    /// [`Context::set_inline_height`](crate::tui::Context::set_inline_height).
+    pub fn set_inline_height(&mut self, height: u16) -> Result<()> {
+        let Viewport::Inline(current) = self.config.viewport else {
+            return Ok(());
+        };
+        if height == current {
+            return Ok(());
+        }
+
+        let top = self.terminal.get_frame().area().top();
+        execute!(
+            self.terminal.backend_mut(),
+            MoveTo(0, top),
+            Clear(ClearType::FromCursorDown)
+        )
+        .context("clear inline viewport")?;
+
+        // Like `resume`, rebuild to re-anchor: the new terminal claims
+        // `height` rows from the cursor, scrolling if they don't fit.
+        self.config.viewport = Viewport::Inline(height);
+        self.terminal = Self::build_terminal(self.config, &self.cast)?;
         Ok(())
     }
 
-    fn build_terminal(config: TerminalConfig) -> Result<TerminalType> {
+    /// Moves a bottom panel to the new bottom edge after the terminal
+    /// resizes. Fullscreen and inline viewports follow resizes on their own
+    /// and fixed regions stay put, so for them this does nothing.
+    pub(crate) fn handle_resize(&mut self, width: u16, height: u16) -> Result<()> {
+        if let Some(cast) = &mut *lock(&self.cast) {
+            cast.resize(width, height).context("record cast")?;
+        }
+        if let Viewport::BottomPanel(rows) = self.config.viewport {
+            self.terminal
+                .resize(bottom_panel_area(rows, Size::new(width, height)))
+                .context("move bottom panel")?;
+        }
+        Ok(())
+    }
+
+    fn build_terminal(config: TerminalConfig, cast: &CastSlot) -> Result<TerminalType> {
         let viewport = match config.viewport {
             Viewport::Fullscreen => ratatui::Viewport::Fullscreen,
             Viewport::Inline(height) => ratatui::Viewport::Inline(height),
+            Viewport::Fixed(area) => ratatui::Viewport::Fixed(area),
+            Viewport::BottomPanel(height) => {
+                let (width, rows) = crossterm_terminal::size().context("read terminal size")?;
+                ratatui::Viewport::Fixed(bottom_panel_area(height, Size::new(width, rows)))
+            }
         };
 
-        Terminal::with_options(
-            CrosstermBackend::new(io::stdout()),
-            TerminalOptions { viewport },
+        let mut terminal = Terminal::with_options(
+            CrosstermBackend::new(TerminalWriter {
+                cast: Some(cast.clone()),
+                ..config.output.writer().context("open terminal output")?
+            }),
+            TerminalOptions {
+                viewport: viewport.clone(),
+            },
         )
-        .context("create ratatui terminal")
+        .context("create ratatui terminal")?;
+
+        // A fixed region starts out holding whatever was on screen, which
+        // would show through blank cells of the first frame. `resize` clears
+        // it without the cursor query `Terminal::clear` makes.
+        if let ratatui::Viewport::Fixed(area) = viewport {
+            terminal.resize(area).context("clear fixed viewport")?;
+        }
+
+        Ok(terminal)
     }
 
-    fn enter_terminal(mut stdout: impl io::Write, config: TerminalConfig) -> io::Result<()> {
+    fn enter_terminal(mut stdout: impl Write, config: TerminalConfig) -> io::Result<()> {
         match config.viewport {
             // Clear with a plain escape code rather than `Terminal::clear`,
             // which round-trips a cursor-position query through stdin and
@@ -157,7 +395,13 @@ impl TerminalGuard {
             }
             // Inline draws into the normal scrollback: no alternate screen,
             // no whole-screen clear. The cursor stays hidden between draws.
-            Viewport::Inline(_) => execute!(stdout, Hide)?,
+            Viewport::Inline(_) | Viewport::Fixed(_) => execute!(stdout, Hide)?,
+            // Newlines from the cursor scroll the screen only as far as
+            // needed to leave the bottom rows free (raw mode makes each a
+            // plain line feed).
+            Viewport::BottomPanel(height) => {
+                execute!(stdout, Hide, Print("\n".repeat(height.into())))?;
+            }
         }
 
         if config.mouse_capture {
@@ -175,22 +419,26 @@ impl TerminalGuard {
         Ok(())
     }
 
-    /// Restores the terminal for the shell. In inline mode the UI stays in
-    /// the scrollback, so first park the cursor on the viewport's last line
-    /// and finish with a newline — the next prompt starts below the UI
+    /// Restores the terminal for the shell. Without the alternate screen the
+    /// UI stays on screen, so first park the cursor on the viewport's last
+    /// line and finish with a newline — the next prompt starts below the UI
     /// instead of overwriting it.
     fn hand_back_terminal(&mut self) {
-        let inline = matches!(self.config.viewport, Viewport::Inline(_));
+        let inline = self.config.viewport != Viewport::Fullscreen;
 
         if inline {
             let area = self.terminal.get_frame().area();
-            let _ = execute!(io::stdout(), MoveTo(0, area.bottom().saturating_sub(1)));
+            let _ = execute!(
+                self.terminal.backend_mut(),
+                MoveTo(0, area.bottom().saturating_sub(1))
+            );
         }
 
-        restore_terminal();
+        restore_terminal(self.config.output);
 
         if inline {
-            println!();
+            let _ = writeln!(self.terminal.backend_mut());
+            let _ = self.terminal.backend_mut().flush();
         }
     }
 }
@@ -203,10 +451,13 @@ impl Drop for TerminalGuard {
 
 /// Undoes everything [`TerminalGuard`] set up. Safe to call more than once;
 /// terminals ignore the disable sequences when the feature is not active.
-fn restore_terminal() {
-    let mut stdout = io::stdout();
+fn restore_terminal(output: OutputTarget) {
+    let Ok(mut writer) = output.writer() else {
+        let _ = disable_raw_mode();
+        return;
+    };
     let _ = execute!(
-        stdout,
+        writer,
         Show,
         DisableFocusChange,
         DisableBracketedPaste,
@@ -225,8 +476,33 @@ fn install_panic_hook() {
     HOOK.call_once(|| {
         let original = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |info| {
-            restore_terminal();
+            restore_terminal(*lock(&ACTIVE_OUTPUT));
             original(info);
         }));
     });
 }
+
+/// The bottom `height` rows of a screen of `size`, or all of it if shorter.
+fn bottom_panel_area(height: u16, size: Size) -> Rect {
+    let height = height.min(size.height);
+    Rect::new(0, size.height - height, size.width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bottom_panel_area;
+    use ratatui::layout::{Rect, Size};
+
+    #[test]
+    fn bottom_panel_sits_on_the_last_rows() {
+        assert_eq!(
+            bottom_panel_area(3, Size::new(80, 24)),
+            Rect::new(0, 21, 80, 3)
+        );
+        assert_eq!(
+            bottom_panel_area(30, Size::new(80, 24)),
+            Rect::new(0, 0, 80, 24),
+            "never taller than the screen"
+        );
+    }
+}
@@ -0,0 +1,462 @@
+//! Toast notifications drawn over the component's frame.
+//!
+//! [`Context::notify`](crate::tui::Context::notify) queues a toast; the app
+//! loop stacks toasts in a corner on top of whatever the component drew and
+//! removes each one once its time is up, counted from the elapsed time in
+//! [`Event::Tick`] so a busy UI doesn't cut them short. The newest toast's
+//! action keys (see [`Toast::action`]) are checked before the component sees
+//! the key, and clicking a toast dismisses it.
+
+use crate::tui::event::Event;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Widget};
+use std::fmt;
+use std::time::Duration;
+
+/// The most lines of text a toast shows before cutting off with `…`.
+const MAX_LINES: usize = 4;
+
+/// How a toast is colored and marked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    fn color(self) -> Color {
+        match self {
+            Self::Info => Color::Cyan,
+            Self::Success => Color::Green,
+            Self::Warning => Color::Yellow,
+            Self::Error => Color::Red,
+        }
+    }
+
+    pub(crate) fn icon(self) -> &'static str {
+        match self {
+            Self::Info => "ℹ",
+            Self::Success => "✓",
+            Self::Warning => "⚠",
+            Self::Error => "✗",
+        }
+    }
+}
+
+/// The corner toasts stack in; the newest sits closest to it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToastCorner {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+/// Where toasts appear and how long they stay. Part of
+/// [`AppConfig`](crate::tui::AppConfig).
+#[derive(Debug, Clone, Copy)]
+pub struct ToastConfig {
+    /// The corner to stack in.
+    pub corner: ToastCorner,
+    /// How long a toast stays unless it sets its own
+    /// [`duration`](Toast::duration).
+    pub duration: Duration,
+    /// The most toasts shown at once; older ones are dropped.
+    pub max_visible: usize,
+    /// Toast width in columns, including the border.
+    pub width: u16,
+}
+
+impl Default for ToastConfig {
+    fn default() -> Self {
+        Self {
+            corner: ToastCorner::default(),
+            duration: Duration::from_secs(4),
+            max_visible: 4,
+            width: 40,
+        }
+    }
+}
+
+/// A toast with options beyond [`Context::notify`]: its own duration and
+/// action keys. Show it with [`Context::toast`].
+///
+/// ```ignore
+/// context.toast(
+///     Toast::new(ToastLevel::Info, "Deleted 3 files")
+///         .action(KeyCode::Char('u'), "Undo", Msg::Undo)
+///         .duration(Duration::from_secs(8)),
+/// );
+/// ```
+///
+/// [`Context::notify`]: crate::tui::Context::notify
+/// [`Context::toast`]: crate::tui::Context::toast
+pub struct Toast<M> {
+    pub(crate) level: ToastLevel,
+    pub(crate) text: String,
+    pub(crate) duration: Option<Duration>,
+    pub(crate) actions: Vec<(KeyCode, String, M)>,
+}
+
+impl<M> Toast<M> {
+    /// Creates a toast showing `text`.
+    pub fn new(level: ToastLevel, text: impl Into<String>) -> Self {
+        Self {
+            level,
+            text: text.into(),
+            duration: None,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Keeps the toast up for `duration` instead of the configured default.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// While this is the newest toast, pressing `key` sends `message` to
+    /// `update` and dismisses the toast. The key is taken before the
+    /// component sees it, so prefer keys your screens don't use while a
+    /// toast is likely. Only a plain press of `key` counts (Shift aside,
+    /// for capitals): Ctrl-C still quits while a `'c'` action is showing.
+    pub fn action(mut self, key: KeyCode, label: impl Into<String>, message: M) -> Self {
+        self.actions.push((key, label.into(), message));
+        self
+    }
+}
+
+/// An action whose message has been bound to the app's channel.
+pub(crate) struct Action {
+    pub(crate) key: KeyCode,
+    pub(crate) label: String,
+    pub(crate) run: Box<dyn FnOnce() + Send>,
+}
+
+impl fmt::Debug for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Action")
+            .field("key", &self.key)
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+
+// Compares what the user sees; the callbacks can't be compared.
+impl PartialEq for Action {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.label == other.label
+    }
+}
+
+impl Eq for Action {}
+
+/// A toast on its way from a [`Context`](crate::tui::Context) to the app
+/// loop, its messages already bound.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Notification {
+    pub(crate) level: ToastLevel,
+    pub(crate) text: String,
+    pub(crate) duration: Option<Duration>,
+    pub(crate) actions: Vec<Action>,
+}
+
+#[derive(Debug)]
+struct Shown {
+    notification: Notification,
+    remaining: Duration,
+    /// Where it was last drawn, for clicks.
+    area: Rect,
+}
+
+/// The toasts on screen, oldest first.
+#[derive(Debug)]
+pub(crate) struct Toasts {
+    config: ToastConfig,
+    shown: Vec<Shown>,
+}
+
+impl Toasts {
+    pub(crate) fn new(config: ToastConfig) -> Self {
+        Self {
+            config,
+            shown: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, notification: Notification) {
+        let remaining = notification.duration.unwrap_or(self.config.duration);
+        self.shown.push(Shown {
+            notification,
+            remaining,
+            area: Rect::default(),
+        });
+        let excess = self
+            .shown
+            .len()
+            .saturating_sub(self.config.max_visible.max(1));
+        self.shown.drain(..excess);
+    }
+
+    /// Ages every toast by `elapsed`. Returns `true` if any expired and the
+    /// frame needs a redraw.
+    pub(crate) fn advance(&mut self, elapsed: Duration) -> bool {
+        let before = self.shown.len();
+        self.shown.retain_mut(|toast| {
+            toast.remaining = toast.remaining.saturating_sub(elapsed);
+            !toast.remaining.is_zero()
+        });
+        self.shown.len() != before
+    }
+
+    /// Runs the newest toast's action for a matching key, or dismisses the
+    /// toast under a click. Returns `true` if the event was used.
+    pub(crate) fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Key(_) => {
+                let Some(newest) = self.shown.last() else {
+                    return false;
+                };
+                let Some(index) = newest.notification.actions.iter().position(|action| {
+                    event.is_key(action.key)
+                        && event
+                            .key()
+                            .is_some_and(|key| (key.modifiers - KeyModifiers::SHIFT).is_empty())
+                }) else {
+                    return false;
+                };
+                let mut toast = self.shown.pop().expect("newest toast exists");
+                (toast.notification.actions.swap_remove(index).run)();
+                true
+            }
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(mouse.column, mouse.row);
+                let Some(index) = self
+                    .shown
+                    .iter()
+                    .position(|toast| toast.area.contains(position))
+                else {
+                    return false;
+                };
+                self.shown.remove(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Draws the stack into `area`'s configured corner, newest nearest the
+    /// corner, for as many as fit.
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let width = self.config.width.min(area.width);
+        if width < 5 {
+            return;
+        }
+        let x = match self.config.corner {
+            ToastCorner::TopLeft | ToastCorner::BottomLeft => area.x,
+            ToastCorner::TopRight | ToastCorner::BottomRight => area.right() - width,
+        };
+        let from_top = matches!(
+            self.config.corner,
+            ToastCorner::TopLeft | ToastCorner::TopRight
+        );
+
+        let mut used = 0;
+        let newest = self.shown.len().saturating_sub(1);
+        for (index, toast) in self.shown.iter_mut().enumerate().rev() {
+            let lines = toast_lines(&toast.notification, width - 2, index == newest);
+            let height = lines.len() as u16 + 2;
+            if used + height > area.height {
+                toast.area = Rect::default();
+                continue;
+            }
+            let y = if from_top {
+                area.y + used
+            } else {
+                area.bottom() - used - height
+            };
+            used += height;
+
+            toast.area = Rect::new(x, y, width, height);
+            Clear.render(toast.area, buf);
+            let block = Block::bordered()
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(toast.notification.level.color()));
+            let inner = block.inner(toast.area);
+            block.render(toast.area, buf);
+            for (row, line) in lines.into_iter().enumerate() {
+                line.render(
+                    Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                    buf,
+                );
+            }
+        }
+    }
+}
+
+/// The toast's text wrapped to `width`, plus its action hints when it is
+/// the newest.
+fn toast_lines(notification: &Notification, width: u16, newest: bool) -> Vec<Line<'static>> {
+    let level = notification.level;
+    let text = format!("{} {}", level.icon(), notification.text);
+    let mut lines: Vec<Line> = wrap(&text, width as usize)
+        .into_iter()
+        .map(Line::raw)
+        .collect();
+    if let Some(first) = lines.first_mut() {
+        first.spans[0].style = Style::default().fg(level.color());
+    }
+
+    if newest && !notification.actions.is_empty() {
+        let hints: Vec<Span> = notification
+            .actions
+            .iter()
+            .flat_map(|action| {
+                [
+                    Span::styled(
+                        format!("[{}]", action.key),
+                        Style::default().fg(level.color()),
+                    ),
+                    Span::styled(
+                        format!(" {}  ", action.label),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]
+            })
+            .collect();
+        lines.push(Line::from(hints));
+    }
+    lines
+}
+
+/// Greedy word wrap by display width, at most [`MAX_LINES`] lines. Words
+/// longer than a line are cut.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if Span::raw(candidate.as_str()).width() <= width || current.is_empty() {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > MAX_LINES {
+        lines.truncate(MAX_LINES);
+        lines[MAX_LINES - 1].push('…');
+    }
+    lines
+        .into_iter()
+        .map(|line| {
+            // Cut lines that are still too wide (a single long word).
+            let mut cut = String::new();
+            for c in line.chars() {
+                if Span::raw(format!("{cut}{c}")).width() > width {
+                    break;
+                }
+                cut.push(c);
+            }
+            cut
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ToastConfig, ToastCorner, ToastLevel, Toasts};
+    use crate::tui::component::{Context, Request};
+    use crate::tui::event::Event;
+    use crate::tui::toast::Toast;
+    use crossterm::event::KeyCode;
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::{Position, Rect};
+    use std::time::Duration;
+
+    /// Moves everything the context queued onto the stack.
+    fn collect<M>(context: &Context<M>, toasts: &mut Toasts) {
+        for request in context.take_requests() {
+            if let Request::Notify(notification) = request {
+                toasts.push(notification);
+            }
+        }
+    }
+
+    fn row(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer[Position::new(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn expires_by_elapsed_time_and_drops_the_oldest_beyond_the_limit() {
+        let (context, _messages) = Context::<()>::test();
+        let mut toasts = Toasts::new(ToastConfig {
+            max_visible: 2,
+            ..ToastConfig::default()
+        });
+
+        context.notify(ToastLevel::Info, "one");
+        context.toast(Toast::new(ToastLevel::Info, "two").duration(Duration::from_secs(10)));
+        context.notify(ToastLevel::Info, "three");
+        collect(&context, &mut toasts);
+        assert_eq!(toasts.shown.len(), 2, "the oldest made room");
+
+        assert!(!toasts.advance(Duration::from_secs(3)));
+        assert!(toasts.advance(Duration::from_secs(2)), "\"three\" ran out");
+        assert_eq!(toasts.shown[0].notification.text, "two");
+    }
+
+    #[test]
+    fn stacks_newest_nearest_the_corner() {
+        let (context, _messages) = Context::<()>::test();
+        let mut toasts = Toasts::new(ToastConfig {
+            corner: ToastCorner::BottomRight,
+            width: 12,
+            ..ToastConfig::default()
+        });
+        context.notify(ToastLevel::Info, "older");
+        context.notify(ToastLevel::Success, "newer");
+        collect(&context, &mut toasts);
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 7));
+        toasts.render(buffer.area, &mut buffer);
+
+        assert_eq!(row(&buffer, 0), "                    ");
+        assert_eq!(row(&buffer, 2), "        │ℹ older   │");
+        assert_eq!(row(&buffer, 5), "        │✓ newer   │");
+        assert_eq!(row(&buffer, 6), "        ╰──────────╯");
+    }
+
+    #[test]
+    fn the_newest_toasts_action_key_sends_its_message() {
+        let (context, mut messages) = Context::test();
+        let mut toasts = Toasts::new(ToastConfig::default());
+        context.toast(Toast::new(ToastLevel::Info, "Deleted").action(
+            KeyCode::Char('u'),
+            "Undo",
+            "undo",
+        ));
+        collect(&context, &mut toasts);
+
+        assert!(!toasts.handle_event(&Event::key_press(KeyCode::Char('x'))));
+        assert!(toasts.handle_event(&Event::key_press(KeyCode::Char('u'))));
+        assert_eq!(messages.try_recv().unwrap(), "undo");
+        assert!(toasts.shown.is_empty(), "acting dismisses the toast");
+    }
+}
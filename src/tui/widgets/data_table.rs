@@ -0,0 +1,932 @@
+//! A sortable, filterable table over rows supplied on demand.
+
+use super::scroll_view::scrollbar_state;
+use crate::tui::event::{Event, EventResult};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, StatefulWidget, Widget};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+/// Rows moved per mouse-wheel notch.
+const WHEEL_STEP: usize = 3;
+
+/// The rows behind a [`DataTable`].
+///
+/// Cells are fetched by row and column index when drawn, sorted, or
+/// filtered; the table never copies them.
+pub trait TableSource {
+    /// The number of rows.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no rows.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The text of one cell.
+    fn cell(&self, row: usize, column: usize) -> Cow<'_, str>;
+
+    /// Orders two rows by `column`, ascending. Compares the cell text by
+    /// default; override it for numbers, dates, and the like.
+    fn compare(&self, a: usize, b: usize, column: usize) -> Ordering {
+        self.cell(a, column).cmp(&self.cell(b, column))
+    }
+
+    /// Whether `row` passes the filter on `column`. `filter` is non-empty
+    /// and lowercase; the default is a case-insensitive substring match.
+    fn matches(&self, row: usize, column: usize, filter: &str) -> bool {
+        self.cell(row, column).to_lowercase().contains(filter)
+    }
+}
+
+/// Each row is a `Vec` of cell strings.
+impl<S: AsRef<str>> TableSource for [Vec<S>] {
+    fn len(&self) -> usize {
+        <[Vec<S>]>::len(self)
+    }
+
+    fn cell(&self, row: usize, column: usize) -> Cow<'_, str> {
+        Cow::Borrowed(self[row].get(column).map_or("", AsRef::as_ref))
+    }
+}
+
+/// Each row is an array of cell strings.
+impl<S: AsRef<str>, const N: usize> TableSource for [[S; N]] {
+    fn len(&self) -> usize {
+        <[[S; N]]>::len(self)
+    }
+
+    fn cell(&self, row: usize, column: usize) -> Cow<'_, str> {
+        Cow::Borrowed(self[row].get(column).map_or("", AsRef::as_ref))
+    }
+}
+
+/// Sort direction of a [`DataTable`] column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// A column's title and width. Width is in cells and can be changed by
+/// the user at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableColumn {
+    title: String,
+    width: u16,
+    filter: String,
+}
+
+impl TableColumn {
+    /// Creates a column `width` cells wide (at least 1).
+    pub fn new(title: impl Into<String>, width: u16) -> Self {
+        Self {
+            title: title.into(),
+            width: width.max(1),
+            filter: String::new(),
+        }
+    }
+}
+
+/// Where things were drawn last frame, for mouse hit-testing.
+#[derive(Debug, Clone, Default)]
+struct TableLayout {
+    header: Rect,
+    body: Rect,
+    /// Each visible column and its cells' horizontal extent.
+    columns: Vec<(usize, u16, u16)>,
+}
+
+/// Interactive state for a table of any number of rows: a cursor row,
+/// multi-row selection, sorting, per-column filters, resizable columns,
+/// and horizontal scrolling.
+///
+/// The header row and the first column stay in place; the other columns
+/// scroll sideways when they don't fit. Keep a `DataTable` next to the data
+/// and pass the data in for events and rendering, like [`VirtualList`]:
+///
+/// ```ignore
+/// self.table.render(frame, area, self.rows.as_slice());
+///
+/// if self.table.handle_event(&event, self.rows.as_slice()).is_consumed() {
+///     return EventResult::Consumed;
+/// }
+/// ```
+///
+/// Keys: Up/Down, PageUp/PageDown, Home/End move the cursor (with Shift,
+/// they extend the selection); Space toggles the cursor row; Ctrl+A selects
+/// every shown row; Esc clears the selection. Left/Right pick the current
+/// column, `s` cycles its sort (ascending, descending, off), `<`/`>` narrow
+/// and widen it, and `/` edits its filter (Enter keeps it, Esc clears it).
+///
+/// Mouse: click a header to sort by it, drag a header's right edge to
+/// resize, click a row to move the cursor (Ctrl toggles its selection,
+/// Shift selects the range from the cursor), and use the wheel to scroll.
+///
+/// Sorting and filtering keep an index per shown row, so they cost memory
+/// in proportion to the row count, but never copy cells. Call
+/// [`refresh`](Self::refresh) when cell contents change; a changed row
+/// count is noticed on its own.
+///
+/// [`VirtualList`]: super::VirtualList
+#[derive(Debug, Clone)]
+pub struct DataTable {
+    columns: Vec<TableColumn>,
+    sort: Option<(usize, SortOrder)>,
+    /// Source row indices in display order, after filtering and sorting.
+    order: Vec<usize>,
+    dirty: bool,
+    source_len: usize,
+    /// The cursor's position in `order`.
+    cursor: usize,
+    column: usize,
+    /// Selected rows, as source indices, so they survive re-sorting.
+    selected: BTreeSet<usize>,
+    top: usize,
+    /// The first column shown to the right of the frozen one.
+    first_scrolled: usize,
+    editing_filter: bool,
+    reveal: bool,
+    /// The column being resized by mouse: column, press x, width at press.
+    resizing: Option<(usize, u16, u16)>,
+    header_style: Style,
+    cursor_style: Style,
+    selected_style: Style,
+    layout: TableLayout,
+}
+
+impl DataTable {
+    /// Creates a table with the given columns, unsorted and unfiltered.
+    pub fn new(columns: impl IntoIterator<Item = TableColumn>) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+            sort: None,
+            order: Vec::new(),
+            dirty: true,
+            source_len: 0,
+            cursor: 0,
+            column: 0,
+            selected: BTreeSet::new(),
+            top: 0,
+            first_scrolled: 1,
+            editing_filter: false,
+            reveal: false,
+            resizing: None,
+            header_style: Style::default().add_modifier(Modifier::BOLD),
+            cursor_style: Style::default().add_modifier(Modifier::REVERSED),
+            selected_style: Style::default().bg(Color::DarkGray),
+            layout: TableLayout::default(),
+        }
+    }
+
+    /// Sets the style of the header row.
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Sets the style patched over the cursor row.
+    pub fn cursor_style(mut self, style: Style) -> Self {
+        self.cursor_style = style;
+        self
+    }
+
+    /// Sets the style patched over selected rows.
+    pub fn selected_style(mut self, style: Style) -> Self {
+        self.selected_style = style;
+        self
+    }
+
+    /// The source index of the row under the cursor.
+    pub fn cursor(&self) -> Option<usize> {
+        self.order.get(self.cursor).copied()
+    }
+
+    /// The current column, which sorting, resizing, and filter keys act on.
+    pub fn current_column(&self) -> usize {
+        self.column
+    }
+
+    /// Selected rows as source indices, in ascending order. Rows hidden by
+    /// a filter stay selected.
+    pub fn selected_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected.iter().copied()
+    }
+
+    /// Whether source row `row` is selected.
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selected.contains(&row)
+    }
+
+    /// Deselects every row.
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Source row indices in display order: filtered and sorted, as of the
+    /// last event or render.
+    pub fn rows(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// The current sort column and direction.
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    /// Sorts by `column`, or restores source order with `None`.
+    pub fn set_sort(&mut self, sort: Option<(usize, SortOrder)>) {
+        self.sort = sort;
+        self.dirty = true;
+    }
+
+    /// The filter on `column` (empty when unfiltered).
+    pub fn filter(&self, column: usize) -> &str {
+        self.columns.get(column).map_or("", |column| &column.filter)
+    }
+
+    /// Shows only rows whose `column` matches `filter`; an empty filter
+    /// removes it.
+    pub fn set_filter(&mut self, column: usize, filter: impl Into<String>) {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.filter = filter.into();
+            self.dirty = true;
+        }
+    }
+
+    /// Whether keys are going to the current column's filter. A parent
+    /// should not treat printable keys as shortcuts while this is `true`.
+    pub fn is_editing_filter(&self) -> bool {
+        self.editing_filter
+    }
+
+    /// The width of `column` in cells.
+    pub fn column_width(&self, column: usize) -> u16 {
+        self.columns.get(column).map_or(0, |column| column.width)
+    }
+
+    /// Sets the width of `column` (at least 1 cell).
+    pub fn set_column_width(&mut self, column: usize, width: u16) {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.width = width.max(1);
+        }
+    }
+
+    /// Re-applies sorting and filtering on the next event or render, after
+    /// cell contents changed.
+    pub fn refresh(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Handles the keys and mouse input listed on [`DataTable`].
+    ///
+    /// Returns [`EventResult::Propagate`] for input the table doesn't use,
+    /// including movement past either end.
+    pub fn handle_event<S: TableSource + ?Sized>(
+        &mut self,
+        event: &Event,
+        source: &S,
+    ) -> EventResult {
+        self.sync(source);
+        let consumed = match event {
+            Event::Key(_) if self.editing_filter && self.edit_filter(event) => true,
+            Event::Key(key) => self.handle_key(key.code, key.modifiers),
+            Event::Paste(text) if self.editing_filter => {
+                self.columns[self.column].filter.push_str(text);
+                self.dirty = true;
+                true
+            }
+            Event::Mouse(mouse) => {
+                let pointer = Position::new(mouse.column, mouse.row);
+                self.handle_mouse(mouse.kind, mouse.modifiers, pointer)
+            }
+            _ => false,
+        };
+        self.sync(source);
+
+        if consumed {
+            EventResult::Consumed
+        } else {
+            EventResult::Propagate
+        }
+    }
+
+    /// Draws the header, the optional filter row, and the rows in view.
+    pub fn render<S: TableSource + ?Sized>(&mut self, frame: &mut Frame, area: Rect, source: &S) {
+        self.sync(source);
+
+        let filter_row = self.editing_filter || self.columns.iter().any(|c| !c.filter.is_empty());
+        let header_rows = 1 + u16::from(filter_row);
+        let body_height = area.height.saturating_sub(header_rows);
+        let overflows = self.order.len() > usize::from(body_height);
+        let width = area.width.saturating_sub(u16::from(overflows));
+
+        self.layout.header = Rect::new(area.x, area.y, width, area.height.min(1));
+        self.layout.body = Rect::new(area.x, area.y + header_rows, width, body_height);
+        self.scroll_columns(width);
+        self.layout.columns = self.visible_columns(area.x, width);
+        self.scroll_rows();
+
+        let buffer = frame.buffer_mut();
+        self.render_header(buffer);
+        if filter_row && area.height > 1 {
+            let cursor = self.render_filters(buffer, area.y + 1);
+            if let Some(cursor) = cursor {
+                frame.set_cursor_position(cursor);
+            }
+        }
+
+        let buffer = frame.buffer_mut();
+        let body = self.layout.body;
+        for (offset, &row) in self.order[self.top..]
+            .iter()
+            .take(body.height.into())
+            .enumerate()
+        {
+            let y = body.y + offset as u16;
+            for &(column, x, cell_width) in &self.layout.columns {
+                let text = source.cell(row, column);
+                Line::raw(text.as_ref()).render(Rect::new(x, y, cell_width, 1), buffer);
+                separator(buffer, x + cell_width, y, body.right());
+            }
+
+            let line = Rect::new(body.x, y, body.width, 1);
+            if self.selected.contains(&row) {
+                buffer.set_style(line, self.selected_style);
+            }
+            if self.top + offset == self.cursor {
+                buffer.set_style(line, self.cursor_style);
+            }
+        }
+
+        if overflows {
+            let mut state = scrollbar_state(self.top, self.order.len(), body.height.into());
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(
+                Rect::new(area.x, body.y, area.width, body.height),
+                buffer,
+                &mut state,
+            );
+        }
+    }
+
+    /// Applies a key to the filter being edited. Returns `false` for keys
+    /// the filter doesn't use, so they still navigate.
+    fn edit_filter(&mut self, event: &Event) -> bool {
+        let filter = &mut self.columns[self.column].filter;
+        if let Some(c) = event.char() {
+            filter.push(c);
+        } else if event.is_key(KeyCode::Backspace) {
+            filter.pop();
+        } else if event.is_key(KeyCode::Enter) {
+            self.editing_filter = false;
+            return true;
+        } else if event.is_key(KeyCode::Esc) {
+            filter.clear();
+            self.editing_filter = false;
+        } else {
+            return false;
+        }
+        self.dirty = true;
+        true
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        let extend = modifiers.contains(KeyModifiers::SHIFT);
+        let page = usize::from(self.layout.body.height.saturating_sub(1).max(1));
+        let last = self.order.len().saturating_sub(1);
+        // Letter and symbol keys are plain presses only, so chords like
+        // Ctrl+S reach the parent.
+        let plain = !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+        match code {
+            KeyCode::Up => self.move_cursor(self.cursor.saturating_sub(1), extend),
+            KeyCode::Down => self.move_cursor((self.cursor + 1).min(last), extend),
+            KeyCode::PageUp => self.move_cursor(self.cursor.saturating_sub(page), extend),
+            KeyCode::PageDown => self.move_cursor((self.cursor + page).min(last), extend),
+            KeyCode::Home => self.move_cursor(0, extend),
+            KeyCode::End => self.move_cursor(last, extend),
+            KeyCode::Left if self.column > 0 => {
+                self.column -= 1;
+                true
+            }
+            KeyCode::Right if self.column + 1 < self.columns.len() => {
+                self.column += 1;
+                true
+            }
+            KeyCode::Char(' ') if plain => {
+                let Some(row) = self.cursor() else {
+                    return false;
+                };
+                if !self.selected.remove(&row) {
+                    self.selected.insert(row);
+                }
+                true
+            }
+            KeyCode::Char('a') if modifiers.contains(KeyModifiers::CONTROL) => {
+                if self.order.iter().all(|row| self.selected.contains(row)) {
+                    self.selected.clear();
+                } else {
+                    self.selected.extend(&self.order);
+                }
+                true
+            }
+            KeyCode::Esc if !self.selected.is_empty() => {
+                self.selected.clear();
+                true
+            }
+            KeyCode::Char('s') if plain && !self.columns.is_empty() => {
+                self.cycle_sort(self.column);
+                true
+            }
+            KeyCode::Char('<') if plain => {
+                self.resize(self.column_width(self.column).saturating_sub(1))
+            }
+            KeyCode::Char('>') if plain => {
+                self.resize(self.column_width(self.column).saturating_add(1))
+            }
+            KeyCode::Char('/') if plain && !self.columns.is_empty() => {
+                self.editing_filter = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_mouse(
+        &mut self,
+        kind: MouseEventKind,
+        modifiers: KeyModifiers,
+        pointer: Position,
+    ) -> bool {
+        if let Some((column, press_x, width)) = self.resizing {
+            return match kind {
+                MouseEventKind::Drag(MouseButton::Left) => {
+                    let delta = i32::from(pointer.x) - i32::from(press_x);
+                    let width = (i32::from(width) + delta).clamp(1, i32::from(u16::MAX));
+                    self.set_column_width(column, width as u16);
+                    true
+                }
+                _ => {
+                    self.resizing = None;
+                    true
+                }
+            };
+        }
+
+        let in_header = self.layout.header.contains(pointer);
+        let in_body = self.layout.body.contains(pointer);
+        match kind {
+            MouseEventKind::ScrollUp if in_body => self.scroll_by(-(WHEEL_STEP as isize)),
+            MouseEventKind::ScrollDown if in_body => self.scroll_by(WHEEL_STEP as isize),
+            MouseEventKind::ScrollLeft if in_body || in_header => self.scroll_sideways(-1),
+            MouseEventKind::ScrollRight if in_body || in_header => self.scroll_sideways(1),
+            MouseEventKind::Down(MouseButton::Left) if in_header => {
+                // The separator right of a column is its resize handle.
+                let edge = self
+                    .layout
+                    .columns
+                    .iter()
+                    .find(|&&(_, x, width)| pointer.x == x + width);
+                if let Some(&(column, _, _)) = edge {
+                    self.resizing = Some((column, pointer.x, self.column_width(column)));
+                    return true;
+                }
+                let Some(&(column, _, _)) = self
+                    .layout
+                    .columns
+                    .iter()
+                    .find(|&&(_, x, width)| (x..x + width).contains(&pointer.x))
+                else {
+                    return false;
+                };
+                self.column = column;
+                self.cycle_sort(column);
+                true
+            }
+            MouseEventKind::Down(MouseButton::Left) if in_body => {
+                let position = self.top + usize::from(pointer.y - self.layout.body.y);
+                let Some(&row) = self.order.get(position) else {
+                    return false;
+                };
+                if modifiers.contains(KeyModifiers::CONTROL) {
+                    if !self.selected.remove(&row) {
+                        self.selected.insert(row);
+                    }
+                } else if modifiers.contains(KeyModifiers::SHIFT) {
+                    let (from, to) = (self.cursor.min(position), self.cursor.max(position));
+                    self.selected.extend(&self.order[from..=to]);
+                }
+                self.cursor = position;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the cursor to display position `to`, selecting the rows it
+    /// passes over when `extend` is set.
+    fn move_cursor(&mut self, to: usize, extend: bool) -> bool {
+        if self.order.is_empty() || to == self.cursor {
+            return false;
+        }
+        if extend {
+            let (from, until) = (self.cursor.min(to), self.cursor.max(to));
+            self.selected.extend(&self.order[from..=until]);
+        }
+        self.cursor = to;
+        self.reveal = true;
+        true
+    }
+
+    fn cycle_sort(&mut self, column: usize) {
+        self.sort = match self.sort {
+            Some((current, SortOrder::Ascending)) if current == column => {
+                Some((column, SortOrder::Descending))
+            }
+            Some((current, SortOrder::Descending)) if current == column => None,
+            _ => Some((column, SortOrder::Ascending)),
+        };
+        self.dirty = true;
+    }
+
+    fn resize(&mut self, width: u16) -> bool {
+        let before = self.column_width(self.column);
+        self.set_column_width(self.column, width);
+        self.column_width(self.column) != before
+    }
+
+    fn scroll_by(&mut self, rows: isize) -> bool {
+        let limit = self
+            .order
+            .len()
+            .saturating_sub(self.layout.body.height.into());
+        let top = self.top.saturating_add_signed(rows).min(limit);
+        let moved = top != self.top;
+        self.top = top;
+        moved
+    }
+
+    fn scroll_sideways(&mut self, columns: isize) -> bool {
+        let last = self.columns.len().saturating_sub(1).max(1);
+        let first = self
+            .first_scrolled
+            .saturating_add_signed(columns)
+            .clamp(1, last);
+        let moved = first != self.first_scrolled;
+        self.first_scrolled = first;
+        moved
+    }
+
+    /// Rebuilds the display order if sorting, filters, or the row count
+    /// changed, keeping the cursor on the same source row when it's still
+    /// shown.
+    fn sync<S: TableSource + ?Sized>(&mut self, source: &S) {
+        let len = source.len();
+        if !self.dirty && len == self.source_len {
+            return;
+        }
+        let cursor_row = self.cursor();
+
+        let filters: Vec<(usize, String)> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.filter.is_empty())
+            .map(|(index, column)| (index, column.filter.to_lowercase()))
+            .collect();
+        self.order = (0..len)
+            .filter(|&row| {
+                filters
+                    .iter()
+                    .all(|(column, filter)| source.matches(row, *column, filter))
+            })
+            .collect();
+        if let Some((column, order)) = self.sort {
+            self.order.sort_by(|&a, &b| {
+                let ordering = source.compare(a, b, column);
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+
+        self.selected.retain(|&row| row < len);
+        self.cursor = cursor_row
+            .and_then(|row| self.order.iter().position(|&shown| shown == row))
+            .unwrap_or(self.cursor)
+            .min(self.order.len().saturating_sub(1));
+        self.reveal = true;
+        self.dirty = false;
+        self.source_len = len;
+    }
+
+    /// Keeps the cursor row in view after it moved, and the view within
+    /// the rows.
+    fn scroll_rows(&mut self) {
+        let height = usize::from(self.layout.body.height);
+        if self.reveal && height > 0 {
+            if self.cursor < self.top {
+                self.top = self.cursor;
+            } else if self.cursor >= self.top + height {
+                self.top = self.cursor + 1 - height;
+            }
+        }
+        self.reveal = false;
+        self.top = self.top.min(self.order.len().saturating_sub(height));
+    }
+
+    /// Scrolls sideways so the current column is in view, without leaving
+    /// empty space on the right.
+    fn scroll_columns(&mut self, width: u16) {
+        let count = self.columns.len();
+        if count < 2 {
+            return;
+        }
+        self.first_scrolled = self.first_scrolled.clamp(1, count - 1);
+        if self.column >= 1 && self.column < self.first_scrolled {
+            self.first_scrolled = self.column;
+        }
+
+        // Cells plus one separator per column.
+        let room = u32::from(width).saturating_sub(u32::from(self.columns[0].width) + 1);
+        let span = |from: usize, to: usize| -> u32 {
+            self.columns[from..=to]
+                .iter()
+                .map(|column| u32::from(column.width) + 1)
+                .sum()
+        };
+        while self.column > self.first_scrolled && span(self.first_scrolled, self.column) > room {
+            self.first_scrolled += 1;
+        }
+        while self.first_scrolled > 1 && span(self.first_scrolled - 1, count - 1) <= room {
+            self.first_scrolled -= 1;
+        }
+    }
+
+    /// Lays out the frozen column and the scrolled ones that fit, the last
+    /// possibly cut short.
+    fn visible_columns(&self, x: u16, width: u16) -> Vec<(usize, u16, u16)> {
+        let right = x.saturating_add(width);
+        let shown = std::iter::once(0).chain(self.first_scrolled..self.columns.len());
+        let mut columns = Vec::new();
+        let mut x = x;
+        for index in shown.take_while(|&index| index < self.columns.len()) {
+            if x >= right {
+                break;
+            }
+            let cell_width = self.columns[index].width.min(right - x);
+            columns.push((index, x, cell_width));
+            x = x.saturating_add(cell_width + 1);
+        }
+        columns
+    }
+
+    fn render_header(&self, buffer: &mut Buffer) {
+        let header = self.layout.header;
+        if header.height == 0 {
+            return;
+        }
+        for &(index, x, width) in &self.layout.columns {
+            let column = &self.columns[index];
+            let arrow = match self.sort {
+                Some((sorted, SortOrder::Ascending)) if sorted == index => Some("▲"),
+                Some((sorted, SortOrder::Descending)) if sorted == index => Some("▼"),
+                _ => None,
+            };
+            let mut style = self.header_style;
+            if index == self.column {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            // The sort arrow takes the column's last cell, cutting a long
+            // title short rather than hiding the arrow.
+            let title_width = width.saturating_sub(u16::from(arrow.is_some()));
+            Line::styled(column.title.as_str(), style)
+                .render(Rect::new(x, header.y, title_width, 1), buffer);
+            if let Some(arrow) = arrow {
+                Line::styled(arrow, style)
+                    .render(Rect::new(x + title_width, header.y, 1, 1), buffer);
+            }
+            separator(buffer, x + width, header.y, header.right());
+        }
+    }
+
+    /// Draws each column's filter under its title; returns where the
+    /// terminal cursor goes while a filter is being edited.
+    fn render_filters(&self, buffer: &mut Buffer, y: u16) -> Option<Position> {
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut cursor = None;
+        for &(index, x, width) in &self.layout.columns {
+            let filter = &self.columns[index].filter;
+            let editing = self.editing_filter && index == self.column;
+            if filter.is_empty() && !editing {
+                continue;
+            }
+            let line = Line::styled(format!("/{filter}"), dim);
+            if editing {
+                let typed = line.width() as u16;
+                cursor = Some(Position::new(x + typed.min(width.saturating_sub(1)), y));
+            }
+            line.render(Rect::new(x, y, width, 1), buffer);
+        }
+        cursor
+    }
+}
+
+/// The line between two columns, unless it falls off the right edge.
+fn separator(buffer: &mut Buffer, x: u16, y: u16, right: u16) {
+    if x < right
+        && let Some(cell) = buffer.cell_mut(Position::new(x, y))
+    {
+        cell.set_symbol("│")
+            .set_style(Style::default().fg(Color::DarkGray));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataTable, SortOrder, TableColumn};
+    use crate::tui::event::{Event, EventResult};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+    use ratatui::layout::Position;
+    use ratatui::{Frame, Terminal, backend::TestBackend};
+
+    const ROWS: [[&str; 3]; 4] = [
+        ["pear", "3", "green"],
+        ["apple", "1", "red"],
+        ["plum", "4", "purple"],
+        ["fig", "2", "brown"],
+    ];
+
+    fn table() -> DataTable {
+        DataTable::new([
+            TableColumn::new("Fruit", 6),
+            TableColumn::new("Qty", 3),
+            TableColumn::new("Color", 6),
+        ])
+    }
+
+    fn draw(table: &mut DataTable, terminal: &mut Terminal<TestBackend>) -> Vec<String> {
+        terminal
+            .draw(|frame: &mut Frame| table.render(frame, frame.area(), ROWS.as_slice()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[Position::new(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn key(table: &mut DataTable, code: KeyCode) -> EventResult {
+        table.handle_event(&Event::key_press(code), ROWS.as_slice())
+    }
+
+    fn click(table: &mut DataTable, column: u16, row: u16) -> EventResult {
+        let event = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(crossterm::event::MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        });
+        table.handle_event(&event, ROWS.as_slice())
+    }
+
+    #[test]
+    fn sorts_by_key_and_by_header_click_and_keeps_the_cursor_row() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let mut table = table();
+        draw(&mut table, &mut terminal);
+        assert_eq!(table.cursor(), Some(0), "pear");
+
+        for (c, modifiers) in [('s', KeyModifiers::CONTROL), ('/', KeyModifiers::ALT)] {
+            let chord = Event::Key(KeyEvent::new(KeyCode::Char(c), modifiers));
+            assert_eq!(
+                table.handle_event(&chord, ROWS.as_slice()),
+                EventResult::Propagate,
+                "chords are the parent's"
+            );
+        }
+        assert_eq!(table.sort(), None);
+
+        key(&mut table, KeyCode::Char('s'));
+        assert_eq!(table.sort(), Some((0, SortOrder::Ascending)));
+        assert_eq!(table.rows(), [1, 3, 0, 2]);
+        assert_eq!(table.cursor(), Some(0), "still on pear");
+
+        let screen = draw(&mut table, &mut terminal);
+        assert_eq!(screen[0], "Fruit▲│Qty│Color │");
+        assert_eq!(screen[1], "apple │1  │red   │");
+
+        // "Qty" starts at column 7.
+        assert_eq!(click(&mut table, 8, 0), EventResult::Consumed);
+        assert_eq!(table.sort(), Some((1, SortOrder::Ascending)));
+        assert_eq!(table.current_column(), 1);
+        click(&mut table, 8, 0);
+        assert_eq!(table.rows(), [2, 0, 3, 1]);
+        click(&mut table, 8, 0);
+        assert_eq!(
+            (table.sort(), table.rows()),
+            (None, [0, 1, 2, 3].as_slice())
+        );
+    }
+
+    #[test]
+    fn filters_narrow_rows_while_typing() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 6)).unwrap();
+        let mut table = table();
+        draw(&mut table, &mut terminal);
+
+        key(&mut table, KeyCode::Char('/'));
+        assert!(table.is_editing_filter());
+        key(&mut table, KeyCode::Char('P'));
+        assert_eq!(table.rows(), [0, 1, 2], "case-insensitive substring");
+        key(&mut table, KeyCode::Char('l'));
+        assert_eq!(table.rows(), [1, 2]);
+        key(&mut table, KeyCode::Char('u'));
+        assert_eq!(table.rows(), [2]);
+        assert_eq!(key(&mut table, KeyCode::Enter), EventResult::Consumed);
+        assert!(!table.is_editing_filter());
+
+        let screen = draw(&mut table, &mut terminal);
+        assert_eq!(screen[1], "/Plu");
+        assert_eq!(screen[2], "plum  │4  │purple│");
+
+        key(&mut table, KeyCode::Char('/'));
+        key(&mut table, KeyCode::Esc);
+        assert_eq!(table.filter(0), "");
+        assert_eq!(table.rows().len(), 4);
+    }
+
+    #[test]
+    fn selection_extends_toggles_and_survives_sorting() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let mut table = table();
+        draw(&mut table, &mut terminal);
+
+        let shift_down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT));
+        table.handle_event(&shift_down, ROWS.as_slice());
+        key(&mut table, KeyCode::Down);
+        key(&mut table, KeyCode::Char(' '));
+        assert_eq!(table.selected_rows().collect::<Vec<_>>(), [0, 1, 2]);
+
+        key(&mut table, KeyCode::Char(' '));
+        key(&mut table, KeyCode::Char('s'));
+        assert_eq!(table.selected_rows().collect::<Vec<_>>(), [0, 1]);
+
+        assert_eq!(key(&mut table, KeyCode::Esc), EventResult::Consumed);
+        assert_eq!(table.selected_rows().count(), 0);
+        assert_eq!(key(&mut table, KeyCode::Esc), EventResult::Propagate);
+    }
+
+    #[test]
+    fn narrow_tables_freeze_the_first_column_and_scroll_the_rest() {
+        let mut terminal = Terminal::new(TestBackend::new(12, 5)).unwrap();
+        let mut table = table();
+
+        let screen = draw(&mut table, &mut terminal);
+        assert_eq!(screen[1], "pear  │3  │g");
+
+        key(&mut table, KeyCode::Right);
+        key(&mut table, KeyCode::Right);
+        let screen = draw(&mut table, &mut terminal);
+        assert_eq!(screen[1], "pear  │green");
+
+        key(&mut table, KeyCode::Char('>'));
+        assert_eq!(table.column_width(2), 7);
+    }
+
+    #[test]
+    fn dragging_a_header_edge_resizes_the_column() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let mut table = table();
+        draw(&mut table, &mut terminal);
+
+        let mouse = |kind, column| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        let left = crossterm::event::MouseButton::Left;
+        table.handle_event(&mouse(MouseEventKind::Down(left), 6), ROWS.as_slice());
+        table.handle_event(&mouse(MouseEventKind::Drag(left), 9), ROWS.as_slice());
+        table.handle_event(&mouse(MouseEventKind::Up(left), 9), ROWS.as_slice());
+
+        assert_eq!(table.column_width(0), 9);
+        assert_eq!(table.sort(), None, "resizing is not a sort click");
+    }
+}
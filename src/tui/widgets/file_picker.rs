@@ -0,0 +1,699 @@
+//! A file picker component: browse, filter, complete, preview, choose.
+
+use super::text_input::TextInput;
+use super::virtual_list::{ListSource, VirtualList};
+use crate::tui::component::{Component, Context};
+use crate::tui::event::{Event, EventResult};
+use crate::tui::gesture::Gesture;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton};
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// How many entries of a directory the list reads. Listing runs in the
+/// event handler, so a huge directory must not stall it; past this, the
+/// rest are left out and a note says so.
+const MAX_ENTRIES: usize = 10_000;
+/// How much of a file the preview pane reads.
+const PREVIEW_BYTES: u64 = 64 * 1024;
+/// How many entries of a directory the preview pane lists.
+const PREVIEW_ENTRIES: usize = 500;
+/// Narrower than this, the preview pane is left out.
+const PREVIEW_MIN_WIDTH: u16 = 70;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    /// Anything but a directory: regular files, and also devices, FIFOs,
+    /// and sockets, which can be chosen but not previewed. Sorts
+    /// directories first.
+    is_file: bool,
+    name: String,
+}
+
+/// The entries shown in the list, after hiding and filtering.
+#[derive(Debug, Clone, Default)]
+struct Listing(Vec<Entry>);
+
+impl ListSource for Listing {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn render(&self, index: usize, area: Rect, buf: &mut Buffer) {
+        let entry = &self.0[index];
+        let line = if entry.is_file {
+            Line::raw(entry.name.as_str())
+        } else {
+            Line::styled(
+                format!("{}/", entry.name),
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+        };
+        line.render(area, buf);
+    }
+}
+
+/// A file chooser you can embed in any screen, or run on its own.
+///
+/// The list shows the current directory, directories first. Typing narrows
+/// it: plain text matches names by prefix and offers the completion (Tab
+/// accepts it, and enters the directory when only one matches); text with
+/// `*` or `?` is a glob matched against file names. Up/Down pick an entry,
+/// Enter opens a directory or chooses a file, `/` after a directory name
+/// enters it (otherwise it's typed, for a path like `src/main.rs`), Backspace on an empty input goes up, and Alt+H shows or hides
+/// dot files. A text preview of the highlighted file sits on the right when
+/// there's room. Esc is left to the parent.
+///
+/// Choosing a file sends `on_pick(path)` through the [`Context`], so the
+/// path arrives in `update` like any other message:
+///
+/// ```ignore
+/// let picker = FilePicker::new(".", Msg::Picked).filter("*.toml");
+///
+/// fn update(&mut self, message: Msg, context: &Context<Msg>) {
+///     if let Msg::Picked(path) = message {
+///         self.config = Some(path);
+///     }
+/// }
+/// ```
+///
+/// When nothing matches the typed text, Enter chooses it as a new name in
+/// the current directory — handy for "save as". That's also the way to a
+/// file in a directory too big to list: only the first 10,000 entries the
+/// system returns are shown.
+pub struct FilePicker<M> {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    /// The directory had more than [`MAX_ENTRIES`] entries.
+    truncated: bool,
+    listing: Listing,
+    list: VirtualList,
+    input: TextInput,
+    show_hidden: bool,
+    filter: Option<String>,
+    error: Option<String>,
+    preview: Option<(PathBuf, Vec<String>)>,
+    on_pick: Box<dyn Fn(PathBuf) -> M + Send>,
+}
+
+impl<M: Send + 'static> FilePicker<M> {
+    /// Creates a picker showing `dir`; choosing a file sends
+    /// `on_pick(path)`.
+    pub fn new(dir: impl Into<PathBuf>, on_pick: impl Fn(PathBuf) -> M + Send + 'static) -> Self {
+        let mut picker = Self {
+            dir: PathBuf::new(),
+            entries: Vec::new(),
+            truncated: false,
+            listing: Listing::default(),
+            list: VirtualList::new(),
+            input: TextInput::new().placeholder("type to filter, *.ext to glob"),
+            show_hidden: false,
+            filter: None,
+            error: None,
+            preview: None,
+            on_pick: Box::new(on_pick),
+        };
+        picker.set_directory(dir);
+        picker
+    }
+
+    /// Shows dot files from the start (Alt+H toggles it either way).
+    pub fn show_hidden(mut self, show: bool) -> Self {
+        self.show_hidden = show;
+        self.refilter();
+        self
+    }
+
+    /// Only lists files matching `glob` (`*` and `?`); directories are
+    /// always listed so you can navigate.
+    pub fn filter(mut self, glob: impl Into<String>) -> Self {
+        self.filter = Some(glob.into());
+        self.refilter();
+        self
+    }
+
+    /// The directory being shown.
+    pub fn directory(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The highlighted entry.
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        let entry = self.listing.0.get(self.list.selected()?)?;
+        Some(self.dir.join(&entry.name))
+    }
+
+    /// Shows `dir`, clearing the typed text. If it can't be read, the
+    /// current directory stays and the error is shown. At most 10,000
+    /// entries are listed.
+    pub fn set_directory(&mut self, dir: impl Into<PathBuf>) {
+        let dir = dir.into();
+        let dir = fs::canonicalize(&dir).unwrap_or(dir);
+        match read_entries(&dir, MAX_ENTRIES + 1) {
+            Ok(mut entries) => {
+                self.truncated = entries.len() > MAX_ENTRIES;
+                entries.truncate(MAX_ENTRIES);
+                self.dir = dir;
+                self.entries = entries;
+                self.error = None;
+            }
+            Err(error) => self.error = Some(format!("{}: {error}", dir.display())),
+        }
+        self.input.clear();
+        self.refilter();
+    }
+
+    /// Opens the highlighted directory or chooses the highlighted file.
+    fn activate(&mut self, context: &Context<M>) {
+        let Some(index) = self.list.selected() else {
+            let typed = self.input.value();
+            if !typed.is_empty() && !is_glob(typed) {
+                let path = self.dir.join(typed);
+                self.pick(path, context);
+            }
+            return;
+        };
+        let entry = self.listing.0[index].clone();
+        let path = self.dir.join(&entry.name);
+        if entry.is_file {
+            self.pick(path, context);
+        } else {
+            self.set_directory(path);
+        }
+    }
+
+    fn pick(&self, path: PathBuf, context: &Context<M>) {
+        context.deliver((self.on_pick)(path));
+    }
+
+    fn go_up(&mut self) -> bool {
+        let Some(parent) = self.dir.parent().map(Path::to_path_buf) else {
+            return false;
+        };
+        let child = self
+            .dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+        self.set_directory(parent);
+        // Keep the directory we came from highlighted.
+        if let Some(child) = child
+            && let Some(index) = self.listing.0.iter().position(|entry| entry.name == child)
+        {
+            self.list.select(Some(index));
+        }
+        true
+    }
+
+    /// Enters the directory named exactly by the typed text, if it's the
+    /// only match.
+    fn enter_typed_directory(&mut self) -> bool {
+        match self.listing.0.as_slice() {
+            [entry] if !entry.is_file && entry.name == self.input.value() => {
+                let path = self.dir.join(&entry.name);
+                self.set_directory(path);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Recomputes the shown entries, the selection, and the completion
+    /// from the typed text.
+    fn refilter(&mut self) {
+        let pattern = self.input.value();
+        let glob = is_glob(pattern);
+        let show_hidden = self.show_hidden || pattern.starts_with('.');
+
+        self.listing.0 = self
+            .entries
+            .iter()
+            .filter(|entry| show_hidden || !entry.name.starts_with('.'))
+            .filter(|entry| match &self.filter {
+                Some(filter) if entry.is_file => glob_match(filter, &entry.name),
+                _ => true,
+            })
+            .filter(|entry| {
+                if glob {
+                    !entry.is_file || glob_match(pattern, &entry.name)
+                } else {
+                    entry.name.starts_with(pattern)
+                }
+            })
+            .cloned()
+            .collect();
+
+        let suggestion = if glob || pattern.is_empty() {
+            None
+        } else {
+            common_prefix(self.listing.0.iter().map(|entry| entry.name.as_str()))
+                .and_then(|prefix| prefix.strip_prefix(pattern).map(str::to_string))
+        };
+        self.input.set_suggestion(suggestion);
+
+        self.list = VirtualList::new().highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        self.list.select((!self.listing.0.is_empty()).then_some(0));
+        self.update_preview();
+    }
+
+    fn update_preview(&mut self) {
+        let Some(path) = self.selected_path() else {
+            self.preview = None;
+            return;
+        };
+        if self
+            .preview
+            .as_ref()
+            .is_some_and(|(shown, _)| *shown == path)
+        {
+            return;
+        }
+        let lines = preview(&path);
+        self.preview = Some((path, lines));
+    }
+
+    fn render_preview(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self
+            .preview
+            .iter()
+            .flat_map(|(_, lines)| lines.iter().map(|line| Line::raw(line.as_str())))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Preview ")),
+            area,
+        );
+    }
+}
+
+impl<M: Send + 'static> Component for FilePicker<M> {
+    type Message = M;
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let [body, input_area, hint] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let (list_area, preview_area) = if area.width >= PREVIEW_MIN_WIDTH {
+            let [list, preview] =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(body);
+            (list, Some(preview))
+        } else {
+            (body, None)
+        };
+
+        let block = Block::bordered().title(format!(" {} ", self.dir.display()));
+        let inner = block.inner(list_area);
+        frame.render_widget(block, list_area);
+        let note = match &self.error {
+            Some(error) => Some(Line::styled(
+                error.as_str(),
+                Style::default().fg(Color::Red),
+            )),
+            None if self.truncated => Some(Line::styled(
+                format!("(first {MAX_ENTRIES} entries; type a name for the rest)"),
+                Style::default().fg(Color::DarkGray),
+            )),
+            None => None,
+        };
+        if let Some(note) = note {
+            let [note_area, rest] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+            frame.render_widget(note, note_area);
+            self.list.render(frame, rest, &self.listing);
+        } else if self.listing.0.is_empty() {
+            frame.render_widget(
+                Line::styled("(no matches)", Style::default().fg(Color::DarkGray)),
+                inner,
+            );
+        } else {
+            self.list.render(frame, inner, &self.listing);
+        }
+
+        if let Some(preview_area) = preview_area {
+            self.render_preview(frame, preview_area);
+        }
+
+        let block = Block::bordered().title(" Name ");
+        let inner = block.inner(input_area);
+        frame.render_widget(block, input_area);
+        self.input.render(frame, inner, true);
+
+        let hidden = if self.show_hidden { "on" } else { "off" };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![Span::raw(format!(
+                " ↑/↓: move | Enter: open/choose | Tab: complete | Backspace: up | Alt+H: hidden ({hidden})"
+            ))]))
+            .style(Style::default().fg(Color::DarkGray)),
+            hint,
+        );
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        let before = self.list.selected();
+        let result = self.handle(&event, context);
+        if self.list.selected() != before {
+            self.update_preview();
+        }
+        result
+    }
+}
+
+impl<M: Send + 'static> FilePicker<M> {
+    fn handle(&mut self, event: &Event, context: &Context<M>) -> EventResult {
+        if event.is_key_with(KeyCode::Char('h'), KeyModifiers::ALT) {
+            self.show_hidden = !self.show_hidden;
+            self.refilter();
+            return EventResult::Consumed;
+        }
+
+        match event {
+            Event::Key(key)
+                if matches!(
+                    key.code,
+                    KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown
+                ) =>
+            {
+                return self.list.handle_event(event, &self.listing);
+            }
+            Event::Mouse(_) => return self.list.handle_event(event, &self.listing),
+            Event::Gesture(Gesture::Click {
+                button: MouseButton::Left,
+                position,
+                count: 2,
+            }) if self.list.viewport().contains(*position) => {
+                self.activate(context);
+                return EventResult::Consumed;
+            }
+            _ => {}
+        }
+
+        if event.is_key(KeyCode::Enter) {
+            self.activate(context);
+            return EventResult::Consumed;
+        }
+        if event.is_key(KeyCode::Backspace) && self.input.value().is_empty() {
+            return if self.go_up() {
+                EventResult::Consumed
+            } else {
+                EventResult::Propagate
+            };
+        }
+        if event.char() == Some('/') {
+            if self.input.value().is_empty() {
+                self.set_directory(root_of(&self.dir));
+                return EventResult::Consumed;
+            }
+            if self.input.value() == "~" {
+                if let Some(home) = std::env::var_os("HOME") {
+                    self.set_directory(home);
+                }
+                return EventResult::Consumed;
+            }
+            if self.enter_typed_directory() {
+                return EventResult::Consumed;
+            }
+            // Part of a longer path, or a directory yet to be made: typed
+            // like any other character.
+        }
+
+        let tab = event.is_key(KeyCode::Tab);
+        if self.input.handle_event(event).is_consumed() {
+            self.refilter();
+            if tab {
+                self.enter_typed_directory();
+            }
+            return EventResult::Consumed;
+        }
+        if tab && self.enter_typed_directory() {
+            return EventResult::Consumed;
+        }
+
+        EventResult::Propagate
+    }
+}
+
+/// Lists up to `limit` entries of `dir`, directories first, each group by
+/// name.
+fn read_entries(dir: &Path, limit: usize) -> std::io::Result<Vec<Entry>> {
+    let mut entries: Vec<Entry> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .take(limit)
+        .map(|entry| {
+            let is_dir = match entry.file_type() {
+                // Follow symlinks, so a link to a directory can be entered.
+                Ok(file_type) if file_type.is_symlink() => entry.path().is_dir(),
+                Ok(file_type) => file_type.is_dir(),
+                Err(_) => false,
+            };
+            Entry {
+                is_file: !is_dir,
+                name: entry.file_name().to_string_lossy().into_owned(),
+            }
+        })
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// The first lines of a text file, or a note saying why there's no text.
+///
+/// Runs in the event handler, so it only ever reads a bounded amount, and
+/// never opens anything but a regular file: a FIFO would block the UI
+/// forever, and a tty would steal the app's own input.
+fn preview(path: &Path) -> Vec<String> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(error) => return vec![error.to_string()],
+    };
+    if metadata.is_dir() {
+        return match read_entries(path, PREVIEW_ENTRIES) {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|entry| match entry.is_file {
+                    true => entry.name,
+                    false => format!("{}/", entry.name),
+                })
+                .collect(),
+            Err(error) => vec![error.to_string()],
+        };
+    }
+    if !metadata.is_file() {
+        return vec!["(not a regular file)".to_string()];
+    }
+
+    let mut bytes = Vec::new();
+    let read =
+        fs::File::open(path).and_then(|file| file.take(PREVIEW_BYTES).read_to_end(&mut bytes));
+    match read {
+        Err(error) => vec![error.to_string()],
+        Ok(_) if bytes.contains(&0) => vec!["(binary file)".to_string()],
+        Ok(_) => String::from_utf8_lossy(&bytes)
+            .lines()
+            .map(|line| line.replace('\t', "    "))
+            .collect(),
+    }
+}
+
+fn root_of(path: &Path) -> PathBuf {
+    path.ancestors()
+        .last()
+        .map_or_else(|| PathBuf::from("/"), Path::to_path_buf)
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Matches `name` against a glob where `*` is any run of characters and
+/// `?` is any one character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Where to resume after the last `*` if the rest fails to match.
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// The longest prefix shared by every name, or `None` if there are none.
+fn common_prefix<'a>(mut names: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let first = names.next()?;
+    let mut length = first.len();
+    for name in names {
+        length = first
+            .char_indices()
+            .zip(name.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((index, a), _)| index + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(length);
+    }
+    Some(&first[..length])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FilePicker, common_prefix, glob_match, preview};
+    use crate::tui::component::{Component, Context};
+    use crate::tui::event::Event;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A scratch directory: `a.txt`, `b.rs`, `.hidden`, `sub/inner.txt`.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("file-picker-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(dir.join("sub")).unwrap();
+            for file in ["a.txt", "b.rs", ".hidden", "sub/inner.txt"] {
+                fs::write(dir.join(file), "hello\n").unwrap();
+            }
+            Self(fs::canonicalize(&dir).unwrap())
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn names(picker: &FilePicker<PathBuf>) -> Vec<&str> {
+        picker
+            .listing
+            .0
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    fn send(picker: &mut FilePicker<PathBuf>, context: &Context<PathBuf>, code: KeyCode) {
+        picker.handle_event(Event::key_press(code), context);
+    }
+
+    #[test]
+    fn globs_match_stars_and_single_characters() {
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(!glob_match("*.rs", "main.rs.bak"));
+        assert!(glob_match("a?c*", "abcdef"));
+        assert!(glob_match("*a*b", "xxaxxb"));
+        assert!(!glob_match("?", ""));
+        assert_eq!(
+            common_prefix(["cargo", "car", "cart"].into_iter()),
+            Some("car")
+        );
+    }
+
+    #[test]
+    fn lists_directories_first_and_hides_dot_files() {
+        let scratch = Scratch::new("listing");
+        let (context, _messages) = Context::test();
+        let mut picker = FilePicker::new(&scratch.0, |path| path);
+        assert_eq!(names(&picker), ["sub", "a.txt", "b.rs"]);
+
+        let alt_h = Event::Key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::ALT));
+        picker.handle_event(alt_h, &context);
+        assert_eq!(names(&picker), ["sub", ".hidden", "a.txt", "b.rs"]);
+
+        for c in "*.rs".chars() {
+            send(&mut picker, &context, KeyCode::Char(c));
+        }
+        assert_eq!(
+            names(&picker),
+            ["sub", "b.rs"],
+            "directories stay for navigation"
+        );
+    }
+
+    #[test]
+    fn completes_into_a_directory_and_sends_the_chosen_path() {
+        let scratch = Scratch::new("choose");
+        let (context, mut messages) = Context::test();
+        let mut picker = FilePicker::new(&scratch.0, |path| path);
+
+        send(&mut picker, &context, KeyCode::Char('s'));
+        send(&mut picker, &context, KeyCode::Tab);
+        assert_eq!(picker.directory(), scratch.0.join("sub"));
+
+        send(&mut picker, &context, KeyCode::Enter);
+        assert_eq!(
+            messages.try_recv().unwrap(),
+            scratch.0.join("sub/inner.txt")
+        );
+
+        send(&mut picker, &context, KeyCode::Backspace);
+        assert_eq!(picker.directory(), scratch.0);
+        assert_eq!(picker.selected_path(), Some(scratch.0.join("sub")));
+    }
+
+    #[test]
+    fn slashes_enter_a_directory_or_are_typed_as_a_path() {
+        let scratch = Scratch::new("slash");
+        let (context, mut messages) = Context::test();
+        let mut picker = FilePicker::new(&scratch.0, |path| path);
+
+        for c in "sub/inner.txt".chars() {
+            send(&mut picker, &context, KeyCode::Char(c));
+        }
+        assert_eq!(picker.directory(), scratch.0.join("sub"));
+        assert_eq!(picker.input.value(), "inner.txt");
+
+        let mut picker = FilePicker::new(&scratch.0, |path| path);
+        for c in "new/a.txt".chars() {
+            send(&mut picker, &context, KeyCode::Char(c));
+        }
+        send(&mut picker, &context, KeyCode::Enter);
+        assert_eq!(messages.try_recv().unwrap(), scratch.0.join("new/a.txt"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn previews_never_open_devices_or_fifos() {
+        assert_eq!(preview("/dev/zero".as_ref()), ["(not a regular file)"]);
+
+        let scratch = Scratch::new("preview");
+        assert_eq!(preview(&scratch.0.join("a.txt")), ["hello"]);
+    }
+}
@@ -0,0 +1,998 @@
+//! Forms assembled from typed fields, with validation and submission.
+
+use super::text_input::TextInput;
+use crate::tui::component::{Component, Context};
+use crate::tui::event::{Event, EventResult};
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use ratatui::Frame;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::mpsc;
+
+type Validator = Box<dyn Fn(&Value) -> Result<(), String> + Send>;
+type AsyncValidator =
+    Box<dyn Fn(Value) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send>;
+/// An async check's result: field index, generation, outcome.
+type CheckResult = (usize, u64, Result<(), String>);
+
+/// The value of one field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Text and password fields.
+    Text(String),
+    /// Number fields; `None` while empty.
+    Number(Option<f64>),
+    /// Checkboxes.
+    Bool(bool),
+    /// Select fields; `None` until an option is chosen.
+    Choice(Option<String>),
+    /// Multi-select fields: the chosen options, in option order.
+    Choices(Vec<String>),
+}
+
+impl Value {
+    /// The text, or `""` for non-text values.
+    pub fn as_text(&self) -> &str {
+        match self {
+            Self::Text(text) => text,
+            _ => "",
+        }
+    }
+
+    /// The number, if this is a filled-in number.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => *number,
+            _ => None,
+        }
+    }
+
+    /// Whether this is a ticked checkbox.
+    pub fn as_bool(&self) -> bool {
+        matches!(self, Self::Bool(true))
+    }
+
+    /// The chosen option of a select.
+    pub fn as_choice(&self) -> Option<&str> {
+        match self {
+            Self::Choice(choice) => choice.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The chosen options of a multi-select.
+    pub fn as_choices(&self) -> &[String] {
+        match self {
+            Self::Choices(choices) => choices,
+            _ => &[],
+        }
+    }
+
+    /// Whether a required field would reject this value.
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(text) => text.trim().is_empty(),
+            Self::Number(number) => number.is_none(),
+            Self::Bool(checked) => !checked,
+            Self::Choice(choice) => choice.is_none(),
+            Self::Choices(choices) => choices.is_empty(),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(number: f64) -> Self {
+        Self::Number(Some(number))
+    }
+}
+
+impl From<bool> for Value {
+    fn from(checked: bool) -> Self {
+        Self::Bool(checked)
+    }
+}
+
+impl<S: Into<String>> From<Vec<S>> for Value {
+    fn from(choices: Vec<S>) -> Self {
+        Self::Choices(choices.into_iter().map(Into::into).collect())
+    }
+}
+
+/// A submitted form: every field's value by name, in field order.
+///
+/// The typed accessors return an empty value for unknown names, so turning
+/// the map into your own struct stays short:
+///
+/// ```ignore
+/// Form::new(|values| Msg::SignedUp(Signup {
+///     name: values.text("name").to_string(),
+///     age: values.number("age").unwrap_or_default() as u8,
+///     newsletter: values.checked("newsletter"),
+/// }))
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormValues(Vec<(String, Value)>);
+
+impl FormValues {
+    /// The value of the field called `name`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+
+    /// The text of a text or password field.
+    pub fn text(&self, name: &str) -> &str {
+        self.get(name).map_or("", Value::as_text)
+    }
+
+    /// The number of a number field, if filled in.
+    pub fn number(&self, name: &str) -> Option<f64> {
+        self.get(name).and_then(Value::as_number)
+    }
+
+    /// Whether a checkbox is ticked.
+    pub fn checked(&self, name: &str) -> bool {
+        self.get(name).is_some_and(Value::as_bool)
+    }
+
+    /// The chosen option of a select field.
+    pub fn choice(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(Value::as_choice)
+    }
+
+    /// The chosen options of a multi-select field.
+    pub fn choices(&self, name: &str) -> &[String] {
+        self.get(name).map_or(&[], Value::as_choices)
+    }
+
+    /// Every field's name and value, in field order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// The values keyed by field name.
+    pub fn into_map(self) -> BTreeMap<String, Value> {
+        self.0.into_iter().collect()
+    }
+}
+
+enum Kind {
+    Text,
+    Number,
+    Password,
+    Checkbox,
+    Select(Vec<String>),
+    MultiSelect(Vec<String>),
+}
+
+/// Where a field's async check stands for its current value.
+#[derive(Debug, Clone, PartialEq)]
+enum Check {
+    NotRun,
+    /// Running; results from other generations are stale.
+    Pending(u64),
+    Passed,
+    Failed(String),
+}
+
+/// One field of a [`Form`], described by its constructor and builders.
+///
+/// ```ignore
+/// Field::text("user", "Username")
+///     .required()
+///     .validate(|value| match value.as_text().len() {
+///         3.. => Ok(()),
+///         _ => Err("At least 3 characters".into()),
+///     })
+///     .validate_async(|value| async move { check_available(value.as_text()).await })
+/// ```
+pub struct Field {
+    name: String,
+    label: String,
+    kind: Kind,
+    required: bool,
+    validators: Vec<Validator>,
+    async_validator: Option<AsyncValidator>,
+    /// Text, number and password fields edit through this.
+    input: TextInput,
+    checked: bool,
+    /// Select: the chosen option. Multi-select: the highlighted option.
+    cursor: Option<usize>,
+    /// Multi-select: which options are chosen.
+    chosen: Vec<bool>,
+    /// Errors show once the field has been left or submitted.
+    touched: bool,
+    error: Option<String>,
+    check: Check,
+}
+
+impl Field {
+    fn new(name: impl Into<String>, label: impl Into<String>, kind: Kind) -> Self {
+        let chosen = match &kind {
+            Kind::MultiSelect(options) => vec![false; options.len()],
+            _ => Vec::new(),
+        };
+        let input = match kind {
+            Kind::Password => TextInput::new().masked('•'),
+            _ => TextInput::new(),
+        };
+        Self {
+            name: name.into(),
+            label: label.into(),
+            kind,
+            required: false,
+            validators: Vec::new(),
+            async_validator: None,
+            input,
+            checked: false,
+            cursor: None,
+            chosen,
+            touched: false,
+            error: None,
+            check: Check::NotRun,
+        }
+    }
+
+    /// A line of text.
+    pub fn text(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label, Kind::Text)
+    }
+
+    /// A number; anything that doesn't parse as one is an error.
+    pub fn number(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label, Kind::Number)
+    }
+
+    /// A line of text shown as `•`.
+    pub fn password(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label, Kind::Password)
+    }
+
+    /// A checkbox, toggled with Space or a click.
+    pub fn checkbox(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label, Kind::Checkbox)
+    }
+
+    /// One of `options`, cycled with Left/Right or Space.
+    pub fn select<S: Into<String>>(
+        name: impl Into<String>,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let options = options.into_iter().map(Into::into).collect();
+        Self::new(name, label, Kind::Select(options))
+    }
+
+    /// Any of `options`: Left/Right move between them, Space toggles.
+    pub fn multi_select<S: Into<String>>(
+        name: impl Into<String>,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let options = options.into_iter().map(Into::into).collect();
+        Self::new(name, label, Kind::MultiSelect(options))
+    }
+
+    /// Rejects an empty value: blank text, no number, an unticked
+    /// checkbox, or nothing chosen.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Sets dimmed text shown while a text field is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.input = self.input.placeholder(placeholder);
+        self
+    }
+
+    /// Sets the starting value. Select fields take the option's text.
+    pub fn initial(mut self, value: impl Into<Value>) -> Self {
+        self.set_value(value.into());
+        self
+    }
+
+    /// Adds a check run whenever the value changes after the field was
+    /// first left; the `Err` text is shown under the field.
+    pub fn validate(
+        mut self,
+        validator: impl Fn(&Value) -> Result<(), String> + Send + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Adds a check that runs in the background — a server round trip, say
+    /// — when the field is left or the form submitted, once the other
+    /// checks pass. Submission waits for it.
+    pub fn validate_async<F, Fut>(mut self, validator: F) -> Self
+    where
+        F: Fn(Value) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.async_validator = Some(Box::new(move |value| Box::pin(validator(value))));
+        self
+    }
+
+    fn options(&self) -> &[String] {
+        match &self.kind {
+            Kind::Select(options) | Kind::MultiSelect(options) => options,
+            _ => &[],
+        }
+    }
+
+    fn value(&self) -> Value {
+        match &self.kind {
+            Kind::Text | Kind::Password => Value::Text(self.input.value().to_string()),
+            Kind::Number => Value::Number(self.input.value().trim().parse().ok()),
+            Kind::Checkbox => Value::Bool(self.checked),
+            Kind::Select(options) => Value::Choice(self.cursor.map(|i| options[i].clone())),
+            Kind::MultiSelect(options) => Value::Choices(
+                options
+                    .iter()
+                    .zip(&self.chosen)
+                    .filter(|(_, chosen)| **chosen)
+                    .map(|(option, _)| option.clone())
+                    .collect(),
+            ),
+        }
+    }
+
+    fn set_value(&mut self, value: Value) {
+        match (&self.kind, value) {
+            (Kind::Text | Kind::Number | Kind::Password, Value::Text(text)) => {
+                self.input.set_value(text);
+            }
+            (Kind::Text | Kind::Number | Kind::Password, Value::Number(number)) => {
+                self.input
+                    .set_value(number.map(|n| n.to_string()).unwrap_or_default());
+            }
+            (Kind::Checkbox, Value::Bool(checked)) => self.checked = checked,
+            (Kind::Select(options), Value::Text(choice) | Value::Choice(Some(choice))) => {
+                self.cursor = options.iter().position(|option| *option == choice);
+            }
+            (Kind::Select(_), Value::Choice(None)) => self.cursor = None,
+            (Kind::MultiSelect(options), Value::Choices(choices)) => {
+                self.chosen = options
+                    .iter()
+                    .map(|option| choices.contains(option))
+                    .collect();
+            }
+            _ => {}
+        }
+        self.changed();
+    }
+
+    /// Invalidates the async check and, once errors are showing, re-runs
+    /// the synchronous ones.
+    fn changed(&mut self) {
+        self.check = Check::NotRun;
+        if self.touched {
+            self.validate_now();
+        }
+    }
+
+    /// Runs the synchronous checks. Returns whether they passed.
+    fn validate_now(&mut self) -> bool {
+        let value = self.value();
+        self.error = if self.required && value.is_empty() {
+            Some("Required".to_string())
+        } else if matches!(self.kind, Kind::Number)
+            && !self.input.value().trim().is_empty()
+            && value.as_number().is_none()
+        {
+            Some("Must be a number".to_string())
+        } else {
+            self.validators
+                .iter()
+                .find_map(|validator| validator(&value).err())
+        };
+        self.error.is_none()
+    }
+
+    /// The error to show, if any.
+    fn message(&self) -> Option<&str> {
+        if !self.touched {
+            return None;
+        }
+        match (&self.error, &self.check) {
+            (Some(error), _) | (None, Check::Failed(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        let options = self.options().len();
+        let consumed = match &self.kind {
+            Kind::Text | Kind::Number | Kind::Password => {
+                self.input.handle_event(event).is_consumed()
+            }
+            Kind::Checkbox if event.is_key(KeyCode::Char(' ')) => {
+                self.checked = !self.checked;
+                true
+            }
+            Kind::Select(_) if options > 0 => {
+                let last = options - 1;
+                if event.is_key(KeyCode::Right) || event.is_key(KeyCode::Char(' ')) {
+                    self.cursor =
+                        Some(self.cursor.map_or(0, |i| if i == last { 0 } else { i + 1 }));
+                    true
+                } else if event.is_key(KeyCode::Left) {
+                    self.cursor = Some(
+                        self.cursor
+                            .map_or(last, |i| i.checked_sub(1).unwrap_or(last)),
+                    );
+                    true
+                } else {
+                    false
+                }
+            }
+            Kind::MultiSelect(_) if options > 0 => {
+                let at = self.cursor.unwrap_or(0);
+                if event.is_key(KeyCode::Right) && at + 1 < options {
+                    self.cursor = Some(at + 1);
+                    return EventResult::Consumed;
+                } else if event.is_key(KeyCode::Left) && at > 0 {
+                    self.cursor = Some(at - 1);
+                    return EventResult::Consumed;
+                } else if event.is_key(KeyCode::Char(' ')) {
+                    self.chosen[at] = !self.chosen[at];
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        };
+        if consumed {
+            self.changed();
+            EventResult::Consumed
+        } else {
+            EventResult::Propagate
+        }
+    }
+
+    /// Draws the control part of the field into the first row of `area`.
+    fn render_control(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        let dim = Style::default().fg(Color::DarkGray);
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+        let line = match &self.kind {
+            Kind::Text | Kind::Number | Kind::Password => {
+                self.input.render(frame, area, focused);
+                return;
+            }
+            Kind::Checkbox => Line::raw(if self.checked { "[x]" } else { "[ ]" }),
+            Kind::Select(options) => match self.cursor {
+                Some(index) => Line::raw(format!("‹ {} ›", options[index])),
+                None => Line::styled("‹ choose ›", dim),
+            },
+            Kind::MultiSelect(options) => {
+                let cursor = self.cursor.unwrap_or(0);
+                let mut spans = Vec::new();
+                for (index, option) in options.iter().enumerate() {
+                    let mark = if self.chosen[index] { "[x] " } else { "[ ] " };
+                    let style = if focused && index == cursor {
+                        highlight
+                    } else {
+                        Style::default()
+                    };
+                    spans.push(Span::styled(format!("{mark}{option}"), style));
+                    spans.push(Span::raw("  "));
+                }
+                Line::from(spans)
+            }
+        };
+        frame.render_widget(line, Rect { height: 1, ..area });
+    }
+}
+
+/// A form: labelled fields, one per row, and a submit button.
+///
+/// Tab/Down and Shift+Tab/Up move between fields (a click works too),
+/// Enter submits from anywhere. Text, number and password fields edit like
+/// [`TextInput`]; checkboxes toggle with Space; selects cycle with
+/// Left/Right; multi-selects move with Left/Right and toggle with Space.
+/// Esc is left to the parent.
+///
+/// A field's errors appear under it once you leave it, and update as you
+/// fix them. Submitting checks every field — waiting for
+/// [async checks](Field::validate_async) — and either moves focus to the
+/// first error or sends `on_submit(values)` through the [`Context`]:
+///
+/// ```ignore
+/// let form = Form::new(Msg::Submitted)
+///     .field(Field::text("name", "Name").required())
+///     .field(Field::number("age", "Age").validate(|value| match value.as_number() {
+///         Some(age) if age > 150.0 => Err("Really?".into()),
+///         _ => Ok(()),
+///     }))
+///     .field(Field::password("password", "Password").required())
+///     .field(Field::select("plan", "Plan", ["Free", "Pro"]).initial("Free"));
+/// ```
+///
+/// Async results are applied on the next [`Event::Tick`], which returns
+/// [`EventResult::Consumed`] so they show within one tick
+/// (`AppConfig::tick_rate`). A parent must pass ticks on for them to show.
+pub struct Form<M> {
+    fields: Vec<Field>,
+    /// A field index, or `fields.len()` for the submit button.
+    focus: usize,
+    submit_label: String,
+    /// Submitted, waiting for async checks.
+    submitting: bool,
+    generation: u64,
+    results_tx: mpsc::UnboundedSender<CheckResult>,
+    results: mpsc::UnboundedReceiver<CheckResult>,
+    /// Rows scrolled off the top.
+    scroll: u16,
+    /// Where each field (and the button) was drawn, for clicks.
+    hit_areas: Vec<(usize, Rect)>,
+    on_submit: Box<dyn Fn(FormValues) -> M + Send>,
+}
+
+impl<M: Send + 'static> Form<M> {
+    /// Creates an empty form; submitting it sends `on_submit(values)`.
+    pub fn new(on_submit: impl Fn(FormValues) -> M + Send + 'static) -> Self {
+        let (results_tx, results) = mpsc::unbounded_channel();
+        Self {
+            fields: Vec::new(),
+            focus: 0,
+            submit_label: "Submit".to_string(),
+            submitting: false,
+            generation: 0,
+            results_tx,
+            results,
+            scroll: 0,
+            hit_areas: Vec::new(),
+            on_submit: Box::new(on_submit),
+        }
+    }
+
+    /// Appends a field.
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Sets the button text (default "Submit").
+    pub fn submit_label(mut self, label: impl Into<String>) -> Self {
+        self.submit_label = label.into();
+        self
+    }
+
+    /// The current values, valid or not.
+    pub fn values(&self) -> FormValues {
+        FormValues(
+            self.fields
+                .iter()
+                .map(|field| (field.name.clone(), field.value()))
+                .collect(),
+        )
+    }
+
+    /// Sets the value of the field called `name`, as
+    /// [`Field::initial`] does.
+    pub fn set_value(&mut self, name: &str, value: impl Into<Value>) {
+        if let Some(field) = self.fields.iter_mut().find(|field| field.name == name) {
+            field.set_value(value.into());
+        }
+    }
+
+    /// The error shown under the field called `name`.
+    pub fn error(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .and_then(Field::message)
+    }
+
+    /// The name of the focused field; `None` on the submit button.
+    pub fn focused(&self) -> Option<&str> {
+        self.fields.get(self.focus).map(|field| field.name.as_str())
+    }
+
+    /// Moves focus to the field called `name`.
+    pub fn focus(&mut self, name: &str) {
+        if let Some(index) = self.fields.iter().position(|field| field.name == name) {
+            self.move_focus(index);
+        }
+    }
+
+    /// Whether a submission is waiting for async checks.
+    pub fn is_submitting(&self) -> bool {
+        self.submitting
+    }
+
+    /// Leaves the focused field — showing its errors and starting its
+    /// async check — and focuses `index`.
+    fn move_focus(&mut self, index: usize) {
+        if index == self.focus {
+            return;
+        }
+        if self.focus < self.fields.len() {
+            let field = &mut self.fields[self.focus];
+            field.touched = true;
+            if field.validate_now() {
+                self.start_check(self.focus);
+            }
+        }
+        self.focus = index;
+    }
+
+    /// Starts the async check of field `index` unless its current value
+    /// is already checked or being checked.
+    fn start_check(&mut self, index: usize) {
+        let field = &mut self.fields[index];
+        let Some(validator) = &field.async_validator else {
+            return;
+        };
+        if field.check != Check::NotRun {
+            return;
+        }
+        self.generation += 1;
+        field.check = Check::Pending(self.generation);
+        let check = validator(field.value());
+        let (results, generation) = (self.results_tx.clone(), self.generation);
+        tokio::spawn(async move {
+            let _ = results.send((index, generation, check.await));
+        });
+    }
+
+    fn submit(&mut self, context: &Context<M>) {
+        for field in &mut self.fields {
+            field.touched = true;
+            field.validate_now();
+        }
+        if let Some(index) = self.fields.iter().position(|field| field.error.is_some()) {
+            self.submitting = false;
+            self.move_focus(index);
+            return;
+        }
+        for index in 0..self.fields.len() {
+            self.start_check(index);
+        }
+        self.submitting = true;
+        self.finish_submit(context);
+    }
+
+    /// Sends the values once no async check is running, unless one failed.
+    /// Returns whether the submission finished either way.
+    fn finish_submit(&mut self, context: &Context<M>) -> bool {
+        let checks = || self.fields.iter().map(|field| &field.check);
+        if !self.submitting || checks().any(|check| matches!(check, Check::Pending(_))) {
+            return false;
+        }
+        self.submitting = false;
+        if let Some(index) = checks().position(|check| matches!(check, Check::Failed(_))) {
+            self.move_focus(index);
+            return true;
+        }
+        context.deliver((self.on_submit)(self.values()));
+        true
+    }
+
+    /// Applies finished async checks. Returns whether anything shown
+    /// changed.
+    fn collect_results(&mut self, context: &Context<M>) -> bool {
+        let mut changed = false;
+        while let Ok((index, generation, result)) = self.results.try_recv() {
+            let Some(field) = self.fields.get_mut(index) else {
+                continue;
+            };
+            if field.check == Check::Pending(generation) {
+                field.check = match result {
+                    Ok(()) => Check::Passed,
+                    Err(error) => Check::Failed(error),
+                };
+                changed = true;
+            }
+        }
+        self.finish_submit(context) || changed
+    }
+
+    fn handle(&mut self, event: &Event, context: &Context<M>) -> EventResult {
+        if let Some(field) = self.fields.get_mut(self.focus)
+            && field.handle_event(event).is_consumed()
+        {
+            return EventResult::Consumed;
+        }
+
+        let last = self.fields.len();
+        if event.is_key(KeyCode::Tab) || event.is_key(KeyCode::Down) {
+            self.move_focus(if self.focus == last {
+                0
+            } else {
+                self.focus + 1
+            });
+        } else if event.is_key(KeyCode::BackTab) || event.is_key(KeyCode::Up) {
+            self.move_focus(self.focus.checked_sub(1).unwrap_or(last));
+        } else if event.is_key(KeyCode::Enter) {
+            self.submit(context);
+        } else if let Event::Mouse(mouse) = event
+            && mouse.kind == MouseEventKind::Down(MouseButton::Left)
+        {
+            let position = Position::new(mouse.column, mouse.row);
+            let Some(&(index, _)) = self
+                .hit_areas
+                .iter()
+                .find(|(_, area)| area.contains(position))
+            else {
+                return EventResult::Propagate;
+            };
+            self.move_focus(index);
+            match self.fields.get_mut(index) {
+                Some(field) if matches!(field.kind, Kind::Checkbox) => {
+                    field.checked = !field.checked;
+                    field.changed();
+                }
+                None => self.submit(context),
+                _ => {}
+            }
+        } else {
+            return EventResult::Propagate;
+        }
+        EventResult::Consumed
+    }
+}
+
+impl<M: Send + 'static> Component for Form<M> {
+    type Message = M;
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.hit_areas.clear();
+        if area.is_empty() {
+            return;
+        }
+
+        // Each field takes a row, plus one for its message; the button
+        // follows a blank row.
+        let mut rows = Vec::with_capacity(self.fields.len() + 1);
+        let mut y = 0u16;
+        for field in &self.fields {
+            let pending = matches!(field.check, Check::Pending(_));
+            let height = if field.message().is_some() || pending {
+                2
+            } else {
+                1
+            };
+            rows.push((y, height));
+            y += height;
+        }
+        rows.push((y + 1, 1));
+
+        let (top, height) = rows[self.focus];
+        if top < self.scroll {
+            self.scroll = top;
+        } else if top + height > self.scroll + area.height {
+            self.scroll = top + height - area.height;
+        }
+
+        let label_width = self
+            .fields
+            .iter()
+            .map(|field| Line::raw(field.label.as_str()).width() as u16 + 2)
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let visible = |row: u16| (self.scroll..self.scroll + area.height).contains(&row);
+        let at = |row: u16| area.y + row - self.scroll;
+
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            let (top, _) = rows[index];
+            let focused = index == self.focus;
+            if visible(top) {
+                let row = Rect::new(area.x, at(top), area.width, 1);
+                let label_style = if focused {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let marker = if focused { "› " } else { "  " };
+                let required = if field.required { "*" } else { "" };
+                frame.render_widget(
+                    Line::styled(format!("{marker}{}{required}", field.label), label_style),
+                    row,
+                );
+                let control = Rect {
+                    x: row.x + label_width.min(row.width),
+                    width: row.width.saturating_sub(label_width),
+                    ..row
+                };
+                field.render_control(frame, control, focused);
+                self.hit_areas.push((index, row));
+            }
+
+            let message = match (field.message(), &field.check) {
+                (Some(error), _) => Some(Line::styled(
+                    format!("✗ {error}"),
+                    Style::default().fg(Color::Red),
+                )),
+                (None, Check::Pending(_)) => Some(Line::styled(
+                    "checking…",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                _ => None,
+            };
+            if let Some(message) = message
+                && visible(top + 1)
+            {
+                let x = area.x + label_width.min(area.width);
+                let row = Rect::new(x, at(top + 1), area.right() - x, 1);
+                frame.render_widget(message, row);
+            }
+        }
+
+        let (top, _) = rows[self.fields.len()];
+        if visible(top) {
+            let style = if self.focus == self.fields.len() {
+                Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            let button = format!("[ {} ]", self.submit_label);
+            let width = (Line::raw(button.as_str()).width() as u16).min(area.width);
+            let mut line = Line::from(Span::styled(button, style));
+            if self.submitting {
+                line.push_span(Span::styled(
+                    "  checking…",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            let row = Rect::new(
+                area.x + 2.min(area.width),
+                at(top),
+                area.width.saturating_sub(2),
+                1,
+            );
+            frame.render_widget(line, row);
+            self.hit_areas
+                .push((self.fields.len(), Rect { width, ..row }));
+        }
+    }
+
+    fn handle_event(&mut self, event: Event, context: &Context<Self::Message>) -> EventResult {
+        if !matches!(event, Event::Tick(_)) {
+            return self.handle(&event, context);
+        }
+        if self.collect_results(context) {
+            EventResult::Consumed
+        } else {
+            EventResult::Propagate
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, Form, FormValues, Value};
+    use crate::tui::component::{Component, Context};
+    use crate::tui::event::{Event, EventResult};
+    use crossterm::event::KeyCode;
+    use std::time::Duration;
+
+    fn press(form: &mut Form<FormValues>, context: &Context<FormValues>, code: KeyCode) {
+        form.handle_event(Event::key_press(code), context);
+    }
+
+    fn type_text(form: &mut Form<FormValues>, context: &Context<FormValues>, text: &str) {
+        for c in text.chars() {
+            press(form, context, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn shows_errors_after_leaving_a_field_and_blocks_submission() {
+        let (context, mut submitted) = Context::test();
+        let mut form = Form::new(|values| values)
+            .field(Field::text("name", "Name").required())
+            .field(Field::number("age", "Age"));
+
+        press(&mut form, &context, KeyCode::Tab);
+        assert_eq!(form.error("name"), Some("Required"));
+        type_text(&mut form, &context, "4x");
+        assert_eq!(form.error("age"), None, "not left yet");
+
+        press(&mut form, &context, KeyCode::Enter);
+        assert!(submitted.try_recv().is_err());
+        assert_eq!(form.error("age"), Some("Must be a number"));
+        assert_eq!(
+            form.focused(),
+            Some("name"),
+            "focus goes to the first error"
+        );
+
+        type_text(&mut form, &context, "Ada");
+        assert_eq!(form.error("name"), None, "errors clear as you fix them");
+    }
+
+    #[test]
+    fn submits_every_kind_of_field() {
+        let (context, mut submitted) = Context::test();
+        let mut form = Form::new(|values| values)
+            .field(Field::text("name", "Name").initial("Ada"))
+            .field(Field::password("secret", "Password"))
+            .field(Field::checkbox("terms", "Accept terms").required())
+            .field(Field::select("plan", "Plan", ["Free", "Pro", "Team"]).initial("Pro"))
+            .field(Field::multi_select("tags", "Tags", ["a", "b", "c"]));
+
+        press(&mut form, &context, KeyCode::Tab);
+        type_text(&mut form, &context, "hunter2");
+        press(&mut form, &context, KeyCode::Tab);
+        press(&mut form, &context, KeyCode::Char(' '));
+        press(&mut form, &context, KeyCode::Tab);
+        press(&mut form, &context, KeyCode::Right);
+        press(&mut form, &context, KeyCode::Tab);
+        press(&mut form, &context, KeyCode::Char(' '));
+        press(&mut form, &context, KeyCode::Right);
+        press(&mut form, &context, KeyCode::Right);
+        press(&mut form, &context, KeyCode::Char(' '));
+        press(&mut form, &context, KeyCode::Enter);
+
+        let values = submitted.try_recv().unwrap();
+        assert_eq!(values.text("name"), "Ada");
+        assert_eq!(values.text("secret"), "hunter2");
+        assert!(values.checked("terms"));
+        assert_eq!(values.choice("plan"), Some("Team"));
+        assert_eq!(values.choices("tags"), ["a", "c"]);
+        assert_eq!(
+            values.into_map().get("plan"),
+            Some(&Value::Choice(Some("Team".into())))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn submission_waits_for_async_checks() {
+        let (context, mut submitted) = Context::test();
+        let mut form = Form::new(|values| values).field(
+            Field::text("user", "Username").validate_async(|value| async move {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                match value.as_text() {
+                    "admin" => Err("Taken".to_string()),
+                    _ => Ok(()),
+                }
+            }),
+        );
+        let tick = || Event::Tick(Duration::ZERO);
+
+        type_text(&mut form, &context, "admin");
+        press(&mut form, &context, KeyCode::Enter);
+        assert!(form.is_submitting());
+        assert_eq!(form.handle_event(tick(), &context), EventResult::Propagate);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            form.handle_event(tick(), &context),
+            EventResult::Consumed,
+            "the result redraws"
+        );
+        assert!(!form.is_submitting());
+        assert_eq!(form.error("user"), Some("Taken"));
+        assert!(submitted.try_recv().is_err());
+
+        press(&mut form, &context, KeyCode::Char('s'));
+        assert_eq!(form.error("user"), None, "a new value drops the old result");
+        press(&mut form, &context, KeyCode::Enter);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(form.handle_event(tick(), &context), EventResult::Consumed);
+        assert_eq!(submitted.try_recv().unwrap().text("user"), "admins");
+    }
+}
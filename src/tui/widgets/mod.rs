@@ -0,0 +1,32 @@
+//! Ratatui's widgets, plus interactive widgets built on the framework.
+//!
+//! Everything from `ratatui::widgets` is re-exported here unchanged, so
+//! `use tui_base_framework::widgets::{Block, Paragraph}` keeps working. The
+//! framework's own widgets sit alongside them. Like the reusable widgets in
+//! `examples/screens.rs`, they are plain structs rather than components:
+//! `handle_event` takes `&Event` and reports whether it consumed it, and
+//! `render` draws into a `Frame` — so they drop into any component regardless
+//! of its message type.
+//!
+//! [`FilePicker`] and [`Form`] are whole components instead: generic over
+//! the app's message, they report their result by sending one through the
+//! [`Context`](crate::tui::Context).
+
+pub mod data_table;
+pub mod file_picker;
+pub mod form;
+pub mod scroll_view;
+pub mod spinner;
+pub mod text_input;
+pub mod tree_view;
+pub mod virtual_list;
+
+pub use data_table::{DataTable, SortOrder, TableColumn, TableSource};
+pub use file_picker::FilePicker;
+pub use form::{Field, Form, FormValues, Value};
+pub use ratatui::widgets::*;
+pub use scroll_view::ScrollView;
+pub use spinner::{BouncingBar, Spinner};
+pub use text_input::TextInput;
+pub use tree_view::{NodeId, TreeNode, TreeView};
+pub use virtual_list::{ListSource, VirtualList};
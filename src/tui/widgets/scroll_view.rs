@@ -0,0 +1,365 @@
+//! A scrollable viewport over content taller (or wider) than its area.
+
+use crate::tui::event::{Event, EventResult};
+use crossterm::event::{KeyCode, MouseEventKind};
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect, Size};
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget};
+
+/// Rows moved per mouse-wheel notch.
+const WHEEL_STEP: u16 = 3;
+
+/// Scroll state and rendering for content larger than the area it's shown
+/// in.
+///
+/// The content is drawn into an off-screen buffer of its full size, and the
+/// visible window is copied into the frame, with scrollbars on the edges
+/// that overflow. Keep a `ScrollView` in your component, route events to
+/// it, and render through it:
+///
+/// ```ignore
+/// fn render(&mut self, frame: &mut Frame, area: Rect) {
+///     let text = Paragraph::new(self.log.as_str());
+///     let height = self.log.lines().count() as u16;
+///     self.scroll.render(frame, area, Size::new(0, height), |area, buf| {
+///         text.render(area, buf);
+///     });
+/// }
+///
+/// fn handle_event(&mut self, event: Event, context: &Context<Msg>) -> EventResult {
+///     if self.scroll.handle_event(&event).is_consumed() {
+///         return EventResult::Consumed;
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// The whole content is drawn every frame, which is fine for pages of text
+/// and forms, but not for lists with many thousands of rows.
+#[derive(Debug, Clone, Default)]
+pub struct ScrollView {
+    offset: Position,
+    /// Content size and viewport as of the last render; events clamp
+    /// against them so scrolling past the end is a no-op, not a redraw.
+    content: Size,
+    viewport: Rect,
+    /// A row to bring into view on the next render.
+    reveal: Option<u16>,
+}
+
+impl ScrollView {
+    /// Creates a view scrolled to the top-left corner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The content coordinate shown at the viewport's top-left corner.
+    pub fn offset(&self) -> Position {
+        self.offset
+    }
+
+    /// The area the content was last shown in, excluding scrollbars.
+    pub fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
+    /// Scrolls so `offset` is at the viewport's top-left corner, clamped to
+    /// the content.
+    pub fn scroll_to(&mut self, offset: Position) {
+        self.offset = offset;
+        self.clamp();
+    }
+
+    /// Scrolls up by `rows`.
+    pub fn scroll_up(&mut self, rows: u16) {
+        self.scroll_to(Position::new(
+            self.offset.x,
+            self.offset.y.saturating_sub(rows),
+        ));
+    }
+
+    /// Scrolls down by `rows`.
+    pub fn scroll_down(&mut self, rows: u16) {
+        self.scroll_to(Position::new(
+            self.offset.x,
+            self.offset.y.saturating_add(rows),
+        ));
+    }
+
+    /// Scrolls the minimum needed for content row `row` to be visible after
+    /// the next render — the view follows a selection without jumping.
+    pub fn scroll_into_view(&mut self, row: u16) {
+        self.reveal = Some(row);
+    }
+
+    /// Handles Up/Down/Left/Right (one cell), PageUp/PageDown (a viewport),
+    /// Home/End, and the mouse wheel over the viewport.
+    ///
+    /// Returns [`EventResult::Propagate`] when the view couldn't move, so a
+    /// parent can use the key instead (say, to move focus at the top).
+    pub fn handle_event(&mut self, event: &Event) -> EventResult {
+        let before = self.offset;
+        let page = self.viewport.height.saturating_sub(1).max(1);
+
+        match event {
+            Event::Key(key) => match key.code {
+                KeyCode::Up => self.scroll_up(1),
+                KeyCode::Down => self.scroll_down(1),
+                KeyCode::PageUp => self.scroll_up(page),
+                KeyCode::PageDown => self.scroll_down(page),
+                KeyCode::Home => self.scroll_to(Position::new(0, 0)),
+                KeyCode::End => self.scroll_to(Position::new(self.offset.x, u16::MAX)),
+                KeyCode::Left => self.scroll_horizontally(-1),
+                KeyCode::Right => self.scroll_horizontally(1),
+                _ => return EventResult::Propagate,
+            },
+            Event::Mouse(mouse)
+                if self
+                    .viewport
+                    .contains(Position::new(mouse.column, mouse.row)) =>
+            {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => self.scroll_up(WHEEL_STEP),
+                    MouseEventKind::ScrollDown => self.scroll_down(WHEEL_STEP),
+                    MouseEventKind::ScrollLeft => self.scroll_horizontally(-i32::from(WHEEL_STEP)),
+                    MouseEventKind::ScrollRight => self.scroll_horizontally(i32::from(WHEEL_STEP)),
+                    _ => return EventResult::Propagate,
+                }
+            }
+            _ => return EventResult::Propagate,
+        }
+
+        if self.offset == before {
+            EventResult::Propagate
+        } else {
+            EventResult::Consumed
+        }
+    }
+
+    /// Draws `render_content` into an off-screen buffer of `content_size`
+    /// and shows the scrolled window of it in `area`.
+    ///
+    /// A `content_size.width` narrower than the viewport is widened to fill
+    /// it, so `Size::new(0, height)` means "as wide as the view". The closure
+    /// gets the content's full area and buffer, like `Widget::render`.
+    pub fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        content_size: Size,
+        render_content: impl FnOnce(Rect, &mut Buffer),
+    ) {
+        // Scrollbars take a row/column only on the axis that overflows, and
+        // each bar shrinks the view, which can make the other axis overflow
+        // too. One more pass settles it: a vertical bar added second only
+        // narrows a view that already overflows horizontally.
+        let overflows_y = content_size.height > area.height;
+        let overflows_x = content_size.width > area.width.saturating_sub(u16::from(overflows_y));
+        let height = area.height.saturating_sub(u16::from(overflows_x));
+        let overflows_y = content_size.height > height;
+        let width = area.width.saturating_sub(u16::from(overflows_y));
+
+        self.viewport = Rect::new(area.x, area.y, width, height);
+        self.content = Size::new(content_size.width.max(width), content_size.height);
+        if let Some(row) = self.reveal.take() {
+            self.reveal_row(row);
+        }
+        self.clamp();
+
+        let content_area = Rect::new(0, 0, self.content.width, self.content.height);
+        let mut content = Buffer::empty(content_area);
+        render_content(content_area, &mut content);
+
+        let buffer = frame.buffer_mut();
+        for y in 0..self.viewport.height.min(self.content.height) {
+            for x in 0..self.viewport.width {
+                let source = Position::new(self.offset.x + x, self.offset.y + y);
+                let target = Position::new(self.viewport.x + x, self.viewport.y + y);
+                if let (Some(cell), Some(slot)) = (content.cell(source), buffer.cell_mut(target)) {
+                    *slot = cell.clone();
+                }
+            }
+        }
+
+        if overflows_y {
+            let mut state = scrollbar_state(
+                self.offset.y.into(),
+                self.content.height.into(),
+                height.into(),
+            );
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(
+                Rect::new(area.x, area.y, area.width, height),
+                buffer,
+                &mut state,
+            );
+        }
+
+        if overflows_x {
+            let mut state = scrollbar_state(
+                self.offset.x.into(),
+                self.content.width.into(),
+                width.into(),
+            );
+            Scrollbar::new(ScrollbarOrientation::HorizontalBottom)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .render(
+                    Rect::new(area.x, area.y, width, area.height),
+                    buffer,
+                    &mut state,
+                );
+        }
+    }
+
+    fn scroll_horizontally(&mut self, columns: i32) {
+        let x = i32::from(self.offset.x).saturating_add(columns).max(0);
+        self.scroll_to(Position::new(
+            u16::try_from(x).unwrap_or(u16::MAX),
+            self.offset.y,
+        ));
+    }
+
+    fn reveal_row(&mut self, row: u16) {
+        if row < self.offset.y {
+            self.offset.y = row;
+        } else if self.viewport.height > 0 && row >= self.offset.y + self.viewport.height {
+            self.offset.y = row - self.viewport.height + 1;
+        }
+    }
+
+    fn clamp(&mut self) {
+        self.offset.x = self
+            .offset
+            .x
+            .min(self.content.width.saturating_sub(self.viewport.width));
+        self.offset.y = self
+            .offset
+            .y
+            .min(self.content.height.saturating_sub(self.viewport.height));
+    }
+}
+
+/// Scrollbar state whose thumb reaches the end of the track exactly when
+/// the last row (or column, or item) of content is in view.
+pub(super) fn scrollbar_state(offset: usize, content: usize, viewport: usize) -> ScrollbarState {
+    let positions = content.saturating_sub(viewport) + 1;
+    ScrollbarState::new(positions)
+        .viewport_content_length(viewport)
+        .position(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScrollView;
+    use crate::tui::event::{Event, EventResult};
+    use crossterm::event::KeyCode;
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::{Position, Rect, Size};
+    use ratatui::widgets::{Paragraph, Widget};
+    use ratatui::{Frame, Terminal};
+
+    const ROWS: u16 = 20;
+
+    fn numbered_rows() -> String {
+        (0..ROWS).map(|row| format!("row {row}\n")).collect()
+    }
+
+    fn draw(view: &mut ScrollView, terminal: &mut Terminal<TestBackend>) -> Vec<String> {
+        let text = numbered_rows();
+        terminal
+            .draw(|frame: &mut Frame| {
+                view.render(frame, frame.area(), Size::new(0, ROWS), |area, buf| {
+                    Paragraph::new(text.as_str()).render(area, buf);
+                });
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[Position::new(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shows_the_scrolled_window_with_a_scrollbar() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 4)).unwrap();
+        let mut view = ScrollView::new();
+        draw(&mut view, &mut terminal);
+
+        assert_eq!(
+            view.handle_event(&Event::key_press(KeyCode::PageDown)),
+            EventResult::Consumed
+        );
+        let screen = draw(&mut view, &mut terminal);
+
+        assert!(screen[0].starts_with("row 3"), "{screen:?}");
+        assert!(screen[3].starts_with("row 6"), "{screen:?}");
+        // The rightmost column belongs to the scrollbar.
+        assert_eq!(view.viewport(), Rect::new(0, 0, 9, 4));
+    }
+
+    #[test]
+    fn scrolling_past_the_end_propagates() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 4)).unwrap();
+        let mut view = ScrollView::new();
+        draw(&mut view, &mut terminal);
+
+        assert_eq!(
+            view.handle_event(&Event::key_press(KeyCode::End)),
+            EventResult::Consumed
+        );
+        assert_eq!(view.offset(), Position::new(0, ROWS - 4));
+        assert_eq!(
+            view.handle_event(&Event::key_press(KeyCode::Down)),
+            EventResult::Propagate
+        );
+        assert_eq!(
+            view.handle_event(&Event::key_press(KeyCode::Home)),
+            EventResult::Consumed
+        );
+        assert_eq!(view.offset(), Position::new(0, 0));
+    }
+
+    #[test]
+    fn scroll_into_view_moves_the_minimum_distance() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 4)).unwrap();
+        let mut view = ScrollView::new();
+
+        view.scroll_into_view(10);
+        let screen = draw(&mut view, &mut terminal);
+        assert!(screen[3].starts_with("row 10"), "{screen:?}");
+
+        // Already visible: nothing moves.
+        view.scroll_into_view(8);
+        draw(&mut view, &mut terminal);
+        assert_eq!(view.offset().y, 7);
+
+        view.scroll_into_view(2);
+        let screen = draw(&mut view, &mut terminal);
+        assert!(screen[0].starts_with("row 2"), "{screen:?}");
+    }
+
+    #[test]
+    fn a_horizontal_bar_can_make_the_content_overflow_vertically() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 4)).unwrap();
+        let mut view = ScrollView::new();
+
+        // Exactly as tall as the area, but the horizontal bar takes a row.
+        terminal
+            .draw(|frame: &mut Frame| {
+                view.render(frame, frame.area(), Size::new(20, 4), |_, _| {});
+            })
+            .unwrap();
+
+        assert_eq!(view.viewport(), Rect::new(0, 0, 9, 3));
+        assert_eq!(
+            view.handle_event(&Event::key_press(KeyCode::Down)),
+            EventResult::Consumed
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Activity indicators for work of unknown length: spinners and a bouncing
+//! bar, animated from the time carried by [`Event::Tick`].
+
+use crate::tui::event::{Event, EventResult};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use std::time::Duration;
+
+const DOTS: &[&str] = &["   ", ".  ", ".. ", "..."];
+const BRAILLE: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const LINE: &[&str] = &["-", "\\", "|", "/"];
+
+/// A spinner: a short animation cycled by elapsed time, with an optional
+/// label after it.
+///
+/// Feed it every event (or just the ticks) through
+/// [`handle_event`](Self::handle_event). The frame shown is worked out from
+/// the total time elapsed, not from how many ticks arrived, so the speed
+/// stays right at any `tick_rate` and across ticks dropped while the UI was
+/// busy.
+///
+/// ```ignore
+/// let mut spinner = Spinner::braille().label("Connecting…");
+///
+/// // handle_event
+/// if spinner.handle_event(&event).is_consumed() {
+///     return EventResult::Consumed; // the frame changed: redraw
+/// }
+///
+/// // render
+/// spinner.render(frame, area);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    frames: &'static [&'static str],
+    interval: Duration,
+    elapsed: Duration,
+    label: String,
+    style: Style,
+}
+
+impl Spinner {
+    /// A spinner showing `frames` in turn, each for `interval`. With no
+    /// frames, only the label shows.
+    pub fn new(frames: &'static [&'static str], interval: Duration) -> Self {
+        Self {
+            frames,
+            interval,
+            elapsed: Duration::ZERO,
+            label: String::new(),
+            style: Style::default().fg(Color::Cyan),
+        }
+    }
+
+    /// Ellipsis dots filling up: `.`, `..`, `...`.
+    pub fn dots() -> Self {
+        Self::new(DOTS, Duration::from_millis(300))
+    }
+
+    /// A dot circling in a braille cell: `⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏`.
+    pub fn braille() -> Self {
+        Self::new(BRAILLE, Duration::from_millis(80))
+    }
+
+    /// The classic ASCII line: `- \ | /`.
+    pub fn line() -> Self {
+        Self::new(LINE, Duration::from_millis(130))
+    }
+
+    /// Sets text shown after the animation.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the style of the animation (cyan by default).
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Changes how long each frame shows.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Replaces the label.
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
+    /// The frame to show now.
+    pub fn frame(&self) -> &'static str {
+        if self.frames.is_empty() {
+            return "";
+        }
+        let interval = self.interval.as_nanos().max(1);
+        self.frames[(self.elapsed.as_nanos() / interval) as usize % self.frames.len()]
+    }
+
+    /// Moves the animation on by `elapsed`. Returns whether the frame
+    /// changed.
+    pub fn advance(&mut self, elapsed: Duration) -> bool {
+        let before = self.frame();
+        self.elapsed += elapsed;
+        self.frame() != before
+    }
+
+    /// Starts the animation over.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Advances on [`Event::Tick`]. Returns [`EventResult::Consumed`] only
+    /// when the frame changed, so a parent redraws no more than needed.
+    pub fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Tick(elapsed) if self.advance(*elapsed) => EventResult::Consumed,
+            _ => EventResult::Propagate,
+        }
+    }
+
+    /// Draws the frame and label into the first row of `area`.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let mut line = Line::from(Span::styled(self.frame(), self.style));
+        if self.frame().is_empty() {
+            line.push_span(Span::raw(self.label.as_str()));
+        } else if !self.label.is_empty() {
+            line.push_span(Span::raw(format!(" {}", self.label)));
+        }
+        frame.render_widget(line, Rect { height: 1, ..area });
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::braille()
+    }
+}
+
+/// An indeterminate progress bar: a block sliding back and forth along a
+/// track, for work whose length you can't measure.
+///
+/// Like [`Spinner`], its position comes from the total elapsed time, so the
+/// motion stays smooth and on pace whatever the tick rate.
+///
+/// ```ignore
+/// let mut bar = BouncingBar::new().period(Duration::from_secs(2));
+/// bar.handle_event(&event); // in handle_event
+/// bar.render(frame, area);  // in render
+/// ```
+#[derive(Debug, Clone)]
+pub struct BouncingBar {
+    elapsed: Duration,
+    period: Duration,
+    segment: Option<u16>,
+    style: Style,
+    track_style: Style,
+}
+
+impl BouncingBar {
+    /// A bar that crosses and comes back every 1.5 seconds.
+    pub fn new() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            period: Duration::from_millis(1500),
+            segment: None,
+            style: Style::default().fg(Color::Cyan),
+            track_style: Style::default().fg(Color::DarkGray),
+        }
+    }
+
+    /// Sets the time for one trip there and back.
+    pub fn period(mut self, period: Duration) -> Self {
+        self.period = period;
+        self
+    }
+
+    /// Sets the moving block's width in cells (a quarter of the bar by
+    /// default).
+    pub fn segment_width(mut self, width: u16) -> Self {
+        self.segment = Some(width);
+        self
+    }
+
+    /// Sets the style of the moving block.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the track behind it.
+    pub fn track_style(mut self, style: Style) -> Self {
+        self.track_style = style;
+        self
+    }
+
+    /// Moves the block on by `elapsed`.
+    pub fn advance(&mut self, elapsed: Duration) {
+        self.elapsed += elapsed;
+    }
+
+    /// Starts over from the left.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Advances on [`Event::Tick`]; the block moves on every tick, so
+    /// ticks are always [`EventResult::Consumed`].
+    pub fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Tick(elapsed) => {
+                self.advance(*elapsed);
+                EventResult::Consumed
+            }
+            _ => EventResult::Propagate,
+        }
+    }
+
+    /// Where the block sits on a track `width` cells wide: its first cell
+    /// and its width.
+    fn segment(&self, width: u16) -> (u16, u16) {
+        let segment = self.segment.unwrap_or(width / 4).clamp(1, width.max(1));
+        let period = self.period.as_secs_f64().max(f64::EPSILON);
+        let phase = self.elapsed.as_secs_f64() % period / period;
+        // There for the first half of the period, back for the second.
+        let along = if phase < 0.5 {
+            phase * 2.0
+        } else {
+            2.0 - phase * 2.0
+        };
+        let start = (along * f64::from(width - segment)).round() as u16;
+        (start, segment)
+    }
+
+    /// Draws the bar into the first row of `area`.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+        let (start, segment) = self.segment(area.width);
+        let end = start + segment;
+        let line = Line::from(vec![
+            Span::styled("━".repeat(start.into()), self.track_style),
+            Span::styled("━".repeat(segment.into()), self.style),
+            Span::styled("━".repeat((area.width - end).into()), self.track_style),
+        ]);
+        frame.render_widget(line, Rect { height: 1, ..area });
+    }
+}
+
+impl Default for BouncingBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BouncingBar, Spinner};
+    use crate::tui::event::{Event, EventResult};
+    use std::time::Duration;
+
+    fn tick(millis: u64) -> Event {
+        Event::Tick(Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn spinner_frames_follow_elapsed_time_not_tick_count() {
+        let mut steady = Spinner::line();
+        for _ in 0..3 {
+            steady.handle_event(&tick(130));
+        }
+        let mut dropped = Spinner::line();
+        dropped.handle_event(&tick(390));
+        assert_eq!(steady.frame(), "/");
+        assert_eq!(dropped.frame(), steady.frame(), "one late tick catches up");
+
+        assert_eq!(
+            steady.handle_event(&tick(50)),
+            EventResult::Propagate,
+            "same frame, no redraw"
+        );
+        assert_eq!(steady.handle_event(&tick(100)), EventResult::Consumed);
+        assert_eq!(steady.frame(), "-", "wraps around");
+    }
+
+    #[test]
+    fn spinners_without_frames_stay_blank() {
+        let mut spinner = Spinner::new(&[], Duration::from_millis(100));
+        assert_eq!(spinner.handle_event(&tick(250)), EventResult::Propagate);
+        assert_eq!(spinner.frame(), "");
+    }
+
+    #[test]
+    fn bouncing_bar_goes_there_and_back_each_period() {
+        let mut bar = BouncingBar::new()
+            .period(Duration::from_secs(2))
+            .segment_width(4);
+        assert_eq!(bar.segment(20), (0, 4));
+        bar.advance(Duration::from_millis(500));
+        assert_eq!(bar.segment(20), (8, 4), "halfway out");
+        bar.advance(Duration::from_millis(500));
+        assert_eq!(bar.segment(20), (16, 4), "at the far end");
+        bar.advance(Duration::from_millis(1500));
+        assert_eq!(bar.segment(20), (8, 4), "halfway back, one period on");
+        assert_eq!(BouncingBar::new().segment(2), (0, 1), "never empty");
+    }
+}
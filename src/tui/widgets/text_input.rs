@@ -0,0 +1,335 @@
+//! A single-line text editor with a cursor, masking, and suggestions.
+
+use crate::tui::event::{Event, EventResult};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+/// An editable line of text.
+///
+/// Handles typing and paste, Left/Right (Ctrl for whole words), Home/End
+/// (also Ctrl+A/Ctrl+E), Backspace/Delete, Ctrl+W (delete the word before
+/// the cursor), Ctrl+U and Ctrl+K (delete to the start or end). Text wider
+/// than the area scrolls to keep the cursor visible.
+///
+/// A suggestion set with [`set_suggestion`](Self::set_suggestion) is shown
+/// dimmed after the text; Tab (or Right at the end) accepts it.
+///
+/// ```ignore
+/// if self.name.handle_event(&event).is_consumed() {
+///     return EventResult::Consumed;
+/// }
+/// // ...
+/// self.name.render(frame, area, self.focus == Field::Name);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Byte offset of the cursor, always on a char boundary.
+    cursor: usize,
+    placeholder: String,
+    suggestion: Option<String>,
+    mask: Option<char>,
+    /// Display columns scrolled off the left edge.
+    scroll: u16,
+    style: Style,
+}
+
+impl TextInput {
+    /// Creates an empty input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the initial text, with the cursor at its end.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets dimmed text shown while the input is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Shows every character as `mask`, for passwords.
+    pub fn masked(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Sets the style of the text.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The text.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text and moves the cursor to its end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    /// Clears the text.
+    pub fn clear(&mut self) {
+        self.set_value(String::new());
+    }
+
+    /// The cursor position, in characters from the start.
+    pub fn cursor(&self) -> usize {
+        self.value[..self.cursor].chars().count()
+    }
+
+    /// Offers `suggestion` as the continuation of the current text — say,
+    /// the rest of a completed path. `None` removes it.
+    pub fn set_suggestion(&mut self, suggestion: Option<String>) {
+        self.suggestion = suggestion.filter(|suggestion| !suggestion.is_empty());
+    }
+
+    /// Appends the suggestion, if any. Returns whether there was one.
+    pub fn accept_suggestion(&mut self) -> bool {
+        let Some(suggestion) = self.suggestion.take() else {
+            return false;
+        };
+        self.cursor = self.value.len();
+        self.insert(&suggestion);
+        true
+    }
+
+    /// Handles the editing keys listed on [`TextInput`] and pasted text
+    /// (line breaks become spaces).
+    ///
+    /// Returns [`EventResult::Propagate`] for other keys, and for moves
+    /// that go nowhere, so a parent can use them — Left at the start or
+    /// Tab without a suggestion, for instance.
+    pub fn handle_event(&mut self, event: &Event) -> EventResult {
+        let before = (self.value.len(), self.cursor);
+
+        if let Some(c) = event.char() {
+            self.insert(c.encode_utf8(&mut [0; 4]));
+            return EventResult::Consumed;
+        }
+
+        let key = match event {
+            Event::Paste(text) => {
+                let line: String = text
+                    .chars()
+                    .filter(|&c| c != '\r')
+                    .map(|c| if c == '\n' { ' ' } else { c })
+                    .collect();
+                self.insert(&line);
+                return EventResult::Consumed;
+            }
+            Event::Key(key) => key,
+            _ => return EventResult::Propagate,
+        };
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Tab if self.accept_suggestion() => return EventResult::Consumed,
+            KeyCode::Right if self.cursor == self.value.len() && self.accept_suggestion() => {
+                return EventResult::Consumed;
+            }
+            KeyCode::Left if ctrl => self.cursor = self.word_start(),
+            KeyCode::Right if ctrl => self.cursor = self.word_end(),
+            KeyCode::Left => self.cursor = self.previous_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.value.len(),
+            KeyCode::Backspace => self.delete(self.previous_boundary()..self.cursor),
+            KeyCode::Delete => self.delete(self.cursor..self.next_boundary()),
+            KeyCode::Char('w') if ctrl => self.delete(self.word_start()..self.cursor),
+            KeyCode::Char('u') if ctrl => self.delete(0..self.cursor),
+            KeyCode::Char('k') if ctrl => self.delete(self.cursor..self.value.len()),
+            _ => return EventResult::Propagate,
+        }
+
+        if (self.value.len(), self.cursor) == before {
+            EventResult::Propagate
+        } else {
+            EventResult::Consumed
+        }
+    }
+
+    /// Draws the text into the first row of `area`. When `focused`, the
+    /// terminal cursor is placed at the editing position.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        if area.is_empty() {
+            return;
+        }
+
+        let shown = match self.mask {
+            Some(mask) => mask.to_string().repeat(self.value.chars().count()),
+            None => self.value.clone(),
+        };
+        let before_cursor = match self.mask {
+            Some(_) => self.cursor() as u16,
+            None => Line::raw(&self.value[..self.cursor]).width() as u16,
+        };
+
+        // Scroll just enough to keep the cursor (and the cell after it)
+        // inside the area.
+        let last_column = area.width - 1;
+        if before_cursor < self.scroll {
+            self.scroll = before_cursor;
+        } else if before_cursor - self.scroll > last_column {
+            self.scroll = before_cursor - last_column;
+        }
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let line = if shown.is_empty() && self.suggestion.is_none() {
+            Line::from(Span::styled(self.placeholder.as_str(), dim))
+        } else {
+            let mut line = Line::from(Span::styled(shown, self.style));
+            if let Some(suggestion) = &self.suggestion {
+                line.push_span(Span::styled(suggestion.as_str(), dim));
+            }
+            line
+        };
+        frame.render_widget(
+            Paragraph::new(line).scroll((0, self.scroll)),
+            Rect { height: 1, ..area },
+        );
+
+        if focused {
+            frame.set_cursor_position(Position::new(area.x + before_cursor - self.scroll, area.y));
+        }
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.value.insert_str(self.cursor, text);
+        self.cursor += text.len();
+        self.suggestion = None;
+    }
+
+    fn delete(&mut self, range: std::ops::Range<usize>) {
+        self.cursor = range.start;
+        self.value.replace_range(range, "");
+        self.suggestion = None;
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// The start of the word before the cursor, skipping spaces first.
+    fn word_start(&self) -> usize {
+        let before = self.value[..self.cursor].trim_end();
+        before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(index, c)| index + c.len_utf8())
+    }
+
+    /// The end of the word after the cursor, skipping spaces first.
+    fn word_end(&self) -> usize {
+        let after = &self.value[self.cursor..];
+        let skipped = after.len() - after.trim_start().len();
+        after[skipped..]
+            .find(char::is_whitespace)
+            .map_or(self.value.len(), |end| self.cursor + skipped + end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+    use crate::tui::event::{Event, EventResult};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::layout::Position;
+    use ratatui::{Frame, Terminal, backend::TestBackend};
+
+    fn type_text(input: &mut TextInput, text: &str) {
+        for c in text.chars() {
+            input.handle_event(&Event::key_press(KeyCode::Char(c)));
+        }
+    }
+
+    fn ctrl(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL))
+    }
+
+    #[test]
+    fn edits_at_the_cursor_across_multibyte_characters() {
+        let mut input = TextInput::new();
+        type_text(&mut input, "héllo wörld");
+
+        input.handle_event(&ctrl('w'));
+        assert_eq!(input.value(), "héllo ");
+        input.handle_event(&Event::key_press(KeyCode::Home));
+        input.handle_event(&Event::key_press(KeyCode::Right));
+        input.handle_event(&Event::key_press(KeyCode::Delete));
+        assert_eq!(input.value(), "hllo ");
+        assert_eq!(input.cursor(), 1);
+
+        assert_eq!(
+            input.handle_event(&Event::key_press(KeyCode::Home)),
+            EventResult::Consumed
+        );
+        assert_eq!(
+            input.handle_event(&Event::key_press(KeyCode::Left)),
+            EventResult::Propagate,
+            "nowhere to go"
+        );
+        input.handle_event(&ctrl('k'));
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn tab_accepts_a_suggestion_and_typing_drops_it() {
+        let mut input = TextInput::new().with_value("Car");
+        input.set_suggestion(Some("go.toml".into()));
+        assert_eq!(
+            input.handle_event(&Event::key_press(KeyCode::Tab)),
+            EventResult::Consumed
+        );
+        assert_eq!(input.value(), "Cargo.toml");
+        assert_eq!(
+            input.handle_event(&Event::key_press(KeyCode::Tab)),
+            EventResult::Propagate
+        );
+
+        input.set_suggestion(Some("x".into()));
+        type_text(&mut input, "!");
+        assert!(!input.accept_suggestion());
+    }
+
+    #[test]
+    fn scrolls_to_keep_the_cursor_visible() {
+        let mut terminal = Terminal::new(TestBackend::new(5, 1)).unwrap();
+        let mut input = TextInput::new().with_value("abcdefgh").masked('*');
+
+        terminal
+            .draw(|frame: &mut Frame| input.render(frame, frame.area(), true))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let shown: String = (0..5)
+            .map(|x| buffer[Position::new(x, 0)].symbol())
+            .collect();
+        assert_eq!(shown, "**** ", "the last column is left for the cursor");
+        assert_eq!(terminal.get_cursor_position().unwrap(), Position::new(4, 0));
+    }
+}
@@ -0,0 +1,816 @@
+//! An expandable tree with keyboard and mouse navigation and lazily
+//! loaded children.
+
+use super::scroll_view::scrollbar_state;
+use super::spinner::Spinner;
+use crate::tui::component::Context;
+use crate::tui::event::{Event, EventResult};
+use crate::tui::gesture::Gesture;
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use ratatui::Frame;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, StatefulWidget, Widget};
+use std::future::Future;
+
+/// Rows moved per mouse-wheel notch.
+const WHEEL_STEP: usize = 3;
+
+/// Identifies a node in a [`TreeView`]. Stays valid until the node is
+/// replaced by [`TreeView::set_children`] on one of its ancestors; after
+/// that it refers to nothing, even once a new node reuses its slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: usize,
+    generation: u32,
+}
+
+/// A node to add to a [`TreeView`], with its label, a value of your
+/// choosing (a path, a JSON pointer, a package id), and its children.
+#[derive(Debug, Clone)]
+pub struct TreeNode<T> {
+    label: String,
+    value: T,
+    children: NodeChildren<T>,
+}
+
+#[derive(Debug, Clone)]
+enum NodeChildren<T> {
+    Leaf,
+    Known(Vec<TreeNode<T>>),
+    Lazy,
+}
+
+impl<T> TreeNode<T> {
+    /// A node without children.
+    pub fn leaf(label: impl Into<String>, value: T) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            children: NodeChildren::Leaf,
+        }
+    }
+
+    /// A node with the given children.
+    pub fn branch(
+        label: impl Into<String>,
+        value: T,
+        children: impl IntoIterator<Item = TreeNode<T>>,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            children: NodeChildren::Known(children.into_iter().collect()),
+        }
+    }
+
+    /// A node whose children are loaded the first time it's expanded; see
+    /// [`TreeView::spawn_loads`].
+    pub fn lazy(label: impl Into<String>, value: T) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            children: NodeChildren::Lazy,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Children {
+    Leaf,
+    Unloaded,
+    Loading,
+    Failed(String),
+    Loaded(Vec<NodeId>),
+}
+
+/// Storage for one node. A dropped node's slot goes to the next one
+/// inserted, under a new generation so old ids don't match it.
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u32,
+    node: Option<Node<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    label: String,
+    value: T,
+    parent: Option<NodeId>,
+    children: Children,
+    expanded: bool,
+}
+
+/// One visible row: the node and the guide-line prefix drawn before it.
+#[derive(Debug, Clone)]
+struct Row {
+    id: NodeId,
+    guides: String,
+}
+
+/// A tree of labelled nodes, navigated in the order they appear on screen.
+///
+/// Up/Down, PageUp/PageDown, and Home/End move through the visible rows;
+/// Right expands the selected node (or steps into it), Left collapses it
+/// (or steps out to its parent), and Enter or Space toggles it. With mouse
+/// capture, a click selects, a click on the ▸/▾ marker or a double-click
+/// toggles, and the wheel scrolls.
+///
+/// Nodes created with [`TreeNode::lazy`] load their children on first
+/// expand: the node shows a spinner and its id is queued. Hand the queue to
+/// [`spawn_loads`](Self::spawn_loads) after each event, and pass the result
+/// back through your message type:
+///
+/// ```ignore
+/// fn handle_event(&mut self, event: Event, context: &Context<Msg>) -> EventResult {
+///     if self.tree.handle_event(&event).is_consumed() {
+///         self.tree.spawn_loads(context, |id, path: &PathBuf| {
+///             let path = path.clone();
+///             async move { Msg::Loaded(id, read_children(path).await) }
+///         });
+///         return EventResult::Consumed;
+///     }
+///     // ...
+/// }
+///
+/// fn update(&mut self, message: Msg, context: &Context<Msg>) {
+///     match message {
+///         Msg::Loaded(id, Ok(children)) => self.tree.set_children(id, children),
+///         Msg::Loaded(id, Err(error)) => self.tree.set_load_error(id, error.to_string()),
+///     }
+/// }
+/// ```
+///
+/// The spinner advances on [`Event::Tick`], which `handle_event` consumes
+/// while anything is loading so the frame is redrawn.
+#[derive(Debug, Clone)]
+pub struct TreeView<T> {
+    nodes: Vec<Slot<T>>,
+    /// Slots emptied by replaced nodes, reused before the list grows.
+    free: Vec<usize>,
+    roots: Vec<NodeId>,
+    selected: Option<NodeId>,
+    pending: Vec<NodeId>,
+    top: usize,
+    reveal: bool,
+    /// Shown on nodes whose children are loading.
+    spinner: Spinner,
+    highlight_style: Style,
+    /// Layout as of the last render, for the mouse.
+    viewport: Rect,
+    rows: Vec<Row>,
+}
+
+impl<T> TreeView<T> {
+    /// Creates a tree with the given top-level nodes, all collapsed, and
+    /// the first one selected.
+    pub fn new(roots: impl IntoIterator<Item = TreeNode<T>>) -> Self {
+        let mut tree = Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            roots: Vec::new(),
+            selected: None,
+            pending: Vec::new(),
+            top: 0,
+            reveal: false,
+            spinner: Spinner::braille(),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+            viewport: Rect::default(),
+            rows: Vec::new(),
+        };
+        tree.roots = roots
+            .into_iter()
+            .map(|node| tree.insert(node, None))
+            .collect();
+        tree.selected = tree.roots.first().copied();
+        tree
+    }
+
+    /// Sets the style patched over the selected row.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// The selected node.
+    pub fn selected(&self) -> Option<NodeId> {
+        self.selected
+    }
+
+    /// Selects `id` and scrolls it into view, expanding its ancestors.
+    pub fn select(&mut self, id: NodeId) {
+        if self.node(id).is_none() {
+            return;
+        }
+        let mut ancestor = self.parent(id);
+        while let Some(parent) = ancestor {
+            self.expand(parent);
+            ancestor = self.parent(parent);
+        }
+        self.selected = Some(id);
+        self.reveal = true;
+    }
+
+    /// The value stored with `id`.
+    pub fn value(&self, id: NodeId) -> Option<&T> {
+        self.node(id).map(|node| &node.value)
+    }
+
+    /// The label shown for `id`.
+    pub fn label(&self, id: NodeId) -> Option<&str> {
+        self.node(id).map(|node| node.label.as_str())
+    }
+
+    /// The parent of `id`, or `None` for top-level nodes.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id)?.parent
+    }
+
+    /// The loaded children of `id`, in order.
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        match self.node(id).map(|node| &node.children) {
+            Some(Children::Loaded(children)) => children,
+            _ => &[],
+        }
+    }
+
+    /// Whether `id` is expanded.
+    pub fn is_expanded(&self, id: NodeId) -> bool {
+        self.node(id).is_some_and(|node| node.expanded)
+    }
+
+    /// Whether children of `id` are being loaded.
+    pub fn is_loading(&self, id: NodeId) -> bool {
+        matches!(self.node(id).map(|n| &n.children), Some(Children::Loading))
+    }
+
+    /// Expands `id`. A lazy node that hasn't loaded yet (or failed to)
+    /// starts loading: it's queued for [`spawn_loads`](Self::spawn_loads).
+    pub fn expand(&mut self, id: NodeId) {
+        let Some(node) = self.node_mut(id) else {
+            return;
+        };
+        match node.children {
+            Children::Leaf => return,
+            Children::Unloaded | Children::Failed(_) => {
+                node.children = Children::Loading;
+                self.pending.push(id);
+            }
+            Children::Loading | Children::Loaded(_) => {}
+        }
+        if let Some(node) = self.node_mut(id) {
+            node.expanded = true;
+        }
+    }
+
+    /// Collapses `id`. If the selection was inside it, `id` is selected.
+    pub fn collapse(&mut self, id: NodeId) {
+        if let Some(node) = self.node_mut(id) {
+            node.expanded = false;
+        }
+        if let Some(selected) = self.selected
+            && self.is_ancestor(id, selected)
+        {
+            self.selected = Some(id);
+        }
+    }
+
+    /// Expands `id` if it's collapsed, and collapses it otherwise.
+    pub fn toggle(&mut self, id: NodeId) {
+        if self.is_expanded(id) {
+            self.collapse(id);
+        } else {
+            self.expand(id);
+        }
+    }
+
+    /// Replaces the children of `id`, typically with the result of a load.
+    /// The node stops spinning; nodes it had before are dropped, and their
+    /// ids become invalid.
+    pub fn set_children(&mut self, id: NodeId, children: impl IntoIterator<Item = TreeNode<T>>) {
+        if self.node(id).is_none() {
+            return;
+        }
+        if let Some(Children::Loaded(old)) = self.node(id).map(|node| node.children.clone()) {
+            for child in old {
+                self.drop_subtree(child);
+            }
+        }
+        let children = children
+            .into_iter()
+            .map(|child| self.insert(child, Some(id)))
+            .collect();
+        if let Some(node) = self.node_mut(id) {
+            node.children = Children::Loaded(children);
+        }
+        if self
+            .selected
+            .is_some_and(|selected| self.node(selected).is_none())
+        {
+            self.selected = Some(id);
+        }
+    }
+
+    /// Marks a load of `id` as failed: the node shows `error` and collapses.
+    /// Expanding it again retries.
+    pub fn set_load_error(&mut self, id: NodeId, error: impl Into<String>) {
+        if let Some(node) = self.node_mut(id) {
+            node.children = Children::Failed(error.into());
+            node.expanded = false;
+        }
+    }
+
+    /// Takes the nodes that started loading since the last call.
+    pub fn take_pending_loads(&mut self) -> Vec<NodeId> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Starts a task per node waiting for children. `load` gets the node
+    /// and its value and returns a future resolving to a message, which is
+    /// delivered to the component through [`Context::sender`] — where it
+    /// should end up in [`set_children`](Self::set_children) or
+    /// [`set_load_error`](Self::set_load_error).
+    pub fn spawn_loads<M, F, Fut>(&mut self, context: &Context<M>, mut load: F)
+    where
+        M: Send + 'static,
+        F: FnMut(NodeId, &T) -> Fut,
+        Fut: Future<Output = M> + Send + 'static,
+    {
+        for id in self.take_pending_loads() {
+            let Some(value) = self.value(id) else {
+                continue;
+            };
+            let task = load(id, value);
+            let sender = context.sender();
+            tokio::spawn(async move {
+                // The app may have quit in the meantime; nothing to tell.
+                let _ = sender.send(task.await).await;
+            });
+        }
+    }
+
+    /// Handles navigation keys, mouse clicks and the wheel over the tree,
+    /// and ticks while something is loading (for the spinner).
+    ///
+    /// Returns [`EventResult::Propagate`] when nothing changed, so a parent
+    /// can use the key instead.
+    pub fn handle_event(&mut self, event: &Event) -> EventResult {
+        let changed = match event {
+            Event::Key(key) => self.handle_key(key.code),
+            Event::Mouse(mouse) => {
+                let pointer = Position::new(mouse.column, mouse.row);
+                if !self.viewport.contains(pointer) {
+                    return EventResult::Propagate;
+                }
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => self.scroll(-(WHEEL_STEP as isize)),
+                    MouseEventKind::ScrollDown => self.scroll(WHEEL_STEP as isize),
+                    MouseEventKind::Down(MouseButton::Left) => self.click(pointer),
+                    _ => false,
+                }
+            }
+            Event::Gesture(Gesture::Click {
+                button: MouseButton::Left,
+                position,
+                count: 2,
+            }) if self.viewport.contains(*position) => match self.row_at(*position) {
+                Some(row) => {
+                    self.toggle(row.id);
+                    true
+                }
+                None => false,
+            },
+            Event::Tick(_) if self.any_loading() => self.spinner.handle_event(event).is_consumed(),
+            _ => false,
+        };
+
+        if changed {
+            EventResult::Consumed
+        } else {
+            EventResult::Propagate
+        }
+    }
+
+    /// Draws the visible rows with guide lines into `area`.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.rows = self.flatten();
+        let overflows = self.rows.len() > usize::from(area.height);
+        self.viewport = Rect {
+            width: area.width.saturating_sub(u16::from(overflows)),
+            ..area
+        };
+
+        let height = usize::from(area.height);
+        if self.reveal
+            && let Some(position) = self.selected_position()
+        {
+            if position < self.top {
+                self.top = position;
+            } else if height > 0 && position >= self.top + height {
+                self.top = position + 1 - height;
+            }
+        }
+        self.reveal = false;
+        self.top = self.top.min(self.rows.len().saturating_sub(height));
+
+        let guide_style = Style::default().fg(Color::DarkGray);
+        let spinner = self.spinner.frame();
+        let buffer = frame.buffer_mut();
+        for (offset, row) in self.rows.iter().skip(self.top).take(height).enumerate() {
+            let Some(node) = self.node(row.id) else {
+                continue;
+            };
+            let marker = match &node.children {
+                Children::Leaf => Span::raw("  "),
+                Children::Loading => Span::styled(format!("{spinner} "), Color::Cyan),
+                _ if node.expanded => Span::raw("▾ "),
+                _ => Span::raw("▸ "),
+            };
+            let mut line = Line::from(vec![
+                Span::styled(row.guides.as_str(), guide_style),
+                marker,
+                Span::raw(node.label.as_str()),
+            ]);
+            if let Children::Failed(error) = &node.children {
+                line.push_span(Span::styled(format!("  ({error})"), Color::Red));
+            }
+
+            let area = Rect::new(
+                self.viewport.x,
+                self.viewport.y + offset as u16,
+                self.viewport.width,
+                1,
+            );
+            line.render(area, buffer);
+            if self.selected == Some(row.id) {
+                buffer.set_style(area, self.highlight_style);
+            }
+        }
+
+        if overflows {
+            let mut state = scrollbar_state(self.top, self.rows.len(), height);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(area, buffer, &mut state);
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        let rows = self.flatten();
+        let Some(selected) = self.selected else {
+            return false;
+        };
+        let position = rows
+            .iter()
+            .position(|row| row.id == selected)
+            .unwrap_or_default();
+        let last = rows.len().saturating_sub(1);
+        let page = usize::from(self.viewport.height.saturating_sub(1).max(1));
+
+        let target = match code {
+            KeyCode::Up => position.saturating_sub(1),
+            KeyCode::Down => (position + 1).min(last),
+            KeyCode::PageUp => position.saturating_sub(page),
+            KeyCode::PageDown => (position + page).min(last),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            KeyCode::Right => {
+                if !self.is_expanded(selected) {
+                    let leaf = matches!(
+                        self.node(selected).map(|n| &n.children),
+                        Some(Children::Leaf)
+                    );
+                    self.expand(selected);
+                    return !leaf;
+                }
+                match self.children(selected).first() {
+                    Some(&child) => return self.move_to(child),
+                    None => return false,
+                }
+            }
+            KeyCode::Left => {
+                if self.is_expanded(selected) {
+                    self.collapse(selected);
+                    return true;
+                }
+                match self.parent(selected) {
+                    Some(parent) => return self.move_to(parent),
+                    None => return false,
+                }
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let leaf = matches!(
+                    self.node(selected).map(|n| &n.children),
+                    Some(Children::Leaf)
+                );
+                self.toggle(selected);
+                return !leaf;
+            }
+            _ => return false,
+        };
+
+        match rows.get(target) {
+            Some(row) if target != position => self.move_to(row.id),
+            _ => false,
+        }
+    }
+
+    fn move_to(&mut self, id: NodeId) -> bool {
+        self.selected = Some(id);
+        self.reveal = true;
+        true
+    }
+
+    fn click(&mut self, pointer: Position) -> bool {
+        let Some(row) = self.row_at(pointer).cloned() else {
+            return false;
+        };
+        // The ▸/▾ marker sits right after the guides.
+        let marker_x = self.viewport.x + Line::raw(row.guides.as_str()).width() as u16;
+        if pointer.x == marker_x {
+            self.toggle(row.id);
+        }
+        self.selected = Some(row.id);
+        true
+    }
+
+    fn scroll(&mut self, rows: isize) -> bool {
+        let limit = self.rows.len().saturating_sub(self.viewport.height.into());
+        let top = self.top.saturating_add_signed(rows).min(limit);
+        let moved = top != self.top;
+        self.top = top;
+        moved
+    }
+
+    fn row_at(&self, pointer: Position) -> Option<&Row> {
+        let offset = usize::from(pointer.y.checked_sub(self.viewport.y)?);
+        self.rows.get(self.top + offset)
+    }
+
+    fn selected_position(&self) -> Option<usize> {
+        let selected = self.selected?;
+        self.rows.iter().position(|row| row.id == selected)
+    }
+
+    fn any_loading(&self) -> bool {
+        self.nodes
+            .iter()
+            .filter_map(|slot| slot.node.as_ref())
+            .any(|node| matches!(node.children, Children::Loading))
+    }
+
+    /// The visible rows in display order: every root, and the children of
+    /// every expanded node, depth first.
+    fn flatten(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for &root in &self.roots {
+            self.flatten_into(root, String::new(), &mut rows);
+        }
+        rows
+    }
+
+    fn flatten_into(&self, id: NodeId, guides: String, rows: &mut Vec<Row>) {
+        rows.push(Row {
+            id,
+            guides: guides.clone(),
+        });
+        if !self.is_expanded(id) {
+            return;
+        }
+
+        // Children continue this node's guides: a bar below a node with
+        // more siblings after it, blank space below the last one.
+        let inherited = guides.replace("├─ ", "│  ").replace("└─ ", "   ");
+        let children = self.children(id);
+        for (index, &child) in children.iter().enumerate() {
+            let connector = if index + 1 == children.len() {
+                "└─ "
+            } else {
+                "├─ "
+            };
+            self.flatten_into(child, format!("{inherited}{connector}"), rows);
+        }
+    }
+
+    fn is_ancestor(&self, ancestor: NodeId, id: NodeId) -> bool {
+        let mut current = self.parent(id);
+        while let Some(parent) = current {
+            if parent == ancestor {
+                return true;
+            }
+            current = self.parent(parent);
+        }
+        false
+    }
+
+    fn insert(&mut self, node: TreeNode<T>, parent: Option<NodeId>) -> NodeId {
+        let index = self.free.pop().unwrap_or_else(|| {
+            self.nodes.push(Slot {
+                generation: 0,
+                node: None,
+            });
+            self.nodes.len() - 1
+        });
+        let id = NodeId {
+            index,
+            generation: self.nodes[index].generation,
+        };
+        let (children, known) = match node.children {
+            NodeChildren::Leaf => (Children::Leaf, Vec::new()),
+            NodeChildren::Lazy => (Children::Unloaded, Vec::new()),
+            NodeChildren::Known(children) => (Children::Loaded(Vec::new()), children),
+        };
+        self.nodes[index].node = Some(Node {
+            label: node.label,
+            value: node.value,
+            parent,
+            children,
+            expanded: false,
+        });
+
+        let ids: Vec<NodeId> = known
+            .into_iter()
+            .map(|child| self.insert(child, Some(id)))
+            .collect();
+        if let Some(node) = self.node_mut(id)
+            && let Children::Loaded(children) = &mut node.children
+        {
+            *children = ids;
+        }
+        id
+    }
+
+    fn drop_subtree(&mut self, id: NodeId) {
+        for child in self.children(id).to_vec() {
+            self.drop_subtree(child);
+        }
+        self.pending.retain(|&pending| pending != id);
+        if let Some(slot) = self.nodes.get_mut(id.index)
+            && slot.generation == id.generation
+        {
+            slot.node = None;
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free.push(id.index);
+        }
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node<T>> {
+        let slot = self.nodes.get(id.index)?;
+        slot.node
+            .as_ref()
+            .filter(|_| slot.generation == id.generation)
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node<T>> {
+        let slot = self.nodes.get_mut(id.index)?;
+        let current = slot.generation == id.generation;
+        slot.node.as_mut().filter(|_| current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TreeNode, TreeView};
+    use crate::tui::component::Context;
+    use crate::tui::event::{Event, EventResult};
+    use crossterm::event::KeyCode;
+    use ratatui::layout::Position;
+    use ratatui::{Frame, Terminal, backend::TestBackend};
+
+    fn tree() -> TreeView<&'static str> {
+        TreeView::new([
+            TreeNode::branch(
+                "src",
+                "src",
+                [
+                    TreeNode::branch(
+                        "tui",
+                        "src/tui",
+                        [TreeNode::leaf("app.rs", "src/tui/app.rs")],
+                    ),
+                    TreeNode::leaf("lib.rs", "src/lib.rs"),
+                ],
+            ),
+            TreeNode::lazy("target", "target"),
+        ])
+    }
+
+    fn draw<T>(tree: &mut TreeView<T>, terminal: &mut Terminal<TestBackend>) -> Vec<String> {
+        terminal
+            .draw(|frame: &mut Frame| tree.render(frame, frame.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[Position::new(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn press(tree: &mut TreeView<&'static str>, code: KeyCode) -> EventResult {
+        tree.handle_event(&Event::key_press(code))
+    }
+
+    fn selected_label<'a>(tree: &'a TreeView<&'static str>) -> &'a str {
+        tree.label(tree.selected().expect("selection"))
+            .expect("node")
+    }
+
+    #[test]
+    fn navigation_follows_the_visible_order() {
+        let mut tree = tree();
+
+        assert_eq!(press(&mut tree, KeyCode::Right), EventResult::Consumed);
+        press(&mut tree, KeyCode::Down);
+        assert_eq!(selected_label(&tree), "tui");
+        press(&mut tree, KeyCode::Down);
+        assert_eq!(selected_label(&tree), "lib.rs", "tui is collapsed");
+
+        press(&mut tree, KeyCode::Up);
+        press(&mut tree, KeyCode::Right);
+        press(&mut tree, KeyCode::Right);
+        assert_eq!(
+            selected_label(&tree),
+            "app.rs",
+            "Right steps into an open node"
+        );
+
+        press(&mut tree, KeyCode::Left);
+        assert_eq!(selected_label(&tree), "tui", "Left steps out of a leaf");
+        press(&mut tree, KeyCode::Left);
+        assert!(!tree.is_expanded(tree.selected().unwrap()));
+        assert_eq!(press(&mut tree, KeyCode::End), EventResult::Consumed);
+        assert_eq!(selected_label(&tree), "target");
+        assert_eq!(press(&mut tree, KeyCode::Down), EventResult::Propagate);
+    }
+
+    #[test]
+    fn draws_guide_lines_and_markers() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let mut tree = tree();
+        press(&mut tree, KeyCode::Right);
+        press(&mut tree, KeyCode::Down);
+        press(&mut tree, KeyCode::Right);
+
+        assert_eq!(
+            draw(&mut tree, &mut terminal),
+            [
+                "▾ src",
+                "├─ ▾ tui",
+                "│  └─   app.rs",
+                "└─   lib.rs",
+                "▸ target",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn lazy_nodes_spin_until_their_children_arrive() {
+        let (context, mut messages) = Context::test();
+        let mut tree = tree();
+        press(&mut tree, KeyCode::End);
+        let target = tree.selected().unwrap();
+
+        press(&mut tree, KeyCode::Right);
+        assert!(tree.is_loading(target));
+        tree.spawn_loads(&context, |id, path: &&str| {
+            let listing = format!("{path}/debug");
+            async move { (id, listing) }
+        });
+        assert!(tree.take_pending_loads().is_empty(), "spawned once");
+        assert_eq!(
+            tree.handle_event(&Event::Tick(std::time::Duration::from_millis(100))),
+            EventResult::Consumed,
+            "ticks redraw the spinner"
+        );
+
+        let (id, listing) = messages.recv().await.expect("load result");
+        tree.set_children(id, [TreeNode::leaf(listing, "")]);
+        assert!(!tree.is_loading(target));
+        press(&mut tree, KeyCode::Right);
+        assert_eq!(selected_label(&tree), "target/debug");
+        assert_eq!(
+            tree.handle_event(&Event::Tick(std::time::Duration::from_millis(100))),
+            EventResult::Propagate
+        );
+    }
+
+    #[test]
+    fn reloads_reuse_slots_without_reviving_old_ids() {
+        let mut tree = tree();
+        let src = tree.roots[0];
+        let old = tree.children(src)[0];
+
+        for _ in 0..3 {
+            tree.set_children(src, [TreeNode::leaf("main.rs", "src/main.rs")]);
+        }
+
+        assert_eq!(tree.nodes.len(), 5, "no slot left behind per reload");
+        assert_eq!(tree.label(old), None);
+        assert_eq!(tree.label(tree.children(src)[0]), Some("main.rs"));
+    }
+}
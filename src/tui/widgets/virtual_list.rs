@@ -0,0 +1,531 @@
+//! A selectable list that only asks its data for the rows on screen.
+
+use super::scroll_view::scrollbar_state;
+use crate::tui::event::{Event, EventResult};
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, StatefulWidget, Widget};
+
+/// Rows moved per mouse-wheel notch.
+const WHEEL_STEP: u16 = 3;
+
+/// The items behind a [`VirtualList`].
+///
+/// The list only calls [`height`](ListSource::height) and
+/// [`render`](ListSource::render) for items on or next to the screen, so a
+/// source can hold millions of rows, or compute them on demand.
+pub trait ListSource {
+    /// The number of items.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The height of item `index` in rows. Values below 1 are treated as 1.
+    fn height(&self, index: usize) -> u16 {
+        let _ = index;
+        1
+    }
+
+    /// Draws item `index` into `area`, which is the item's full
+    /// `height` × list width even when only part of it is on screen.
+    fn render(&self, index: usize, area: Rect, buf: &mut Buffer);
+}
+
+/// Each item is one line of text.
+impl<T: AsRef<str>> ListSource for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn render(&self, index: usize, area: Rect, buf: &mut Buffer) {
+        Line::raw(self[index].as_ref()).render(area, buf);
+    }
+}
+
+/// Selection and scroll state for a list of any length.
+///
+/// Unlike Ratatui's `List`, which is built from every item each frame, a
+/// `VirtualList` asks a [`ListSource`] for the items in view only. Items may
+/// be taller than one row; the view scrolls by rows, so a tall item can be
+/// partly on screen.
+///
+/// Keep a `VirtualList` in your component next to the data, and pass the
+/// data to it for events and rendering:
+///
+/// ```ignore
+/// fn render(&mut self, frame: &mut Frame, area: Rect) {
+///     self.list.render(frame, area, self.rows.as_slice());
+/// }
+///
+/// fn handle_event(&mut self, event: Event, context: &Context<Msg>) -> EventResult {
+///     if self.list.handle_event(&event, self.rows.as_slice()).is_consumed() {
+///         return EventResult::Consumed;
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// When items are added or removed above the selection, report it with
+/// [`insert`](Self::insert) and [`remove`](Self::remove) so the selection
+/// and the rows on screen stay put.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualList {
+    selected: Option<usize>,
+    /// The first item in view, and how many of its rows are scrolled off.
+    top: usize,
+    top_row: u16,
+    highlight_style: Style,
+    /// Bring the selection into view on the next render.
+    reveal: bool,
+    /// Layout as of the last render, for wheel scrolling and clicks.
+    viewport: Rect,
+    visible: Vec<(usize, Rect)>,
+}
+
+impl VirtualList {
+    /// Creates a list with nothing selected, scrolled to the top.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the style patched over the selected item.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// The selected item's index.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Selects item `index` (clamped to the list on the next render) and
+    /// scrolls it into view.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = index;
+        self.reveal = true;
+    }
+
+    /// The index of the first item in view.
+    pub fn offset(&self) -> usize {
+        self.top
+    }
+
+    /// The area the items were last drawn in, excluding the scrollbar.
+    pub fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
+    /// Records that `count` items were inserted at `index`. The selection
+    /// keeps pointing at the same item, and items inserted above the view
+    /// don't push the visible ones down.
+    pub fn insert(&mut self, index: usize, count: usize) {
+        if let Some(selected) = &mut self.selected
+            && *selected >= index
+        {
+            *selected += count;
+        }
+        if self.top >= index {
+            self.top += count;
+        }
+    }
+
+    /// Records that `count` items starting at `index` were removed. A
+    /// removed selection moves to the item that took its place.
+    pub fn remove(&mut self, index: usize, count: usize) {
+        let shift = |position: usize| {
+            if position >= index + count {
+                position - count
+            } else {
+                position.min(index)
+            }
+        };
+        self.selected = self.selected.map(shift);
+        if self.top >= index {
+            self.top = shift(self.top);
+            self.top_row = 0;
+        }
+    }
+
+    /// Scrolls the view down by `rows` without moving the selection.
+    pub fn scroll_down<S: ListSource + ?Sized>(&mut self, rows: u16, source: &S) {
+        let mut rows = rows;
+        while rows > 0 && self.top < source.len() {
+            let height = item_height(source, self.top);
+            if self.top_row.saturating_add(rows) < height {
+                self.top_row += rows;
+                break;
+            }
+            rows -= height.saturating_sub(self.top_row);
+            self.top += 1;
+            self.top_row = 0;
+        }
+        self.clamp(source);
+    }
+
+    /// Scrolls the view up by `rows` without moving the selection.
+    pub fn scroll_up<S: ListSource + ?Sized>(&mut self, rows: u16, source: &S) {
+        let mut rows = rows;
+        while rows > 0 {
+            if self.top_row >= rows {
+                self.top_row -= rows;
+                break;
+            }
+            rows -= self.top_row;
+            self.top_row = 0;
+            if self.top == 0 {
+                break;
+            }
+            self.top -= 1;
+            self.top_row = item_height(source, self.top);
+        }
+    }
+
+    /// Handles Up/Down (one item), PageUp/PageDown (a screenful),
+    /// Home/End, the mouse wheel over the list (scrolls without selecting),
+    /// and left clicks on items (selects).
+    ///
+    /// Returns [`EventResult::Propagate`] when nothing changed, so a parent
+    /// can use the key instead.
+    pub fn handle_event<S: ListSource + ?Sized>(
+        &mut self,
+        event: &Event,
+        source: &S,
+    ) -> EventResult {
+        let len = source.len();
+        let before = (self.selected, self.top, self.top_row);
+
+        match event {
+            Event::Key(key) if len > 0 => {
+                let last = len - 1;
+                let page = self.visible.len().saturating_sub(1).max(1);
+                let selected = match (key.code, self.selected) {
+                    (KeyCode::Up, Some(index)) => index.saturating_sub(1),
+                    (KeyCode::Down, Some(index)) => index.saturating_add(1).min(last),
+                    (KeyCode::PageUp, Some(index)) => index.saturating_sub(page),
+                    (KeyCode::PageDown, Some(index)) => index.saturating_add(page).min(last),
+                    (KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown, None) => {
+                        self.top.min(last)
+                    }
+                    (KeyCode::Home, _) => 0,
+                    (KeyCode::End, _) => last,
+                    _ => return EventResult::Propagate,
+                };
+                self.select(Some(selected));
+            }
+            Event::Mouse(mouse) => {
+                let pointer = Position::new(mouse.column, mouse.row);
+                if !self.viewport.contains(pointer) {
+                    return EventResult::Propagate;
+                }
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => self.scroll_up(WHEEL_STEP, source),
+                    MouseEventKind::ScrollDown => self.scroll_down(WHEEL_STEP, source),
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        let clicked = self
+                            .visible
+                            .iter()
+                            .find(|(_, area)| area.contains(pointer))
+                            .map(|&(index, _)| index);
+                        if clicked.is_some() {
+                            self.select(clicked);
+                        }
+                    }
+                    _ => return EventResult::Propagate,
+                }
+            }
+            _ => return EventResult::Propagate,
+        }
+
+        if (self.selected, self.top, self.top_row) == before {
+            EventResult::Propagate
+        } else {
+            EventResult::Consumed
+        }
+    }
+
+    /// Draws the items in view into `area`, with a scrollbar when they
+    /// don't all fit.
+    pub fn render<S: ListSource + ?Sized>(&mut self, frame: &mut Frame, area: Rect, source: &S) {
+        let len = source.len();
+        self.selected = self
+            .selected
+            .filter(|_| len > 0)
+            .map(|index| index.min(len - 1));
+
+        let overflows = self.top > 0 || self.top_row > 0 || !fits(source, area.height);
+        self.viewport = Rect {
+            width: area.width.saturating_sub(u16::from(overflows)),
+            ..area
+        };
+        if self.reveal {
+            self.reveal = false;
+            if let Some(selected) = self.selected {
+                self.reveal_item(selected, source);
+            }
+        }
+        self.clamp(source);
+
+        self.visible.clear();
+        let buffer = frame.buffer_mut();
+        let mut y = -i32::from(self.top_row);
+        let mut index = self.top;
+        while index < len && y < i32::from(self.viewport.height) {
+            let height = item_height(source, index);
+            let item = Rect::new(0, 0, self.viewport.width, height);
+            let mut scratch = Buffer::empty(item);
+            source.render(index, item, &mut scratch);
+
+            // Copy the rows of the item that land inside the viewport.
+            let first = (-y).max(0) as u16;
+            let last = (i32::from(self.viewport.height) - y).min(i32::from(height)) as u16;
+            let screen_y = self.viewport.y + (y + i32::from(first)) as u16;
+            for row in first..last {
+                for x in 0..self.viewport.width {
+                    let target = Position::new(self.viewport.x + x, screen_y + row - first);
+                    if let (Some(cell), Some(slot)) =
+                        (scratch.cell(Position::new(x, row)), buffer.cell_mut(target))
+                    {
+                        *slot = cell.clone();
+                    }
+                }
+            }
+
+            let shown = Rect::new(self.viewport.x, screen_y, self.viewport.width, last - first);
+            if self.selected == Some(index) {
+                buffer.set_style(shown, self.highlight_style);
+            }
+            self.visible.push((index, shown));
+            y += i32::from(height);
+            index += 1;
+        }
+
+        if overflows {
+            let mut state = scrollbar_state(self.top, len, self.visible.len());
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(area, buffer, &mut state);
+        }
+    }
+
+    /// Scrolls the minimum needed to show all of item `index`, or its top
+    /// rows if it is taller than the viewport.
+    fn reveal_item<S: ListSource + ?Sized>(&mut self, index: usize, source: &S) {
+        let viewport = self.viewport.height;
+        if index < self.top || (index == self.top && self.top_row > 0) {
+            self.top = index;
+            self.top_row = 0;
+            return;
+        }
+
+        // Rows from the top of the view to the bottom of the item, stopping
+        // early once it's clearly below the fold.
+        let mut bottom = -i32::from(self.top_row);
+        for candidate in self.top..=index {
+            bottom += i32::from(item_height(source, candidate));
+            if bottom > i32::from(viewport) {
+                break;
+            }
+        }
+        if bottom <= i32::from(viewport) {
+            return;
+        }
+
+        if item_height(source, index) >= viewport {
+            self.top = index;
+            self.top_row = 0;
+        } else {
+            (self.top, self.top_row) = top_ending_at(source, index, viewport);
+        }
+    }
+
+    /// Keeps the view from scrolling past the last item.
+    fn clamp<S: ListSource + ?Sized>(&mut self, source: &S) {
+        let limit = top_ending_at(source, source.len().saturating_sub(1), self.viewport.height);
+        if (self.top, self.top_row) > limit {
+            (self.top, self.top_row) = limit;
+        }
+    }
+}
+
+fn item_height<S: ListSource + ?Sized>(source: &S, index: usize) -> u16 {
+    source.height(index).max(1)
+}
+
+/// Whether every item fits in `rows` rows.
+fn fits<S: ListSource + ?Sized>(source: &S, rows: u16) -> bool {
+    let mut used = 0u32;
+    for index in 0..source.len() {
+        used += u32::from(item_height(source, index));
+        if used > u32::from(rows) {
+            return false;
+        }
+    }
+    true
+}
+
+/// The scroll position that puts the bottom of item `last` on the bottom
+/// row of a `rows`-tall view, or the very top if everything above fits.
+fn top_ending_at<S: ListSource + ?Sized>(source: &S, last: usize, rows: u16) -> (usize, u16) {
+    if source.is_empty() {
+        return (0, 0);
+    }
+    let mut used = 0u16;
+    for index in (0..=last).rev() {
+        used = used.saturating_add(item_height(source, index));
+        if used >= rows {
+            return (index, used - rows);
+        }
+    }
+    (0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ListSource, VirtualList};
+    use crate::tui::event::{Event, EventResult};
+    use crossterm::event::{KeyCode, KeyModifiers, MouseEvent, MouseEventKind};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::{Position, Rect};
+    use ratatui::text::Line;
+    use ratatui::widgets::Widget;
+    use ratatui::{Frame, Terminal, backend::TestBackend};
+
+    /// A million items, every third one two rows tall; nothing is stored.
+    struct Numbers;
+
+    impl ListSource for Numbers {
+        fn len(&self) -> usize {
+            1_000_000
+        }
+
+        fn height(&self, index: usize) -> u16 {
+            if index % 3 == 2 { 2 } else { 1 }
+        }
+
+        fn render(&self, index: usize, area: Rect, buf: &mut Buffer) {
+            Line::raw(format!("item {index}")).render(area, buf);
+        }
+    }
+
+    fn draw<S: ListSource + ?Sized>(
+        list: &mut VirtualList,
+        terminal: &mut Terminal<TestBackend>,
+        source: &S,
+    ) -> Vec<String> {
+        terminal
+            .draw(|frame: &mut Frame| list.render(frame, frame.area(), source))
+            .unwrap();
+
+        // The viewport only: the scrollbar is Ratatui's business.
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..list.viewport().width)
+                    .map(|x| buffer[Position::new(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn wheel_down() -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn follows_the_selection_through_variable_heights() {
+        let mut terminal = Terminal::new(TestBackend::new(12, 4)).unwrap();
+        let mut list = VirtualList::new();
+        list.select(Some(0));
+        draw(&mut list, &mut terminal, &Numbers);
+
+        for _ in 0..4 {
+            list.handle_event(&Event::key_press(KeyCode::Down), &Numbers);
+        }
+        let screen = draw(&mut list, &mut terminal, &Numbers);
+
+        // Items 2 and 5 are two rows tall: item 4 ends on the bottom row.
+        assert_eq!(list.selected(), Some(4));
+        assert_eq!(screen[3], "item 4");
+        assert_eq!(screen[0], "item 2");
+        assert_eq!(screen[1], "");
+    }
+
+    #[test]
+    fn wheel_scrolls_by_rows_and_stops_at_the_end() {
+        let mut terminal = Terminal::new(TestBackend::new(12, 4)).unwrap();
+        let items = ["a", "b", "c", "d", "e", "f"];
+        let mut list = VirtualList::new();
+        draw(&mut list, &mut terminal, items.as_slice());
+
+        assert_eq!(
+            list.handle_event(&wheel_down(), items.as_slice()),
+            EventResult::Consumed
+        );
+        let screen = draw(&mut list, &mut terminal, items.as_slice());
+        assert_eq!(screen[0], "c");
+        assert_eq!(list.selected(), None, "the wheel doesn't select");
+
+        assert_eq!(
+            list.handle_event(&wheel_down(), items.as_slice()),
+            EventResult::Propagate
+        );
+    }
+
+    #[test]
+    fn insertions_above_keep_the_selection_and_view_in_place() {
+        let mut terminal = Terminal::new(TestBackend::new(12, 3)).unwrap();
+        let mut items: Vec<String> = (0..10).map(|n| format!("row {n}")).collect();
+        let mut list = VirtualList::new();
+        list.select(Some(5));
+        let before = draw(&mut list, &mut terminal, items.as_slice());
+
+        items.splice(0..0, ["new 1".to_string(), "new 2".to_string()]);
+        list.insert(0, 2);
+        let after = draw(&mut list, &mut terminal, items.as_slice());
+
+        assert_eq!(list.selected(), Some(7));
+        assert_eq!(before, after);
+
+        list.remove(0, 2);
+        assert_eq!(list.selected(), Some(5));
+    }
+
+    #[test]
+    fn scrolling_far_inside_a_tall_item_does_not_overflow() {
+        struct Tall;
+
+        impl ListSource for Tall {
+            fn len(&self) -> usize {
+                2
+            }
+
+            fn height(&self, _index: usize) -> u16 {
+                60_000
+            }
+
+            fn render(&self, _index: usize, _area: Rect, _buf: &mut Buffer) {}
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(12, 4)).unwrap();
+        let mut list = VirtualList::new();
+        draw(&mut list, &mut terminal, &Tall);
+
+        list.scroll_down(10, &Tall);
+        list.scroll_down(u16::MAX, &Tall);
+
+        assert_eq!(list.offset(), 1);
+    }
+}